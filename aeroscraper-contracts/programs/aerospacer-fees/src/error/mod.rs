@@ -37,4 +37,28 @@ pub enum AerospacerFeesError {
     
     #[msg("Unauthorized token account - payer must own the payer_token_account")]
     UnauthorizedTokenAccount,
-} 
\ No newline at end of file
+    
+    #[msg("Operation is paused")]
+    OperationPaused,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    
+    #[msg("Parameter changes must be queued through the timelock")]
+    TimelockActive,
+    
+    #[msg("Queued change is not executable yet")]
+    ChangeNotReady,
+    
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Queued change was encoded by an older program version; cancel and queue it again")]
+    StaleQueuedChange,
+}
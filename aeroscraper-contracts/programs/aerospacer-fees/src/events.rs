@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeStateAccount, Role};
+
+// Typed events for indexers, emitted via emit_cpi! so they survive log truncation
+
+// Snapshot of the configurable fee parameters (before/after pairs in ParamsChanged)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeParams {
+    pub is_stake_enabled: bool,
+    pub stake_contract_address: Pubkey,
+    pub fee_address_1: Pubkey,
+    pub fee_address_2: Pubkey,
+    pub timelock_delay: i64,
+}
+
+impl From<&FeeStateAccount> for FeeParams {
+    fn from(state: &FeeStateAccount) -> Self {
+        Self {
+            is_stake_enabled: state.is_stake_enabled,
+            stake_contract_address: state.stake_contract_address,
+            fee_address_1: state.fee_address_1,
+            fee_address_2: state.fee_address_2,
+            timelock_delay: state.timelock_delay,
+        }
+    }
+}
+
+#[event]
+pub struct FeeStateInitialized {
+    pub admin: Pubkey,
+    pub params: FeeParams,
+}
+
+#[event]
+pub struct FeeDistributed {
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    pub fee_amount: u64,
+    pub to_stability_pool: bool,
+    pub stability_pool_amount: u64,
+    pub fee_address_1_amount: u64,
+    pub fee_address_2_amount: u64,
+    pub total_fees_collected_before: u64,
+    pub total_fees_collected_after: u64,
+}
+
+#[event]
+pub struct ParamsChanged {
+    pub authority: Pubkey,           // Signer of the direct instruction, or proposer of a timelocked change
+    pub before: FeeParams,
+    pub after: FeeParams,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub role: Role,
+    pub previous_holder: Pubkey,
+    pub new_holder: Pubkey,          // Pubkey::default() when revoked
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub authority: Pubkey,
+    pub paused_before: u8,
+    pub paused_after: u8,
+}
+
+#[event]
+pub struct ChangeQueued {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ChangeExecuted {
+    pub id: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ChangeCancelled {
+    pub id: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct StateMigrated {
+    pub admin: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub data_len: u64,               // Account size after the resize
+}
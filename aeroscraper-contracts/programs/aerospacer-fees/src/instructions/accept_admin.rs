@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.pending_admin != Pubkey::default() @ AerospacerFeesError::NoPendingAdmin,
+        constraint = state.pending_admin == new_admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous = state.admin;
+    
+    state.admin = state.pending_admin;
+    state.pending_admin = Pubkey::default();
+    
+    msg!("Admin transferred: {} -> {}", previous, state.admin);
+    
+    emit_cpi!(AdminTransferred {
+        previous_admin: previous,
+        new_admin: state.admin,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized,
+        constraint = state.pending_admin != Pubkey::default() @ AerospacerFeesError::NoPendingAdmin
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+pub fn handler(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    msg!("Admin transfer to {} cancelled", state.pending_admin);
+    
+    emit_cpi!(AdminTransferCancelled {
+        admin: state.admin,
+        pending_admin: state.pending_admin,
+    });
+    
+    state.pending_admin = Pubkey::default();
+    
+    Ok(())
+}
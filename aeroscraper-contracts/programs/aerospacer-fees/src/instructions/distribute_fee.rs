@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
-use crate::state::FeeStateAccount;
+use crate::state::{FeeStateAccount, PAUSE_FEE_DISTRIBUTION};
 use crate::error::AerospacerFeesError;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -8,6 +9,7 @@ pub struct DistributeFeeParams {
     pub fee_amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: DistributeFeeParams)]
 pub struct DistributeFee<'info> {
@@ -40,6 +42,12 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
     let state = &mut ctx.accounts.state;
     let fee_amount = params.fee_amount;
     
+    // Circuit breaker
+    require!(
+        !state.is_paused(PAUSE_FEE_DISTRIBUTION),
+        AerospacerFeesError::OperationPaused
+    );
+    
     if fee_amount == 0 {
         return Err(AerospacerFeesError::NoFeesToDistribute.into());
     }
@@ -66,12 +74,13 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
     );
     
     // Update total fees collected
+    let total_fees_collected_before = state.total_fees_collected;
     state.total_fees_collected = state.total_fees_collected
         .checked_add(fee_amount)
         .ok_or(AerospacerFeesError::Overflow)?;
     
-    msg!("Distributing fee amount: {}", fee_amount);
-    msg!("Total fees collected: {}", state.total_fees_collected);
+    debug_msg!("Distributing fee amount: {}", fee_amount);
+    debug_msg!("Total fees collected: {}", state.total_fees_collected);
     
     if state.is_stake_enabled {
         // Validate stake contract address is set
@@ -86,7 +95,7 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
             AerospacerFeesError::InvalidStabilityPoolAccount
         );
         
-        msg!("Distributing fees to stability pool");
+        debug_msg!("Distributing fees to stability pool");
         
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -99,24 +108,36 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
         
         transfer(transfer_ctx, fee_amount)?;
         
-        msg!("Fees distributed to stability pool successfully: {}", fee_amount);
+        debug_msg!("Fees distributed to stability pool successfully: {}", fee_amount);
+        
+        emit_cpi!(FeeDistributed {
+            payer: ctx.accounts.payer.key(),
+            mint: payer_mint,
+            fee_amount,
+            to_stability_pool: true,
+            stability_pool_amount: fee_amount,
+            fee_address_1_amount: 0,
+            fee_address_2_amount: 0,
+            total_fees_collected_before,
+            total_fees_collected_after: state.total_fees_collected,
+        });
     } else {
         // Validate fee address token account owners using state values
         // Note: ctx.accounts.fee_address_1_token_account.owner refers to the TOKEN ACCOUNT's owner field
         // (the wallet that owns the tokens), not the account's program owner (which is always Token Program)
         
-        msg!("Validating fee address 1 token account owner");
-        msg!("Expected owner: {}", state.fee_address_1);
-        msg!("Actual owner: {}", ctx.accounts.fee_address_1_token_account.owner);
+        debug_msg!("Validating fee address 1 token account owner");
+        debug_msg!("Expected owner: {}", state.fee_address_1);
+        debug_msg!("Actual owner: {}", ctx.accounts.fee_address_1_token_account.owner);
         
         require!(
             ctx.accounts.fee_address_1_token_account.owner == state.fee_address_1,
             AerospacerFeesError::InvalidFeeAddress1
         );
         
-        msg!("Validating fee address 2 token account owner");
-        msg!("Expected owner: {}", state.fee_address_2);
-        msg!("Actual owner: {}", ctx.accounts.fee_address_2_token_account.owner);
+        debug_msg!("Validating fee address 2 token account owner");
+        debug_msg!("Expected owner: {}", state.fee_address_2);
+        debug_msg!("Actual owner: {}", ctx.accounts.fee_address_2_token_account.owner);
         
         require!(
             ctx.accounts.fee_address_2_token_account.owner == state.fee_address_2,
@@ -126,9 +147,9 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
         let half_amount = fee_amount / 2;
         let remaining_amount = fee_amount - half_amount;
         
-        msg!("Distributing fees to fee addresses (50/50 split)");
-        msg!("Half amount: {}", half_amount);
-        msg!("Remaining amount: {}", remaining_amount);
+        debug_msg!("Distributing fees to fee addresses (50/50 split)");
+        debug_msg!("Half amount: {}", half_amount);
+        debug_msg!("Remaining amount: {}", remaining_amount);
         
         if half_amount > 0 {
             let transfer_ctx_1 = CpiContext::new(
@@ -141,7 +162,7 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
             );
             
             transfer(transfer_ctx_1, half_amount)?;
-            msg!("Fees transferred to fee address 1: {}", half_amount);
+            debug_msg!("Fees transferred to fee address 1: {}", half_amount);
         }
         
         if remaining_amount > 0 {
@@ -155,10 +176,22 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
             );
             
             transfer(transfer_ctx_2, remaining_amount)?;
-            msg!("Fees transferred to fee address 2: {}", remaining_amount);
+            debug_msg!("Fees transferred to fee address 2: {}", remaining_amount);
         }
         
-        msg!("Fees distributed to fee addresses successfully");
+        debug_msg!("Fees distributed to fee addresses successfully");
+        
+        emit_cpi!(FeeDistributed {
+            payer: ctx.accounts.payer.key(),
+            mint: payer_mint,
+            fee_amount,
+            to_stability_pool: false,
+            stability_pool_amount: 0,
+            fee_address_1_amount: half_amount,
+            fee_address_2_amount: remaining_amount,
+            total_fees_collected_before,
+            total_fees_collected_after: state.total_fees_collected,
+        });
     }
     
     Ok(())
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{FeeStateAccount, QueuedChange, TimelockedChange};
+use crate::error::AerospacerFeesError;
+use crate::instructions::{set_fee_addresses, set_stake_contract_address, toggle_stake_contract};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteChangeParams {
+    pub id: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: ExecuteChangeParams)]
+pub struct ExecuteChange<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"timelock", params.id.to_le_bytes().as_ref()],
+        bump,
+        close = proposer
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Rent refund destination, must match the recorded proposer
+    #[account(
+        mut,
+        address = queued_change.proposer @ AerospacerFeesError::InvalidAddress
+    )]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+// Permissionless: authorization happened when the change was queued
+pub fn handler(ctx: Context<ExecuteChange>, params: ExecuteChangeParams) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        Clock::get()?.unix_timestamp >= queued_change.eta,
+        AerospacerFeesError::ChangeNotReady
+    );
+    
+    let before = FeeParams::from(&**state);
+    match &queued_change.change()? {
+        TimelockedChange::SetFeeAddresses(change) => set_fee_addresses::apply(state, change)?,
+        TimelockedChange::SetStakeContractAddress(change) => set_stake_contract_address::apply(state, change)?,
+        TimelockedChange::ToggleStakeContract => toggle_stake_contract::apply(state)?,
+        TimelockedChange::SetTimelockDelay(delay) => {
+            state.timelock_delay = *delay;
+            msg!("Timelock delay updated: {} seconds", delay);
+        }
+    }
+    
+    msg!("Change {} executed by {}", params.id, ctx.accounts.executor.key());
+    
+    emit_cpi!(ParamsChanged {
+        authority: queued_change.proposer,
+        before,
+        after: FeeParams::from(&**state),
+    });
+    emit_cpi!(ChangeExecuted {
+        id: params.id,
+        executor: ctx.accounts.executor.key(),
+    });
+    
+    Ok(())
+}
@@ -20,6 +20,13 @@ pub fn handler(ctx: Context<GetConfig>) -> Result<ConfigResponse> {
         fee_address_1: state.fee_address_1,
         fee_address_2: state.fee_address_2,
         total_fees_collected: state.total_fees_collected,
+        guardian: state.guardian,
+        paused: state.paused,
+        risk_manager: state.risk_manager,
+        oracle_manager: state.oracle_manager,
+        fee_manager: state.fee_manager,
+        pending_admin: state.pending_admin,
+        timelock_delay: state.timelock_delay,
     };
     
     msg!("Fee distributor config retrieved successfully");
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::{FeeStateAccount, DEFAULT_FEE_ADDR_1, DEFAULT_FEE_ADDR_2};
+use crate::events::*;
+use crate::state::{FeeStateAccount, Versioned, DEFAULT_FEE_ADDR_1, DEFAULT_FEE_ADDR_2};
 use std::str::FromStr;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -31,6 +33,15 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     state.fee_address_2 = Pubkey::from_str(DEFAULT_FEE_ADDR_2).unwrap();
     
     state.total_fees_collected = 0;
+    state.guardian = Pubkey::default();
+    state.paused = 0;
+    state.risk_manager = Pubkey::default();
+    state.oracle_manager = Pubkey::default();
+    state.fee_manager = Pubkey::default();
+    state.pending_admin = Pubkey::default();
+    state.timelock_delay = 0; // Disabled until the owner queues a SetTimelockDelay change
+    state.next_change_id = 0;
+    state.version = FeeStateAccount::VERSION;
     
     msg!("Aerospacer Fee Distributor initialized successfully");
     msg!("Admin: {}", state.admin);
@@ -39,6 +50,11 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     msg!("Fee Address 2: {}", state.fee_address_2);
     msg!("Total fees collected: {}", state.total_fees_collected);
     
+    emit_cpi!(FeeStateInitialized {
+        admin: state.admin,
+        params: FeeParams::from(&**state),
+    });
+    
     Ok(())
 }
 
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::events::*;
+use crate::state::{FeeStateAccount, Versioned};
+use crate::error::AerospacerFeesError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Loaded as FeeStateAccount after the resize, since an older layout may be
+    /// too short to deserialize. The discriminator and admin are checked then
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub state: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let new_len = 8 + FeeStateAccount::LEN;
+    
+    // Grow the account to the current layout first; the admin tops up rent. If the signer
+    // turns out not to be the admin the whole instruction reverts, resize included
+    if state_info.data_len() < new_len {
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(state_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: state_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        state_info.resize(new_len)?;
+    }
+    
+    let mut state = FeeStateAccount::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+    require!(
+        state.admin == ctx.accounts.admin.key(),
+        AerospacerFeesError::Unauthorized
+    );
+    
+    let from_version = state.version;
+    require!(state.upgrade(), AerospacerFeesError::AccountAlreadyMigrated);
+    state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+    
+    msg!("Fee state migrated: v{} -> v{} ({} bytes)", from_version, state.version, state_info.data_len());
+    
+    emit_cpi!(StateMigrated {
+        admin: state.admin,
+        from_version,
+        to_version: state.version,
+        data_len: state_info.data_len() as u64,
+    });
+    
+    Ok(())
+}
@@ -4,6 +4,16 @@ pub mod set_stake_contract_address;
 pub mod set_fee_addresses;
 pub mod distribute_fee;
 pub mod get_config;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod set_paused;
+pub mod queue_change;
+pub mod execute_change;
+pub mod cancel_change;
+pub mod migrate_state;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -16,4 +26,24 @@ pub use set_fee_addresses::*;
 #[allow(ambiguous_glob_reexports)]
 pub use distribute_fee::*;
 #[allow(ambiguous_glob_reexports)]
-pub use get_config::*; 
\ No newline at end of file
+pub use get_config::*;
+#[allow(ambiguous_glob_reexports)]
+pub use grant_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use accept_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_admin_transfer::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_paused::*;
+#[allow(ambiguous_glob_reexports)]
+pub use queue_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_state::*;
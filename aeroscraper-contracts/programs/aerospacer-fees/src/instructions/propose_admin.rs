@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAdminParams {
+    pub new_admin: Pubkey, // Proposed new admin (e.g. a multisig or governance PDA)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        params.new_admin != Pubkey::default() && params.new_admin != state.admin,
+        AerospacerFeesError::InvalidAddress
+    );
+    
+    // Overwrites any earlier proposal; admin rights only move once the new key accepts
+    state.pending_admin = params.new_admin;
+    
+    msg!("Admin transfer proposed: {} -> {}", state.admin, params.new_admin);
+    
+    emit_cpi!(AdminTransferProposed {
+        admin: state.admin,
+        pending_admin: params.new_admin,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{FeeStateAccount, QueuedChange, TimelockedChange, Role, Versioned, MAX_TIMELOCK_DELAY};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueueChangeParams {
+    pub change: TimelockedChange,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: QueueChangeParams)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = QueuedChange::space(&params.change),
+        seeds = [b"timelock", state.next_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+    let proposer = ctx.accounts.proposer.key();
+    let state = &mut ctx.accounts.state;
+    
+    // Same role requirements as the direct instructions
+    match &params.change {
+        TimelockedChange::SetFeeAddresses(_)
+        | TimelockedChange::SetStakeContractAddress(_)
+        | TimelockedChange::ToggleStakeContract => {
+            require!(
+                state.has_role(Role::FeeManager, &proposer),
+                AerospacerFeesError::Unauthorized
+            );
+        }
+        TimelockedChange::SetTimelockDelay(delay) => {
+            require!(
+                state.admin == proposer,
+                AerospacerFeesError::Unauthorized
+            );
+            require!(
+                (0..=MAX_TIMELOCK_DELAY).contains(delay),
+                AerospacerFeesError::InvalidTimelockDelay
+            );
+        }
+    }
+    
+    let now = Clock::get()?.unix_timestamp;
+    let queued_change = &mut ctx.accounts.queued_change;
+    queued_change.id = state.next_change_id;
+    queued_change.proposer = proposer;
+    queued_change.queued_at = now;
+    queued_change.eta = now
+        .checked_add(state.timelock_delay)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    queued_change.set_change(&params.change)?;
+    queued_change.version = QueuedChange::VERSION;
+    
+    state.next_change_id = state.next_change_id
+        .checked_add(1)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    
+    msg!("Change queued successfully");
+    msg!("Change ID: {}", queued_change.id);
+    msg!("Executable at: {}", queued_change.eta);
+    
+    emit_cpi!(ChangeQueued {
+        id: queued_change.id,
+        proposer,
+        queued_at: queued_change.queued_at,
+        eta: queued_change.eta,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{FeeStateAccount, Role};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    pub role: Role,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous = state.role_holder(params.role);
+    state.set_role_holder(params.role, Pubkey::default());
+    
+    msg!("Role {:?} revoked from {}", params.role, previous);
+    
+    emit_cpi!(RoleChanged {
+        role: params.role,
+        previous_holder: previous,
+        new_holder: Pubkey::default(),
+    });
+    
+    Ok(())
+}
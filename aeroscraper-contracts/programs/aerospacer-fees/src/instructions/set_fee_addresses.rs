@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::state::FeeStateAccount;
+use crate::events::*;
+use crate::state::{FeeStateAccount, Role};
 use crate::error::AerospacerFeesError;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SetFeeAddressesParams {
     pub fee_address_1: String,
     pub fee_address_2: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: SetFeeAddressesParams)]
 pub struct SetFeeAddresses<'info> {
@@ -18,14 +20,32 @@ pub struct SetFeeAddresses<'info> {
         mut,
         seeds = [b"fee_state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+        constraint = state.has_role(Role::FeeManager, &admin.key()) @ AerospacerFeesError::Unauthorized
     )]
     pub state: Account<'info, FeeStateAccount>,
 }
 
 pub fn handler(ctx: Context<SetFeeAddresses>, params: SetFeeAddressesParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerFeesError::TimelockActive
+    );
+    
+    let before = FeeParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state, &params)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.admin.key(),
+        before,
+        after: FeeParams::from(&*ctx.accounts.state),
+    });
     
+    Ok(())
+}
+
+/// Validate and store both fee destinations (shared with timelocked execution)
+pub fn apply(state: &mut FeeStateAccount, params: &SetFeeAddressesParams) -> Result<()> {
     // Validate and parse fee address 1
     let fee_address_1 = match Pubkey::try_from(params.fee_address_1.as_str()) {
         Ok(pubkey) => pubkey,
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::FeeStateAccount;
+use crate::events::*;
+use crate::state::{FeeStateAccount, Role};
 use crate::error::AerospacerFeesError;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SetStakeContractAddressParams {
     pub address: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: SetStakeContractAddressParams)]
 pub struct SetStakeContractAddress<'info> {
@@ -17,14 +19,32 @@ pub struct SetStakeContractAddress<'info> {
         mut,
         seeds = [b"fee_state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+        constraint = state.has_role(Role::FeeManager, &admin.key()) @ AerospacerFeesError::Unauthorized
     )]
     pub state: Account<'info, FeeStateAccount>,
 }
 
 pub fn handler(ctx: Context<SetStakeContractAddress>, params: SetStakeContractAddressParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerFeesError::TimelockActive
+    );
     
+    let before = FeeParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state, &params)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.admin.key(),
+        before,
+        after: FeeParams::from(&*ctx.accounts.state),
+    });
+    
+    Ok(())
+}
+
+/// Validate and store the stake contract address (shared with timelocked execution)
+pub fn apply(state: &mut FeeStateAccount, params: &SetStakeContractAddressParams) -> Result<()> {
     let stake_contract_address = match Pubkey::try_from(params.address.as_str()) {
         Ok(pubkey) => pubkey,
         Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
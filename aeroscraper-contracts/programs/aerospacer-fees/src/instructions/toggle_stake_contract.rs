@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::FeeStateAccount;
+use crate::events::*;
+use crate::state::{FeeStateAccount, Role};
 use crate::error::AerospacerFeesError;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ToggleStakeContract<'info> {
     #[account(mut)]
@@ -11,14 +13,32 @@ pub struct ToggleStakeContract<'info> {
         mut,
         seeds = [b"fee_state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+        constraint = state.has_role(Role::FeeManager, &admin.key()) @ AerospacerFeesError::Unauthorized
     )]
     pub state: Account<'info, FeeStateAccount>,
 }
 
 pub fn handler(ctx: Context<ToggleStakeContract>) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerFeesError::TimelockActive
+    );
     
+    let before = FeeParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.admin.key(),
+        before,
+        after: FeeParams::from(&*ctx.accounts.state),
+    });
+    
+    Ok(())
+}
+
+/// Flip fee routing between the stake contract and the fee addresses (shared with timelocked execution)
+pub fn apply(state: &mut FeeStateAccount) -> Result<()> {
     state.is_stake_enabled = !state.is_stake_enabled;
     
     msg!("Stake contract toggled successfully");
@@ -1,6 +1,20 @@
+// #[program] expands to IDL instructions that call the deprecated AccountInfo::realloc
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
+// Diagnostic logging for intermediate values. Compiled to a no-op unless the
+// `verbose-logs` feature is enabled, since each msg! costs compute units.
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -38,6 +52,46 @@ pub mod aerospacer_fees {
     pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigResponse> {
         instructions::get_config::handler(ctx)
     }
+
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::grant_role::handler(ctx, params)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::revoke_role::handler(ctx, params)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+        instructions::propose_admin::handler(ctx, params)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::cancel_admin_transfer::handler(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+        instructions::set_paused::handler(ctx, params)
+    }
+
+    pub fn queue_change(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+        instructions::queue_change::handler(ctx, params)
+    }
+
+    pub fn execute_change(ctx: Context<ExecuteChange>, params: ExecuteChangeParams) -> Result<()> {
+        instructions::execute_change::handler(ctx, params)
+    }
+
+    pub fn cancel_change(ctx: Context<CancelChange>, params: CancelChangeParams) -> Result<()> {
+        instructions::cancel_change::handler(ctx, params)
+    }
+
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        instructions::migrate_state::handler(ctx)
+    }
 }
 
 /// Helper functions for PDA derivation
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::error::AerospacerFeesError;
+use crate::instructions::{SetFeeAddressesParams, SetStakeContractAddressParams};
 
 // Default fee addresses for Solana (following INJECTIVE project pattern)
 // FEE_ADDR_1: Protocol Treasury/Development Fund
@@ -6,6 +8,67 @@ use anchor_lang::prelude::*;
 pub const DEFAULT_FEE_ADDR_1: &str = "8Lv4UrYHTrzvg9jPVVGNmxWyMrMvrZnCQLWucBzfJyyR";
 pub const DEFAULT_FEE_ADDR_2: &str = "GcNwV1nA5bityjNYsWwPLHykpKuuhPzK1AQFBbrPopnX";
 
+// Access-control roles (the owner, stored as `admin`, implicitly holds every role)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    RiskManager,   // Unused by the fee program, kept for a uniform role set
+    OracleManager, // Unused by the fee program, kept for a uniform role set
+    FeeManager,    // Fee destinations and stake contract settings
+    Guardian,      // Pause fee distribution
+}
+
+// Circuit breaker flags (bits of FeeStateAccount.paused)
+pub const PAUSE_FEE_DISTRIBUTION: u8 = 1 << 0; // distribute_fee (fee-charging protocol operations revert while set)
+pub const PAUSE_ALL: u8 = PAUSE_FEE_DISTRIBUTION;
+
+// Account layout versioning
+// Every account ends with a `version` byte; later fields are appended after it and get
+// their defaults in `upgrade`. FeeStateAccount was allocated without slack, so accounts
+// created before the byte existed cannot be loaded until migrate_state resizes them
+pub trait Versioned {
+    const VERSION: u8;
+    
+    fn version(&self) -> u8;
+    
+    /// Bring an account written under an older layout up to VERSION.
+    /// Returns false if it was already current
+    fn upgrade(&mut self) -> bool;
+}
+
+impl Versioned for FeeStateAccount {
+    const VERSION: u8 = 1;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for QueuedChange {
+    const VERSION: u8 = 2;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v2: the change is stored as length-prefixed bytes, so v1 queued changes no longer
+        // load; drain the queue before upgrading (see ACCOUNT_MIGRATION.md)
+        self.version = Self::VERSION;
+        true
+    }
+}
+
 #[account]
 pub struct FeeStateAccount {
     pub admin: Pubkey,                    // 32 bytes
@@ -14,16 +77,55 @@ pub struct FeeStateAccount {
     pub fee_address_1: Pubkey,            // 32 bytes - NEW
     pub fee_address_2: Pubkey,            // 32 bytes - NEW
     pub total_fees_collected: u64,        // 8 bytes
+    pub guardian: Pubkey,                 // 32 bytes - guardian role, can pause fee distribution
+    pub paused: u8,                       // 1 byte - bitmask of PAUSE_* flags
+    pub risk_manager: Pubkey,             // 32 bytes - risk manager role
+    pub oracle_manager: Pubkey,           // 32 bytes - oracle manager role
+    pub fee_manager: Pubkey,              // 32 bytes - fee manager role
+    pub pending_admin: Pubkey,            // 32 bytes - proposed admin awaiting acceptance
+    pub timelock_delay: i64,              // 8 bytes - seconds between queueing and executing a change (0 allows direct admin instructions)
+    pub next_change_id: u64,              // 8 bytes - id (and PDA seed) of the next queued change
+    pub version: u8,                      // 1 byte - layout version (see Versioned), upgraded by migrate_state
 }
 
 impl FeeStateAccount {
-    pub const LEN: usize = 32 + 1 + 32 + 32 + 32 + 8; // Updated to include fee addresses
+    pub const LEN: usize = 32 + 1 + 32 + 32 + 32 + 8 + 32 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 1; // Updated to include fee addresses, guardian, pause flags, roles, pending admin, timelock and version
     
     /// Get the seeds for the fee state PDA
     pub fn seeds() -> [&'static [u8]; 1] {
         [b"fee_state"]
     }
     
+    /// Check whether an operation class is currently paused
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused & operation != 0
+    }
+
+    /// Current holder of a role (Pubkey::default() when unassigned)
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::RiskManager => self.risk_manager,
+            Role::OracleManager => self.oracle_manager,
+            Role::FeeManager => self.fee_manager,
+            Role::Guardian => self.guardian,
+        }
+    }
+    
+    /// Assign a role holder (Pubkey::default() revokes it)
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::RiskManager => self.risk_manager = holder,
+            Role::OracleManager => self.oracle_manager = holder,
+            Role::FeeManager => self.fee_manager = holder,
+            Role::Guardian => self.guardian = holder,
+        }
+    }
+    
+    /// The owner can act in every role, since it could grant any role to itself anyway
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        *key == self.admin || (*key != Pubkey::default() && *key == self.role_holder(role))
+    }
+    
     /// Derive the fee state PDA
     pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&Self::seeds(), program_id)
@@ -38,4 +140,66 @@ pub struct ConfigResponse {
     pub fee_address_1: Pubkey,            // NEW
     pub fee_address_2: Pubkey,            // NEW
     pub total_fees_collected: u64,
-} 
\ No newline at end of file
+    pub guardian: Pubkey,
+    pub paused: u8,
+    pub risk_manager: Pubkey,
+    pub oracle_manager: Pubkey,
+    pub fee_manager: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timelock_delay: i64,
+} 
+
+// Upper bound for the timelock delay (30 days)
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+// Fee parameter change waiting in the timelock; anyone can execute it once `eta` has passed,
+// and the guardian can cancel it before then. Rent goes back to the proposer either way
+#[account]
+pub struct QueuedChange {
+    pub id: u64,                          // 8 bytes - sequential id, also the PDA seed
+    pub proposer: Pubkey,                 // 32 bytes - paid the rent, receives it back
+    pub queued_at: i64,                   // 8 bytes
+    pub eta: i64,                         // 8 bytes - earliest execution timestamp
+    pub change_version: u8,               // 1 byte - CHANGE_VERSION the payload was encoded under
+    pub change: Vec<u8>,                  // 4 + variable - Borsh-encoded TimelockedChange
+    pub version: u8,                      // 1 byte - layout version (see Versioned)
+}
+
+impl QueuedChange {
+    pub const BASE_LEN: usize = 8 + 32 + 8 + 8 + 1 + 4 + 1; // Excludes the discriminator and the change payload
+    
+    /// Encoding of TimelockedChange payloads; bump it whenever a variant or its parameters change shape
+    pub const CHANGE_VERSION: u8 = 1;
+    
+    /// Account space for a given change (payload size varies by variant)
+    pub fn space(change: &TimelockedChange) -> usize {
+        8 + Self::BASE_LEN + change.try_to_vec().map_or(0, |data| data.len())
+    }
+    
+    /// Store a change under the current encoding
+    pub fn set_change(&mut self, change: &TimelockedChange) -> Result<()> {
+        self.change_version = Self::CHANGE_VERSION;
+        self.change = change.try_to_vec()?;
+        Ok(())
+    }
+    
+    /// Decode the queued change; one queued under another encoding has to be cancelled
+    /// and queued again
+    pub fn change(&self) -> Result<TimelockedChange> {
+        require!(
+            self.change_version == Self::CHANGE_VERSION,
+            AerospacerFeesError::StaleQueuedChange
+        );
+        TimelockedChange::try_from_slice(&self.change)
+            .map_err(|_| AerospacerFeesError::StaleQueuedChange.into())
+    }
+}
+
+// Fee parameter changes that go through the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TimelockedChange {
+    SetFeeAddresses(SetFeeAddressesParams),                 // Fee manager
+    SetStakeContractAddress(SetStakeContractAddressParams), // Fee manager
+    ToggleStakeContract,                                    // Fee manager
+    SetTimelockDelay(i64),                                  // Owner only
+}
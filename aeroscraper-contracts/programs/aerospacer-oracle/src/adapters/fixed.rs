@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::{PriceSource, SourceConfig};
+use crate::error::AerospacerOracleError;
+use super::{PriceAdapter, SourcePrice};
+
+/// Price set by the oracle manager through set_data
+///
+/// No account is read. The price carries no confidence interval and counts as published
+/// now, so it never goes stale; changing it goes through set_data (and the timelock).
+pub struct FixedPrice;
+
+impl PriceAdapter for FixedPrice {
+    fn read(_price_account: &AccountInfo, source: &SourceConfig, clock: &Clock) -> Result<SourcePrice> {
+        let PriceSource::Fixed { price, exponent } = source.source else {
+            return err!(AerospacerOracleError::InvalidPriceSource);
+        };
+
+        Ok(SourcePrice {
+            price,
+            conf: 0,
+            expo: exponent,
+            publish_time: clock.unix_timestamp,
+            ema_price: price,
+            ema_conf: 0,
+        })
+    }
+}
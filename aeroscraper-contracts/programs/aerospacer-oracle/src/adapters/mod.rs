@@ -0,0 +1,469 @@
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::Price;
+use crate::state::{
+    AssetConfig, CompositeOp, PriceFeedStatus, PriceSource, SourceConfig, BPS_DENOMINATOR, PEG_PRICE,
+    PRICE_BOUND_EXPONENT,
+};
+use crate::error::AerospacerOracleError;
+use crate::registry;
+
+pub mod fixed;
+pub mod pyth_pull;
+pub mod pyth_push;
+pub mod stake_pool;
+pub mod switchboard;
+
+pub use fixed::FixedPrice;
+pub use pyth_pull::PythPull;
+pub use pyth_push::PythPush;
+pub use stake_pool::SplStakePool;
+pub use switchboard::SwitchboardOnDemand;
+
+/// A price in the Pyth convention (`price` × 10^`expo`), whichever source it came from
+#[derive(Clone, Copy, Debug)]
+pub struct SourcePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    
+    /// Exponential moving average price and confidence, with the same exponent
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// An asset's price as reported to callers, aggregated over its fresh sources
+#[derive(Clone, Copy, Debug)]
+pub struct AssetPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    
+    /// Publish time of the oldest source used
+    pub publish_time: i64,
+    
+    /// Exponential moving average price and confidence, with the same exponent
+    pub ema_price: i64,
+    pub ema_conf: u64,
+    
+    /// Sources used, bit i set for source i (0 = primary)
+    pub sources_used: u8,
+    
+    pub status: PriceFeedStatus,
+}
+
+/// Why the sources gave no price, and the feed status that implies
+struct FeedFailure {
+    error: Error,
+    status: PriceFeedStatus,
+}
+
+/// Reads a price from one kind of source
+///
+/// Adapters only decode the source and check that it is the feed configured for it: the
+/// account's address, its owner and the feed id it carries, each failing with its own
+/// error. `read_price` applies the asset's staleness and confidence limits to every
+/// source alike.
+pub trait PriceAdapter {
+    fn read(price_account: &AccountInfo, source: &SourceConfig, clock: &Clock) -> Result<SourcePrice>;
+}
+
+/// Load the latest price for an asset from its configured sources, `price_accounts`
+/// holding one account per source in configuration order. Pyth push accounts must be
+/// owned by `pyth_program`, the oracle's configured Pyth program. A composite asset's
+/// accounts are followed by the config account of the asset it is derived from and that
+/// asset's price accounts (see `price_account_count`).
+///
+/// A source counts as fresh when its price is no older than the asset's staleness limit,
+/// positive, and has a confidence interval no wider than the asset's confidence limit.
+/// Building with the `devnet` feature skips the age check, since devnet feeds publish
+/// irregularly. A source that is not the configured feed is an error; one that is merely
+/// not fresh is left out. At least `min_sources` must be fresh; their median is reported
+/// once they agree to within `max_deviation_bps` of it, lies within the asset's price
+/// bounds and has not moved more than its change limit from the last good price. A price
+/// that fails the bounds or the change limit counts as a frozen feed. For a composite, the
+/// bounds apply to the derived price, and the other asset's price must be live. A pegged
+/// asset reports exactly the peg while its price is within the peg band, and counts as a
+/// frozen feed while the guardian has frozen it.
+pub fn read_price(
+    asset: &AssetConfig,
+    price_accounts: &[AccountInfo],
+    pyth_program: &Pubkey,
+    clock: &Clock,
+) -> Result<AssetPrice> {
+    read_live(asset, price_accounts, pyth_program, clock)?.map_err(|failure| failure.error)
+}
+
+/// Like `read_price`, but when the sources are stale (Frozen) or unusable (Broken) the
+/// asset's last good price is returned with that status instead. Fails as `read_price`
+/// does if no good price has been recorded yet
+pub fn read_price_or_last_good(
+    asset: &AssetConfig,
+    price_accounts: &[AccountInfo],
+    pyth_program: &Pubkey,
+    clock: &Clock,
+) -> Result<AssetPrice> {
+    match read_live(asset, price_accounts, pyth_program, clock)? {
+        Ok(price) => Ok(price),
+        Err(failure) => {
+            debug_msg!("Feed {:?}: {:?}", failure.status, failure.error);
+            asset.last_good(failure.status).ok_or(failure.error)
+        }
+    }
+}
+
+/// Read and aggregate the sources. The outer error is a caller mistake (wrong accounts);
+/// the inner one a feed that cannot be used right now
+fn read_live(
+    asset: &AssetConfig,
+    price_accounts: &[AccountInfo],
+    pyth_program: &Pubkey,
+    clock: &Clock,
+) -> Result<std::result::Result<AssetPrice, FeedFailure>> {
+    let sources = asset.sources(*pyth_program)?;
+    require!(
+        price_accounts.len() >= sources.len(),
+        AerospacerOracleError::InvalidPriceAccounts
+    );
+    let (price_accounts, other_accounts) = price_accounts.split_at(sources.len());
+    let op = CompositeOp::from_u8(asset.composite_op)?;
+    if op == CompositeOp::None {
+        require!(other_accounts.is_empty(), AerospacerOracleError::InvalidPriceAccounts);
+    }
+    
+    let mut fresh = Vec::with_capacity(sources.len());
+    let mut first_failure: Option<FeedFailure> = None;
+    let mut all_stale = true;
+    let mut sources_used = 0u8;
+    for (i, (source, price_account)) in sources.iter().zip(price_accounts).enumerate() {
+        let price = read_source(price_account, source, clock)?;
+        match check_price(&price, asset, clock) {
+            Ok(()) => {
+                fresh.push(price);
+                sources_used |= 1 << i;
+            }
+            Err(failure) => {
+                debug_msg!("Source {} skipped: {:?}", i, failure.error);
+                all_stale &= failure.status == PriceFeedStatus::Frozen;
+                first_failure.get_or_insert(failure);
+            }
+        }
+    }
+    
+    if fresh.len() < asset.min_fresh_sources() {
+        // A single-source asset reports why its price was rejected
+        let failure = match first_failure {
+            Some(failure) if sources.len() == 1 => failure,
+            _ => FeedFailure {
+                error: AerospacerOracleError::InsufficientPriceSources.into(),
+                status: if all_stale { PriceFeedStatus::Frozen } else { PriceFeedStatus::Broken },
+            },
+        };
+        return Ok(Err(failure));
+    }
+    
+    let status = if sources_used & 1 == 1 {
+        PriceFeedStatus::Working
+    } else {
+        PriceFeedStatus::UsingFallback
+    };
+    let mut price = match median(&fresh, asset.max_deviation_bps)? {
+        Ok(price) => AssetPrice { sources_used, status, ..price },
+        Err(failure) => return Ok(Err(failure)),
+    };
+    if asset.peg_frozen != 0 {
+        return Ok(Err(FeedFailure {
+            error: AerospacerOracleError::PegFrozen.into(),
+            status: PriceFeedStatus::Frozen,
+        }));
+    }
+    if op != CompositeOp::None {
+        let other = match read_other(asset, other_accounts, pyth_program, clock)? {
+            Ok(other) => other,
+            Err(failure) => return Ok(Err(failure)),
+        };
+        price = match combine(&price, &other, op) {
+            Ok(price) => price,
+            Err(error) => {
+                return Ok(Err(FeedFailure {
+                    error: error.into(),
+                    status: PriceFeedStatus::Broken,
+                }))
+            }
+        };
+    }
+    if asset.peg_band_bps > 0 {
+        price = peg(&price, asset.peg_band_bps)?;
+    }
+    if let Err(error) = check_bounds(&price, asset) {
+        debug_msg!("Price {} x 10^{} rejected: {:?}", price.price, price.expo, error);
+        return Ok(Err(FeedFailure {
+            error: error.into(),
+            status: PriceFeedStatus::Frozen,
+        }));
+    }
+    Ok(Ok(price))
+}
+
+/// Number of accounts at the start of `accounts` that `read_price` takes for `asset`: one
+/// per source, then for a composite the other asset's config account and its price accounts
+pub fn price_account_count(asset: &AssetConfig, accounts: &[AccountInfo]) -> Result<usize> {
+    let count = asset.source_total();
+    if CompositeOp::from_u8(asset.composite_op)? == CompositeOp::None {
+        return Ok(count);
+    }
+    let other = accounts.get(count).ok_or(AerospacerOracleError::InvalidPriceAccounts)?;
+    Ok(count + 1 + registry::load_asset(other)?.source_total())
+}
+
+/// Read the live price of the asset a composite is derived from, `accounts` holding its
+/// config account followed by its price accounts. It must not be a composite itself
+fn read_other(
+    asset: &AssetConfig,
+    accounts: &[AccountInfo],
+    pyth_program: &Pubkey,
+    clock: &Clock,
+) -> Result<std::result::Result<AssetPrice, FeedFailure>> {
+    let (other_info, price_accounts) = accounts
+        .split_first()
+        .ok_or(AerospacerOracleError::InvalidPriceAccounts)?;
+    let other = registry::load_asset(other_info)?;
+    require!(
+        other.denom == asset.composite_denom,
+        AerospacerOracleError::InvalidAssetAccount
+    );
+    require!(
+        other.composite_op == CompositeOp::None as u8,
+        AerospacerOracleError::InvalidCompositePrice
+    );
+    read_live(&other, price_accounts, pyth_program, clock)
+}
+
+/// Multiply or divide a composite's own price by the other asset's, at the finer of their
+/// exponents. Confidence propagates as with Pyth's price arithmetic: the relative
+/// confidences add up. The composite uses a fallback when the other asset does
+fn combine(own: &AssetPrice, other: &AssetPrice, op: CompositeOp) -> std::result::Result<AssetPrice, AerospacerOracleError> {
+    let expo = own.expo.min(other.expo);
+    let apply = |a: Price, b: Price| {
+        match op {
+            CompositeOp::Multiply => a.mul(&b),
+            CompositeOp::Divide => a.div(&b),
+            CompositeOp::None => Some(a),
+        }
+        .and_then(|result| result.scale_to_exponent(expo))
+        .filter(|result| result.price > 0)
+        .ok_or(AerospacerOracleError::InvalidPriceData)
+    };
+    let pyth_price = |price: i64, conf: u64, expo: i32| Price { price, conf, expo, publish_time: 0 };
+    
+    let spot = apply(pyth_price(own.price, own.conf, own.expo), pyth_price(other.price, other.conf, other.expo))?;
+    let ema = apply(
+        pyth_price(own.ema_price, own.ema_conf, own.expo),
+        pyth_price(other.ema_price, other.ema_conf, other.expo),
+    )?;
+    Ok(AssetPrice {
+        price: spot.price,
+        conf: spot.conf,
+        expo,
+        publish_time: own.publish_time.min(other.publish_time),
+        ema_price: ema.price,
+        ema_conf: ema.conf,
+        sources_used: own.sources_used,
+        status: if other.status == PriceFeedStatus::Working { own.status } else { PriceFeedStatus::UsingFallback },
+    })
+}
+
+fn read_source(price_account: &AccountInfo, source: &SourceConfig, clock: &Clock) -> Result<SourcePrice> {
+    match source.source {
+        PriceSource::PythPush => PythPush::read(price_account, source, clock),
+        PriceSource::PythPull => PythPull::read(price_account, source, clock),
+        PriceSource::SwitchboardOnDemand => SwitchboardOnDemand::read(price_account, source, clock),
+        PriceSource::Fixed { .. } => FixedPrice::read(price_account, source, clock),
+        PriceSource::SplStakePool { .. } => SplStakePool::read(price_account, source, clock),
+    }
+}
+
+/// Check one source's price against the asset's limits. A stale price means the feed
+/// is frozen, any other failure that it is broken
+fn check_price(price: &SourcePrice, asset: &AssetConfig, clock: &Clock) -> std::result::Result<(), FeedFailure> {
+    let age = clock.unix_timestamp.saturating_sub(price.publish_time);
+    if !cfg!(feature = "devnet") && age > asset.max_staleness_secs as i64 {
+        return Err(FeedFailure {
+            error: AerospacerOracleError::PriceTooOld.into(),
+            status: PriceFeedStatus::Frozen,
+        });
+    }
+    
+    let broken = |error: AerospacerOracleError| FeedFailure {
+        error: error.into(),
+        status: PriceFeedStatus::Broken,
+    };
+    if price.price <= 0 {
+        return Err(broken(AerospacerOracleError::InvalidPriceData));
+    }
+    if !confidence_within_limit(price.price, price.conf, asset.max_confidence_bps) {
+        return Err(broken(AerospacerOracleError::PythPriceValidationFailed));
+    }
+    Ok(())
+}
+
+/// Check an aggregated price against the asset's absolute bounds, and its move from the
+/// last good price against the change limit. The limit grows by `max_change_bps` for
+/// every `change_window_secs` started since the last good price was published, so a feed
+/// held back by it recovers once enough time has passed.
+fn check_bounds(price: &AssetPrice, asset: &AssetConfig) -> std::result::Result<(), AerospacerOracleError> {
+    let bounds = asset.price_bounds();
+    
+    if bounds.min_price > 0 || bounds.max_price > 0 {
+        let expo = price.expo.min(PRICE_BOUND_EXPONENT);
+        let value = scale(price.price, price.expo, expo)?;
+        let scale_bound = |bound: i64| scale(bound, PRICE_BOUND_EXPONENT, expo);
+        if bounds.min_price > 0 && value < scale_bound(bounds.min_price)? {
+            return Err(AerospacerOracleError::PriceOutOfBounds);
+        }
+        if bounds.max_price > 0 && value > scale_bound(bounds.max_price)? {
+            return Err(AerospacerOracleError::PriceOutOfBounds);
+        }
+    }
+    
+    if let (true, Some(last)) = (bounds.max_change_bps > 0, asset.last_good(PriceFeedStatus::Working)) {
+        let expo = price.expo.min(last.expo);
+        let current = scale(price.price, price.expo, expo)?;
+        let previous = scale(last.price, last.expo, expo)?;
+        
+        let elapsed = price.publish_time.saturating_sub(last.publish_time).max(0) as u64;
+        let windows = elapsed.div_ceil(bounds.change_window_secs as u64).max(1);
+        let allowed_bps = (bounds.max_change_bps as u128).saturating_mul(windows as u128);
+        let change = (current - previous).unsigned_abs();
+        if change.saturating_mul(BPS_DENOMINATOR as u128) > (previous as u128).saturating_mul(allowed_bps) {
+            return Err(AerospacerOracleError::PriceChangeTooLarge);
+        }
+    }
+    Ok(())
+}
+
+/// Report PEG_PRICE, with no confidence interval, when the price is within `band_bps` of
+/// it; otherwise the price as it is
+fn peg(price: &AssetPrice, band_bps: u16) -> std::result::Result<AssetPrice, AerospacerOracleError> {
+    let expo = price.expo.min(PRICE_BOUND_EXPONENT);
+    let value = scale(price.price, price.expo, expo)?;
+    let peg = scale(PEG_PRICE, PRICE_BOUND_EXPONENT, expo)?;
+    if (value - peg).unsigned_abs() * BPS_DENOMINATOR as u128 > peg as u128 * band_bps as u128 {
+        return Ok(*price);
+    }
+    Ok(AssetPrice {
+        price: PEG_PRICE,
+        conf: 0,
+        expo: PRICE_BOUND_EXPONENT,
+        ema_price: PEG_PRICE,
+        ema_conf: 0,
+        ..*price
+    })
+}
+
+/// Bring a price mantissa from exponent `from` to a finer exponent `to`
+fn scale(value: i64, from: i32, to: i32) -> std::result::Result<i128, AerospacerOracleError> {
+    10i128
+        .checked_pow((from - to) as u32)
+        .and_then(|factor| (value as i128).checked_mul(factor))
+        .ok_or(AerospacerOracleError::Overflow)
+}
+
+/// Median of the fresh prices, rejected as broken if their spread exceeds the asset's
+/// deviation limit. With two prices it is their mean, with the wider of the two
+/// confidences. Prices are brought to the finest exponent among them first.
+fn median(prices: &[SourcePrice], max_deviation_bps: u16) -> Result<std::result::Result<AssetPrice, FeedFailure>> {
+    let publish_time = prices.iter().map(|p| p.publish_time).min().unwrap_or_default();
+    if let [only] = prices {
+        return Ok(Ok(AssetPrice {
+            price: only.price,
+            conf: only.conf,
+            expo: only.expo,
+            publish_time,
+            ema_price: only.ema_price,
+            ema_conf: only.ema_conf,
+            sources_used: 0,
+            status: PriceFeedStatus::Working,
+        }));
+    }
+    
+    let expo = prices.iter().map(|p| p.expo).min().unwrap_or_default();
+    let mut spot = Vec::with_capacity(prices.len());
+    let mut ema = Vec::with_capacity(prices.len());
+    for p in prices {
+        let factor = 10i128
+            .checked_pow((p.expo - expo) as u32)
+            .ok_or(AerospacerOracleError::Overflow)?;
+        spot.push((p.price as i128 * factor, p.conf as i128 * factor));
+        ema.push((p.ema_price as i128 * factor, p.ema_conf as i128 * factor));
+    }
+    let (price, conf) = middle(&mut spot);
+    let (ema_price, ema_conf) = middle(&mut ema);
+    
+    let spread = spot[spot.len() - 1].0 - spot[0].0;
+    if spread * BPS_DENOMINATOR as i128 > price * max_deviation_bps as i128 {
+        return Ok(Err(FeedFailure {
+            error: AerospacerOracleError::PriceDeviationTooHigh.into(),
+            status: PriceFeedStatus::Broken,
+        }));
+    }
+    
+    let to_i64 = |value: i128| i64::try_from(value).map_err(|_| AerospacerOracleError::Overflow);
+    let to_u64 = |value: i128| u64::try_from(value).map_err(|_| AerospacerOracleError::Overflow);
+    Ok(Ok(AssetPrice {
+        price: to_i64(price)?,
+        conf: to_u64(conf)?,
+        expo,
+        publish_time,
+        ema_price: to_i64(ema_price)?,
+        ema_conf: to_u64(ema_conf)?,
+        sources_used: 0,
+        status: PriceFeedStatus::Working,
+    }))
+}
+
+/// Sort (value, confidence) pairs by value and take the middle one; for an even count
+/// the mean of the middle two, with the wider confidence
+fn middle(values: &mut [(i128, i128)]) -> (i128, i128) {
+    values.sort_unstable_by_key(|(value, _)| *value);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        let (low, high) = (values[middle - 1], values[middle]);
+        ((low.0 + high.0) / 2, low.1.max(high.1))
+    } else {
+        values[middle]
+    }
+}
+
+/// Whether `conf` is at most `max_bps` basis points of a positive `price`
+pub fn confidence_within_limit(price: i64, conf: u64, max_bps: u16) -> bool {
+    (conf as u128) * (BPS_DENOMINATOR as u128) <= (price as u128) * (max_bps as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use crate::state::normalize_price;
+
+    #[test]
+    fn test_positive_exponent_price() {
+        // $500 set as 5 × 10^2
+        let source = SourceConfig {
+            source: PriceSource::Fixed { price: 5, exponent: 2 },
+            price_account: Pubkey::default(),
+            feed_id: [0; 32],
+            pyth_program: Pubkey::default(),
+        };
+        let clock = Clock { unix_timestamp: 1_700_000_000, ..Clock::default() };
+        let key = Pubkey::default();
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = [];
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        let price = FixedPrice::read(&account, &source, &clock).unwrap();
+        assert!(check_price(&price, &AssetConfig::zeroed(), &clock).is_ok());
+        assert_eq!(normalize_price(price.price as i128, price.expo).unwrap(), 500 * 10u128.pow(18));
+    }
+}
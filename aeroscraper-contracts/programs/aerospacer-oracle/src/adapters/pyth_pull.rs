@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use crate::state::SourceConfig;
+use crate::error::AerospacerOracleError;
+use super::{PriceAdapter, SourcePrice};
+
+/// Pyth Solana receiver program, owner of PriceUpdateV2 accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of PriceUpdateV2 (sha256("account:PriceUpdateV2")[..8])
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Pyth pull oracle price update, as posted by the receiver program. Mirrors
+/// pyth_solana_receiver_sdk::price_update::PriceUpdateV2
+#[derive(AnchorDeserialize)]
+struct PriceUpdateV2 {
+    _write_authority: Pubkey,
+    verification_level: VerificationLevel,
+    price_message: PriceFeedMessage,
+    _posted_slot: u64,
+}
+
+#[derive(AnchorDeserialize, PartialEq, Eq)]
+enum VerificationLevel {
+    Partial { _num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorDeserialize)]
+struct PriceFeedMessage {
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+    _prev_publish_time: i64,
+    ema_price: i64,
+    ema_conf: u64,
+}
+
+/// Pyth pull oracle `PriceUpdateV2` account
+///
+/// Update accounts can be posted fresh for each transaction, so the account is matched by
+/// the feed id it carries. A source configured with a price account (such as a sponsored
+/// feed account) is also pinned to that address; the default address accepts any update.
+/// Only fully verified updates are accepted.
+pub struct PythPull;
+
+impl PriceAdapter for PythPull {
+    fn read(price_account: &AccountInfo, source: &SourceConfig, _clock: &Clock) -> Result<SourcePrice> {
+        if source.price_account != Pubkey::default() {
+            require_keys_eq!(
+                price_account.key(),
+                source.price_account,
+                AerospacerOracleError::PriceSourceMismatch
+            );
+        }
+        require_keys_eq!(
+            *price_account.owner,
+            PYTH_RECEIVER_PROGRAM_ID,
+            AerospacerOracleError::InvalidPriceAccountOwner
+        );
+
+        let data = price_account.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            AerospacerOracleError::PythPriceFeedLoadFailed
+        );
+        let update = PriceUpdateV2::deserialize(&mut &data[8..])
+            .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
+
+        require!(
+            update.verification_level == VerificationLevel::Full,
+            AerospacerOracleError::PythPriceValidationFailed
+        );
+
+        let message = update.price_message;
+        require!(
+            message.feed_id == source.feed_id,
+            AerospacerOracleError::PriceFeedIdMismatch
+        );
+
+        Ok(SourcePrice {
+            price: message.price,
+            conf: message.conf,
+            expo: message.exponent,
+            publish_time: message.publish_time,
+            ema_price: message.ema_price,
+            ema_conf: message.ema_conf,
+        })
+    }
+}
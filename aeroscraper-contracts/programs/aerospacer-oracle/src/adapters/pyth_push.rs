@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::state::SourceConfig;
+use crate::error::AerospacerOracleError;
+use super::{PriceAdapter, SourcePrice};
+
+/// Legacy Pyth push oracle price account, read with the Pyth SDK
+///
+/// The account is pinned to the source's configured address and must be owned by the
+/// oracle's configured Pyth program. A push account's feed id is its address on Pythnet,
+/// which mainnet shares, so it has to equal the source's feed id; devnet accounts live at
+/// other addresses, and the `devnet` build skips that check.
+pub struct PythPush;
+
+impl PriceAdapter for PythPush {
+    fn read(price_account: &AccountInfo, source: &SourceConfig, _clock: &Clock) -> Result<SourcePrice> {
+        require_keys_eq!(
+            price_account.key(),
+            source.price_account,
+            AerospacerOracleError::PriceSourceMismatch
+        );
+        require_keys_eq!(
+            *price_account.owner,
+            source.pyth_program,
+            AerospacerOracleError::InvalidPriceAccountOwner
+        );
+
+        let price_feed = SolanaPriceAccount::account_info_to_feed(price_account)
+            .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
+        if !cfg!(feature = "devnet") {
+            require!(
+                price_feed.id.to_bytes() == source.feed_id,
+                AerospacerOracleError::PriceFeedIdMismatch
+            );
+        }
+
+        let price = price_feed.get_price_unchecked();
+        let ema = price_feed.get_ema_price_unchecked();
+        Ok(SourcePrice {
+            price: price.price,
+            conf: price.conf,
+            expo: price.expo,
+            publish_time: price.publish_time,
+            ema_price: ema.price,
+            ema_conf: ema.conf,
+        })
+    }
+}
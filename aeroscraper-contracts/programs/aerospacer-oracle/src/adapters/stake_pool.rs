@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use crate::state::{PriceSource, SourceConfig, BPS_DENOMINATOR};
+use crate::error::AerospacerOracleError;
+use super::{PriceAdapter, SourcePrice};
+
+/// SPL stake pool program, owner of stake pool accounts
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Exponent of the exchange rate: pool tokens and SOL both have 9 decimals
+pub const STAKE_POOL_EXPONENT: i32 = -9;
+
+/// AccountType::StakePool, the first byte of an initialized stake pool
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Leading fields of an SPL stake pool account, up to the exchange rate and the epoch it
+/// was last updated in. Mirrors spl_stake_pool::state::StakePool
+#[derive(AnchorDeserialize)]
+struct StakePool {
+    account_type: u8,
+    _manager: Pubkey,
+    _staker: Pubkey,
+    _stake_deposit_authority: Pubkey,
+    _stake_withdraw_bump_seed: u8,
+    _validator_list: Pubkey,
+    _reserve_stake: Pubkey,
+    _pool_mint: Pubkey,
+    _manager_fee_account: Pubkey,
+    _token_program_id: Pubkey,
+    total_lamports: u64,
+    pool_token_supply: u64,
+    last_update_epoch: u64,
+}
+
+/// SPL stake pool exchange rate
+///
+/// The pool account is pinned to the source's configured address and must be owned by
+/// the stake pool program. The rate is `total_lamports / pool_token_supply` SOL per pool
+/// token, less the source's haircut, with no confidence interval. The pool refreshes it
+/// once per epoch: a rate updated in the current or previous epoch counts as published
+/// now, an older one as published at time 0, so it reads as stale.
+pub struct SplStakePool;
+
+impl PriceAdapter for SplStakePool {
+    fn read(price_account: &AccountInfo, source: &SourceConfig, clock: &Clock) -> Result<SourcePrice> {
+        let PriceSource::SplStakePool { haircut_bps } = source.source else {
+            return err!(AerospacerOracleError::InvalidPriceSource);
+        };
+        require_keys_eq!(
+            price_account.key(),
+            source.price_account,
+            AerospacerOracleError::PriceSourceMismatch
+        );
+        require_keys_eq!(
+            *price_account.owner,
+            SPL_STAKE_POOL_PROGRAM_ID,
+            AerospacerOracleError::InvalidPriceAccountOwner
+        );
+
+        let pool = StakePool::deserialize(&mut &price_account.try_borrow_data()?[..])
+            .map_err(|_| AerospacerOracleError::InvalidPriceData)?;
+        require!(
+            pool.account_type == STAKE_POOL_ACCOUNT_TYPE && pool.pool_token_supply > 0,
+            AerospacerOracleError::InvalidPriceData
+        );
+
+        let rate = (pool.total_lamports as u128)
+            .checked_mul(10u128.pow(STAKE_POOL_EXPONENT.unsigned_abs()))
+            .and_then(|lamports| lamports.checked_mul((BPS_DENOMINATOR - haircut_bps) as u128))
+            .map(|value| value / pool.pool_token_supply as u128 / BPS_DENOMINATOR as u128)
+            .and_then(|rate| i64::try_from(rate).ok())
+            .ok_or(AerospacerOracleError::Overflow)?;
+        let publish_time = if pool.last_update_epoch.saturating_add(1) >= clock.epoch {
+            clock.unix_timestamp
+        } else {
+            0
+        };
+
+        Ok(SourcePrice {
+            price: rate,
+            conf: 0,
+            expo: STAKE_POOL_EXPONENT,
+            publish_time,
+            ema_price: rate,
+            ema_conf: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL_LEN: usize = 282;
+
+    fn pool_data(total_lamports: u64, pool_token_supply: u64, last_update_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; POOL_LEN];
+        data[0] = STAKE_POOL_ACCOUNT_TYPE;
+        data[258..266].copy_from_slice(&total_lamports.to_le_bytes());
+        data[266..274].copy_from_slice(&pool_token_supply.to_le_bytes());
+        data[274..282].copy_from_slice(&last_update_epoch.to_le_bytes());
+        data
+    }
+
+    fn source(pool: Pubkey, haircut_bps: u16) -> SourceConfig {
+        SourceConfig {
+            source: PriceSource::SplStakePool { haircut_bps },
+            price_account: pool,
+            feed_id: [0; 32],
+            pyth_program: Pubkey::default(),
+        }
+    }
+
+    fn clock(epoch: u64) -> Clock {
+        Clock { epoch, unix_timestamp: 1_700_000_000, ..Clock::default() }
+    }
+
+    fn read(data: &mut [u8], owner: &Pubkey, source: &SourceConfig, clock: &Clock) -> Result<SourcePrice> {
+        let key = source.price_account;
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        SplStakePool::read(&account, source, clock)
+    }
+
+    #[test]
+    fn test_exchange_rate_with_haircut() {
+        // 1.1 SOL per pool token
+        let pool = Pubkey::new_unique();
+        let mut data = pool_data(1_100_000_000_000, 1_000_000_000_000, 500);
+
+        let price = read(&mut data, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 0), &clock(500)).unwrap();
+        assert_eq!((price.price, price.conf, price.expo), (1_100_000_000, 0, -9));
+        assert_eq!(price.publish_time, 1_700_000_000);
+
+        // A 1% haircut
+        let price = read(&mut data, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 100), &clock(500)).unwrap();
+        assert_eq!(price.price, 1_089_000_000);
+        assert_eq!(price.ema_price, price.price);
+    }
+
+    #[test]
+    fn test_rate_goes_stale_after_an_epoch() {
+        let pool = Pubkey::new_unique();
+        let mut data = pool_data(1_100_000_000_000, 1_000_000_000_000, 500);
+
+        let price = read(&mut data, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 0), &clock(501)).unwrap();
+        assert_eq!(price.publish_time, 1_700_000_000);
+        let price = read(&mut data, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 0), &clock(502)).unwrap();
+        assert_eq!(price.publish_time, 0);
+    }
+
+    #[test]
+    fn test_rejects_foreign_or_empty_pools() {
+        let pool = Pubkey::new_unique();
+        let mut data = pool_data(1_100_000_000_000, 1_000_000_000_000, 500);
+
+        // Owned by another program
+        assert!(read(&mut data, &Pubkey::new_unique(), &source(pool, 0), &clock(500)).is_err());
+
+        // No pool tokens minted yet
+        let mut empty = pool_data(0, 0, 500);
+        assert!(read(&mut empty, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 0), &clock(500)).is_err());
+
+        // Not an initialized stake pool
+        data[0] = 0;
+        assert!(read(&mut data, &SPL_STAKE_POOL_PROGRAM_ID, &source(pool, 0), &clock(500)).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::{Owner, PullFeedAccountData, PRECISION};
+use crate::state::SourceConfig;
+use crate::error::AerospacerOracleError;
+use super::{PriceAdapter, SourcePrice};
+
+/// Exponent Switchboard results are rescaled to (they carry 18 decimals, which overflow i64)
+pub const SWITCHBOARD_EXPONENT: i32 = -8;
+
+/// Switchboard on-demand pull feed
+///
+/// The feed account is pinned to the source's configured address and its job hash to
+/// its feed id. The feed's current result is used, with its standard deviation as
+/// the confidence interval.
+pub struct SwitchboardOnDemand;
+
+impl PriceAdapter for SwitchboardOnDemand {
+    fn read(price_account: &AccountInfo, source: &SourceConfig, _clock: &Clock) -> Result<SourcePrice> {
+        require_keys_eq!(
+            price_account.key(),
+            source.price_account,
+            AerospacerOracleError::PriceSourceMismatch
+        );
+        require_keys_eq!(
+            *price_account.owner,
+            PullFeedAccountData::owner(),
+            AerospacerOracleError::InvalidPriceAccountOwner
+        );
+
+        let feed = PullFeedAccountData::parse(price_account.try_borrow_data()?)
+            .map_err(|_| AerospacerOracleError::PriceFeedUnavailable)?;
+        require!(
+            feed.feed_hash == source.feed_id,
+            AerospacerOracleError::PriceFeedIdMismatch
+        );
+
+        // A result slot of 0 means no quorum has been reached yet
+        let result = &feed.result;
+        require!(result.slot != 0, AerospacerOracleError::PriceFeedUnavailable);
+
+        // Switchboard feeds have no moving average; the current result stands in for it
+        let price = rescale(result.value)?;
+        let conf = rescale(result.std_dev)?.unsigned_abs();
+        Ok(SourcePrice {
+            price,
+            conf,
+            expo: SWITCHBOARD_EXPONENT,
+            publish_time: feed.result_ts(),
+            ema_price: price,
+            ema_conf: conf,
+        })
+    }
+}
+
+/// Convert an 18-decimal Switchboard value to SWITCHBOARD_EXPONENT
+fn rescale(value: i128) -> Result<i64> {
+    let divisor = 10i128.pow(PRECISION - (-SWITCHBOARD_EXPONENT) as u32);
+    i64::try_from(value / divisor).map_err(|_| AerospacerOracleError::InvalidPriceData.into())
+}
@@ -49,4 +49,100 @@ pub enum AerospacerOracleError {
     
     #[msg("Pyth price account validation failed")]
     PythPriceAccountValidationFailed,
-}
\ No newline at end of file
+    
+    #[msg("Operation is paused by the guardian")]
+    OperationPaused,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid address")]
+    InvalidAddress,
+    
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    
+    #[msg("Parameter changes must be queued through the timelock")]
+    TimelockActive,
+    
+    #[msg("Queued change is not executable yet")]
+    ChangeNotReady,
+    
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Asset config account does not match the denom or registry index")]
+    InvalidAssetAccount,
+    
+    #[msg("Oracle state must be migrated to the current version first")]
+    StateNotMigrated,
+    
+    #[msg("Invalid price source configuration")]
+    InvalidPriceSource,
+    
+    #[msg("Price account does not match the asset's price source")]
+    PriceSourceMismatch,
+    
+    #[msg("Wrong number of price accounts for the asset's sources")]
+    InvalidPriceAccounts,
+    
+    #[msg("Too few price sources are fresh and within their confidence limit")]
+    InsufficientPriceSources,
+    
+    #[msg("Price sources disagree by more than the asset's deviation limit")]
+    PriceDeviationTooHigh,
+    
+    #[msg("Invalid price policy")]
+    InvalidPricePolicy,
+    
+    #[msg("Price history does not cover the requested window")]
+    InsufficientPriceHistory,
+    
+    #[msg("The asset's price policy needs its price history account")]
+    PriceHistoryRequired,
+    
+    #[msg("Invalid price bounds")]
+    InvalidPriceBounds,
+    
+    #[msg("Price is outside the asset's bounds")]
+    PriceOutOfBounds,
+    
+    #[msg("Price moved more than the asset's change limit")]
+    PriceChangeTooLarge,
+    
+    #[msg("Price account is not owned by the program its price source requires")]
+    InvalidPriceAccountOwner,
+    
+    #[msg("Price account carries a different feed than the asset's price id")]
+    PriceFeedIdMismatch,
+    
+    #[msg("Invalid composite price configuration")]
+    InvalidCompositePrice,
+    
+    #[msg("Invalid peg band, or the asset is not pegged")]
+    InvalidPegConfig,
+    
+    #[msg("The guardian has frozen the pegged asset's price")]
+    PegFrozen,
+    
+    #[msg("Price keeper list is full")]
+    TooManyUpdaters,
+    
+    #[msg("Account is already a price keeper")]
+    UpdaterAlreadyAdded,
+    
+    #[msg("Account is not a price keeper")]
+    UpdaterNotFound,
+    
+    #[msg("Queued change was encoded by an older program version; cancel and queue it again")]
+    StaleQueuedChange,
+    
+    #[msg("Price history already exists for this asset")]
+    PriceHistoryExists,
+}
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use crate::state::{OracleStateAccount, AssetConfig, AssetState, CompositePrice, PriceBounds, PriceFeedStatus, PricePolicy, PriceSource, Role};
+use crate::adapters::AssetPrice;
+
+/// Snapshot of the oracle-wide parameters, used for before/after pairs in `ParamsChanged`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct OracleParams {
+    /// External oracle provider address
+    pub oracle_address: Pubkey,
+    
+    /// Timelock delay in seconds
+    pub timelock_delay: i64,
+}
+
+impl From<&OracleStateAccount> for OracleParams {
+    fn from(state: &OracleStateAccount) -> Self {
+        Self {
+            oracle_address: state.oracle_address,
+            timelock_delay: state.timelock_delay,
+        }
+    }
+}
+
+/// Emitted once when the oracle state is created
+#[event]
+pub struct OracleInitialized {
+    pub admin: Pubkey,
+    pub params: OracleParams,
+    pub timestamp: i64,
+}
+
+/// Emitted when an oracle-wide parameter changes (directly or through the timelock)
+#[event]
+pub struct ParamsChanged {
+    /// Signer of the direct instruction, or proposer of a timelocked change
+    pub authority: Pubkey,
+    pub before: OracleParams,
+    pub after: OracleParams,
+    pub timestamp: i64,
+}
+
+/// Emitted when an asset's price feed is added or replaced
+#[event]
+pub struct PriceConfigured {
+    pub authority: Pubkey,
+    pub denom: String,
+    pub decimal: u8,
+    pub price_id: String,
+    pub pyth_price_account: Pubkey,
+    pub max_staleness_secs: u32,
+    pub max_confidence_bps: u16,
+    pub source: PriceSource,
+    pub source_count: u8,
+    pub min_sources: u8,
+    pub max_deviation_bps: u16,
+    pub price_policy: PricePolicy,
+    pub price_bounds: PriceBounds,
+    pub composite: CompositePrice,
+    pub peg_band_bps: u16,
+    pub configured_at: i64,
+    
+    /// Previous feed configuration, None when the asset is new
+    pub previous_decimal: Option<u8>,
+    pub previous_price_id: Option<String>,
+    pub previous_pyth_price_account: Option<Pubkey>,
+    pub previous_max_staleness_secs: Option<u32>,
+    pub previous_max_confidence_bps: Option<u16>,
+    pub previous_source: Option<PriceSource>,
+    pub previous_source_count: Option<u8>,
+    pub previous_min_sources: Option<u8>,
+    pub previous_max_deviation_bps: Option<u16>,
+    pub previous_price_policy: Option<PricePolicy>,
+    pub previous_price_bounds: Option<PriceBounds>,
+    pub previous_composite: Option<CompositePrice>,
+    pub previous_peg_band_bps: Option<u16>,
+}
+
+impl PriceConfigured {
+    pub fn new(authority: Pubkey, current: &AssetConfig, previous: Option<&AssetConfig>) -> Result<Self> {
+        Ok(Self {
+            authority,
+            denom: current.denom(),
+            decimal: current.decimal,
+            price_id: current.price_id(),
+            pyth_price_account: current.pyth_price_account,
+            max_staleness_secs: current.max_staleness_secs,
+            max_confidence_bps: current.max_confidence_bps,
+            source: current.source()?,
+            source_count: current.source_total() as u8,
+            min_sources: current.min_fresh_sources() as u8,
+            max_deviation_bps: current.max_deviation_bps,
+            price_policy: current.price_policy()?,
+            price_bounds: current.price_bounds(),
+            composite: current.composite()?,
+            peg_band_bps: current.peg_band_bps,
+            configured_at: current.configured_at,
+            previous_decimal: previous.map(|d| d.decimal),
+            previous_price_id: previous.map(|d| d.price_id()),
+            previous_pyth_price_account: previous.map(|d| d.pyth_price_account),
+            previous_max_staleness_secs: previous.map(|d| d.max_staleness_secs),
+            previous_max_confidence_bps: previous.map(|d| d.max_confidence_bps),
+            previous_source: previous.map(|d| d.source()).transpose()?,
+            previous_source_count: previous.map(|d| d.source_total() as u8),
+            previous_min_sources: previous.map(|d| d.min_fresh_sources() as u8),
+            previous_max_deviation_bps: previous.map(|d| d.max_deviation_bps),
+            previous_price_policy: previous.map(|d| d.price_policy()).transpose()?,
+            previous_price_bounds: previous.map(|d| d.price_bounds()),
+            previous_composite: previous.map(|d| d.composite()).transpose()?,
+            previous_peg_band_bps: previous.map(|d| d.peg_band_bps),
+        })
+    }
+}
+
+/// Emitted when an asset is removed from the oracle
+#[event]
+pub struct PriceConfigRemoved {
+    pub authority: Pubkey,
+    pub denom: String,
+    pub price_id: String,
+    pub pyth_price_account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a Pyth price is pulled for an asset
+#[event]
+pub struct PriceUpdated {
+    pub denom: String,
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a price read is recorded on its asset account: the feed status always,
+/// and the price as the last good price when the feed is live
+#[event]
+pub struct PriceRecorded {
+    pub denom: String,
+    pub status: PriceFeedStatus,
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub timestamp: i64,
+}
+
+impl PriceRecorded {
+    pub fn new(denom: String, price: &AssetPrice, timestamp: i64) -> Self {
+        Self {
+            denom,
+            status: price.status,
+            price: price.price,
+            confidence: price.conf,
+            exponent: price.expo,
+            publish_time: price.publish_time,
+            timestamp,
+        }
+    }
+}
+
+/// Emitted when an asset's price history account is created
+#[event]
+pub struct PriceHistoryCreated {
+    /// Signer of the direct instruction, or proposer of a timelocked change
+    pub authority: Pubkey,
+    pub denom: String,
+    pub capacity: u16,
+}
+
+/// Emitted when a role is granted (new_holder set) or revoked (new_holder default)
+#[event]
+pub struct RoleChanged {
+    pub role: Role,
+    pub previous_holder: Pubkey,
+    pub new_holder: Pubkey,
+}
+
+/// Emitted when a price keeper is registered or removed
+#[event]
+pub struct UpdaterChanged {
+    pub authority: Pubkey,
+    pub updater: Pubkey,
+    pub added: bool,
+    pub updater_count: u8,
+}
+
+/// Emitted when the admin proposes a successor
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// Emitted when a pending admin transfer is withdrawn
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// Emitted when the proposed admin accepts
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// Emitted when pause flags change
+#[event]
+pub struct PauseChanged {
+    pub authority: Pubkey,
+    pub paused_before: u8,
+    pub paused_after: u8,
+}
+
+/// Emitted when the guardian freezes a pegged asset's price, or the owner unfreezes it
+#[event]
+pub struct PegFreezeChanged {
+    pub authority: Pubkey,
+    pub denom: String,
+    pub frozen: bool,
+}
+
+/// Emitted when an asset moves to another lifecycle state
+#[event]
+pub struct AssetStateChanged {
+    pub authority: Pubkey,
+    pub denom: String,
+    pub previous_state: AssetState,
+    pub state: AssetState,
+}
+
+/// Emitted when a change enters the timelock
+#[event]
+pub struct ChangeQueued {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub eta: i64,
+}
+
+/// Emitted when a queued change is applied
+#[event]
+pub struct ChangeExecuted {
+    pub id: u64,
+    pub executor: Pubkey,
+}
+
+/// Emitted when the guardian drops a queued change
+#[event]
+pub struct ChangeCancelled {
+    pub id: u64,
+    pub authority: Pubkey,
+}
+
+/// Emitted when the admin upgrades the oracle state account to the current layout
+#[event]
+pub struct StateMigrated {
+    pub admin: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub data_len: u64,
+}
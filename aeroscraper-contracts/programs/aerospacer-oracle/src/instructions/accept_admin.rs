@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::OracleStateAccount;
+use crate::error::AerospacerOracleError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.pending_admin != Pubkey::default() @ AerospacerOracleError::NoPendingAdmin,
+        constraint = state.pending_admin == new_admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous = state.admin;
+    
+    state.admin = state.pending_admin;
+    state.pending_admin = Pubkey::default();
+    state.last_update = ctx.accounts.clock.unix_timestamp;
+    
+    msg!("Admin transferred: {} -> {}", previous, state.admin);
+    
+    emit_cpi!(AdminTransferred {
+        previous_admin: previous,
+        new_admin: state.admin,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Role, Versioned, MAX_UPDATERS};
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AddUpdaterParams {
+    /// Price keeper to register
+    pub updater: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: AddUpdaterParams)]
+pub struct AddUpdater<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OracleManager, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<AddUpdater>, params: AddUpdaterParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    let state = &mut ctx.accounts.state;
+    apply(state, &params, &ctx.accounts.clock)?;
+    
+    emit_cpi!(UpdaterChanged {
+        authority: ctx.accounts.authority.key(),
+        updater: params.updater,
+        added: true,
+        updater_count: state.updaters.len() as u8,
+    });
+    
+    Ok(())
+}
+
+/// Register a price keeper (shared with timelocked execution)
+pub fn apply(state: &mut OracleStateAccount, params: &AddUpdaterParams, clock: &Clock) -> Result<()> {
+    // The keeper list lives in the bytes migrate_state adds
+    require!(
+        state.version() >= OracleStateAccount::VERSION,
+        AerospacerOracleError::StateNotMigrated
+    );
+    require!(
+        params.updater != Pubkey::default(),
+        AerospacerOracleError::InvalidAddress
+    );
+    require!(
+        !state.updaters.contains(&params.updater),
+        AerospacerOracleError::UpdaterAlreadyAdded
+    );
+    require!(
+        state.updaters.len() < MAX_UPDATERS,
+        AerospacerOracleError::TooManyUpdaters
+    );
+    
+    state.updaters.push(params.updater);
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Price keeper added: {} ({} registered)", params.updater, state.updaters.len());
+    
+    Ok(())
+}
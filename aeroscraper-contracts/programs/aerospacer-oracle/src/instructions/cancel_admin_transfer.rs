@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::OracleStateAccount;
+use crate::error::AerospacerOracleError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized,
+        constraint = state.pending_admin != Pubkey::default() @ AerospacerOracleError::NoPendingAdmin
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    msg!("Admin transfer to {} cancelled", state.pending_admin);
+    
+    emit_cpi!(AdminTransferCancelled {
+        admin: state.admin,
+        pending_admin: state.pending_admin,
+    });
+    
+    state.pending_admin = Pubkey::default();
+    state.last_update = ctx.accounts.clock.unix_timestamp;
+    
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelChangeParams {
+    /// Id of the queued change
+    pub id: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CancelChangeParams)]
+pub struct CancelChange<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Guardian, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"timelock", params.id.to_le_bytes().as_ref()],
+        bump,
+        close = proposer
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Rent refund destination, must match the recorded proposer
+    #[account(
+        mut,
+        address = queued_change.proposer @ AerospacerOracleError::Unauthorized
+    )]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelChange>, params: CancelChangeParams) -> Result<()> {
+    msg!("Change {} cancelled by {}", params.id, ctx.accounts.authority.key());
+    
+    emit_cpi!(ChangeCancelled {
+        id: params.id,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
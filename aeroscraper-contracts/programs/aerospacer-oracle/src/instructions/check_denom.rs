@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::*;
+use crate::registry;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CheckDenomParams {
@@ -10,20 +10,17 @@ pub struct CheckDenomParams {
 #[derive(Accounts)]
 #[instruction(params: CheckDenomParams)]
 pub struct CheckDenom<'info> {
+    /// CHECK: Asset config PDA for the denom; it only exists once the asset is configured
     #[account(
-        seeds = [b"state"],
+        seeds = [b"asset", params.denom.as_bytes()],
         bump
     )]
-    pub state: Account<'info, OracleStateAccount>,
+    pub asset: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<CheckDenom>, params: CheckDenomParams) -> Result<bool> {
-    let state = &ctx.accounts.state;
-    
-    // Check if the denom exists in collateral data
-    let exists = state.collateral_data
-        .iter()
-        .any(|d| d.denom == params.denom);
+    // The asset is supported if its PDA has been created
+    let exists = registry::load_asset(&ctx.accounts.asset).is_ok();
     
     msg!("Check denom query successful");
     msg!("Denom: {}", params.denom);
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::instructions::{
+    update_oracle_address, set_data, set_data_batch, remove_data, set_asset_state, set_peg_frozen,
+    add_updater, remove_updater, init_price_history, migrate_asset,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteChangeParams {
+    /// Id of the queued change
+    pub id: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: ExecuteChangeParams)]
+pub struct ExecuteChange<'info> {
+    /// Pays rent for asset PDAs the change creates and receives it from ones it closes
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"timelock", params.id.to_le_bytes().as_ref()],
+        bump,
+        close = proposer
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Rent refund destination, must match the recorded proposer
+    #[account(
+        mut,
+        address = queued_change.proposer @ AerospacerOracleError::Unauthorized
+    )]
+    pub proposer: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+    
+    // remaining_accounts: the asset config PDAs the change touches
+    //   SetData: [asset]
+    //   SetDataBatch: [asset per entry, in order]
+    //   RemoveData: [asset, asset at the last registry index]
+    //   SetAssetState, SetPegFrozen, MigrateAsset: [asset]
+    //   InitPriceHistory: [asset, price history]
+}
+
+/// Permissionless: authorization happened when the change was queued
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteChange<'info>>, params: ExecuteChangeParams) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let proposer = queued_change.proposer;
+    let change = queued_change.change()?;
+    let clock = &ctx.accounts.clock;
+    let executor = ctx.accounts.executor.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let assets = ctx.remaining_accounts;
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        clock.unix_timestamp >= queued_change.eta,
+        AerospacerOracleError::ChangeNotReady
+    );
+    
+    match &change {
+        TimelockedChange::UpdateOracleAddress(change) => {
+            let before = OracleParams::from(&**state);
+            update_oracle_address::apply(state, change, clock)?;
+            emit_cpi!(ParamsChanged {
+                authority: proposer,
+                before,
+                after: OracleParams::from(&**state),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        TimelockedChange::SetData(change) => {
+            require!(!state.is_paused(PAUSE_ASSET_CONFIG), AerospacerOracleError::OperationPaused);
+            let asset = assets.first().ok_or(AerospacerOracleError::InvalidAssetAccount)?;
+            let (current, previous) = set_data::apply(state, asset, &executor, &system_program, change, clock)?;
+            emit_cpi!(PriceConfigured::new(proposer, &current, previous.as_ref())?);
+        }
+        TimelockedChange::SetDataBatch(change) => {
+            require!(!state.is_paused(PAUSE_ASSET_CONFIG), AerospacerOracleError::OperationPaused);
+            let configured = set_data_batch::apply(state, assets, &executor, &system_program, change, clock)?;
+            for (current, previous) in configured {
+                emit_cpi!(PriceConfigured::new(proposer, &current, previous.as_ref())?);
+            }
+        }
+        TimelockedChange::RemoveData(change) => {
+            require!(!state.is_paused(PAUSE_ASSET_CONFIG), AerospacerOracleError::OperationPaused);
+            let [asset, last_asset] = assets else {
+                return Err(AerospacerOracleError::InvalidAssetAccount.into());
+            };
+            let removed = remove_data::apply(state, asset, last_asset, &executor, change, clock)?;
+            emit_cpi!(PriceConfigRemoved {
+                authority: proposer,
+                denom: removed.denom,
+                price_id: removed.price_id,
+                pyth_price_account: removed.pyth_price_account,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        TimelockedChange::SetTimelockDelay(delay) => {
+            let before = OracleParams::from(&**state);
+            state.timelock_delay = *delay;
+            state.last_update = clock.unix_timestamp;
+            msg!("Timelock delay updated: {} seconds", delay);
+            emit_cpi!(ParamsChanged {
+                authority: proposer,
+                before,
+                after: OracleParams::from(&**state),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        TimelockedChange::SetAssetState(change) => {
+            let asset = assets.first().ok_or(AerospacerOracleError::InvalidAssetAccount)?;
+            let previous_state = set_asset_state::apply(state, asset, change, clock)?;
+            emit_cpi!(AssetStateChanged {
+                authority: proposer,
+                denom: change.denom.clone(),
+                previous_state,
+                state: change.state,
+            });
+        }
+        TimelockedChange::SetPegFrozen(change) => {
+            let asset = assets.first().ok_or(AerospacerOracleError::InvalidAssetAccount)?;
+            set_peg_frozen::apply(state, asset, change, clock)?;
+            emit_cpi!(PegFreezeChanged {
+                authority: proposer,
+                denom: change.denom.clone(),
+                frozen: change.frozen,
+            });
+        }
+        TimelockedChange::AddUpdater(change) => {
+            add_updater::apply(state, change, clock)?;
+            emit_cpi!(UpdaterChanged {
+                authority: proposer,
+                updater: change.updater,
+                added: true,
+                updater_count: state.updaters.len() as u8,
+            });
+        }
+        TimelockedChange::RemoveUpdater(change) => {
+            remove_updater::apply(state, change, clock)?;
+            emit_cpi!(UpdaterChanged {
+                authority: proposer,
+                updater: change.updater,
+                added: false,
+                updater_count: state.updaters.len() as u8,
+            });
+        }
+        TimelockedChange::InitPriceHistory(change) => {
+            let [asset, price_history] = assets else {
+                return Err(AerospacerOracleError::InvalidAssetAccount.into());
+            };
+            init_price_history::apply(asset, price_history, &executor, &system_program, change)?;
+            emit_cpi!(PriceHistoryCreated {
+                authority: proposer,
+                denom: change.denom.clone(),
+                capacity: PRICE_HISTORY_LEN as u16,
+            });
+        }
+        TimelockedChange::MigrateAsset(change) => {
+            let asset = assets.first().ok_or(AerospacerOracleError::InvalidAssetAccount)?;
+            if let Some(current) = migrate_asset::apply(state, asset, &executor, &system_program, change)? {
+                emit_cpi!(PriceConfigured::new(proposer, &current, None)?);
+            }
+        }
+    }
+    
+    msg!("Change {} executed by {}", params.id, ctx.accounts.executor.key());
+    
+    emit_cpi!(ChangeExecuted {
+        id: params.id,
+        executor: ctx.accounts.executor.key(),
+    });
+    
+    Ok(())
+}
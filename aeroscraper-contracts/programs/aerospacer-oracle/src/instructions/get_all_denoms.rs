@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::registry;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetAllDenomsParams {
-    // No parameters needed for all denoms query
+    /// Registry index of the first asset passed in remaining_accounts
+    pub start: u32,
 }
 
 #[derive(Accounts)]
@@ -14,22 +17,29 @@ pub struct GetAllDenoms<'info> {
         bump
     )]
     pub state: Account<'info, OracleStateAccount>,
+    
+    // remaining_accounts: asset config PDAs with indices start, start + 1, ...
 }
 
-pub fn handler(ctx: Context<GetAllDenoms>, _params: GetAllDenomsParams) -> Result<Vec<String>> {
+pub fn handler(ctx: Context<GetAllDenoms>, params: GetAllDenomsParams) -> Result<Vec<String>> {
     let state = &ctx.accounts.state;
+    require!(
+        params.start as usize + ctx.remaining_accounts.len() <= state.asset_count as usize,
+        AerospacerOracleError::InvalidAssetAccount
+    );
     
-    // Extract all denoms from collateral data, matching INJECTIVE's structure
-    let denoms: Vec<String> = state.collateral_data
-        .iter()
-        .map(|data| data.denom.clone())
-        .collect();
+    // Page over the asset PDAs passed in, checking they are consecutive registry entries
+    let mut denoms = Vec::with_capacity(ctx.remaining_accounts.len());
+    for (offset, asset_info) in ctx.remaining_accounts.iter().enumerate() {
+        let asset = registry::load_asset_at(asset_info, params.start + offset as u32)?;
+        denoms.push(asset.denom());
+    }
     
     msg!("All denoms query successful");
-    msg!("Found {} supported assets", denoms.len());
+    msg!("Found {} of {} supported assets from index {}", denoms.len(), state.asset_count, params.start);
     for denom in &denoms {
         msg!("- {}", denom);
     }
     
     Ok(denoms)
-}
\ No newline at end of file
+}
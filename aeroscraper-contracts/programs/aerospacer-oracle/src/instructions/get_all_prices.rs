@@ -1,16 +1,19 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
-use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::registry;
+use crate::adapters;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetAllPricesParams {
-    // No parameters needed for all prices query
+    /// Registry index of the first asset passed in remaining_accounts
+    pub start: u32,
 }
 
 #[derive(Accounts)]
 #[instruction(params: GetAllPricesParams)]
 pub struct GetAllPrices<'info> {
+    /// Oracle state, for the configured Pyth program that must own push price accounts
     #[account(
         seeds = [b"state"],
         bump
@@ -19,61 +22,37 @@ pub struct GetAllPrices<'info> {
     
     /// CHECK: Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
+    
+    // remaining_accounts: for the assets with indices start, start + 1, ..., the
+    // asset config PDA followed by one price account per configured source (and, for a
+    // composite, the other asset's config PDA and price accounts)
 }
 
-pub fn handler(ctx: Context<GetAllPrices>, _params: GetAllPricesParams) -> Result<Vec<PriceResponse>> {
-    let state = &ctx.accounts.state;
-    let _clock = &ctx.accounts.clock;
-    
-    // Get remaining accounts (should contain Pyth price accounts for each asset)
-    let remaining_accounts = &ctx.remaining_accounts;
-    
-    // Validate we have enough Pyth accounts for all assets
-    require!(
-        remaining_accounts.len() >= state.collateral_data.len(),
-        AerospacerOracleError::InvalidPriceData
-    );
+pub fn handler(ctx: Context<GetAllPrices>, params: GetAllPricesParams) -> Result<Vec<PriceResponse>> {
+    let clock = &ctx.accounts.clock;
     
+    // Get remaining accounts (an asset PDA followed by its price accounts, per asset)
+    let mut remaining_accounts = ctx.remaining_accounts;
     let mut prices = Vec::new();
 
-    // PRODUCTION PYTH INTEGRATION CODE
-    // For each collateral asset, fetch real price data using corresponding Pyth account
-    for (index, collateral_data) in state.collateral_data.iter().enumerate() {
-        // Get the corresponding Pyth price account from remaining_accounts
-        let pyth_price_account = &remaining_accounts[index];
-        
-        // Use Pyth SDK to load and validate price feed data (reusing get_price logic)
-        let price_feed = SolanaPriceAccount::account_info_to_feed(pyth_price_account)
-            .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
+    // For each collateral asset in the page, read its price from its configured sources
+    while let Some((asset_info, rest)) = remaining_accounts.split_first() {
+        let collateral_data = registry::load_asset_at(asset_info, params.start + prices.len() as u32)?;
+        let account_count = adapters::price_account_count(&collateral_data, rest)?;
+        require!(
+            rest.len() >= account_count,
+            AerospacerOracleError::InvalidPriceAccounts
+        );
+        let (price_accounts, rest) = rest.split_at(account_count);
+        remaining_accounts = rest;
         
-        // Get price with hardcoded staleness validation for mainnet (60 seconds)
-        // let current_time = clock.unix_timestamp;
-        // let price = price_feed.get_price_no_older_than(current_time, 60)
-        //     .ok_or(AerospacerOracleError::PriceTooOld)?;
+        let price = adapters::read_price_or_last_good(&collateral_data, price_accounts, &ctx.accounts.state.oracle_address, clock)?;
 
-        // Get the latest available price data (no staleness validation for devnet testing)
-        let price = price_feed.get_price_unchecked();
-
-        // Validate price data integrity with lenient confidence for devnet testing
-        require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
-        require!(price.conf >= 100, AerospacerOracleError::PythPriceValidationFailed); // Reduced from 1000 to 100 for devnet
-
-        let price_response = PriceResponse {
-            denom: collateral_data.denom.clone(),
-            price: price.price,
-            decimal: collateral_data.decimal,
-            timestamp: price.publish_time,
-            confidence: price.conf,
-            exponent: price.expo,
-        };
-        
-        prices.push(price_response);
+        prices.push(PriceResponse::new(collateral_data.denom(), &collateral_data, &price)?);
     }
     
     msg!("All prices query successful");
     msg!("Found {} price responses", prices.len());
-    msg!("Real Pyth data extracted for all assets using official SDK");
-    msg!("Each asset uses its own Pyth price account via remaining_accounts");
     for price in &prices {
         msg!("- {}: {} ± {} x 10^{}", price.denom, price.price, price.confidence, price.exponent);
     }
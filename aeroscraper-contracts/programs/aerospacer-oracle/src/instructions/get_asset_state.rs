@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::AssetState;
+use crate::registry;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetAssetStateParams {
+    /// Asset denomination (e.g., "inj", "atom")
+    pub denom: String,
+}
+
+#[derive(Accounts)]
+#[instruction(params: GetAssetStateParams)]
+pub struct GetAssetState<'info> {
+    /// CHECK: Asset config PDA for the denom, read with registry::load_asset
+    #[account(
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetAssetState>, params: GetAssetStateParams) -> Result<AssetState> {
+    let state = registry::load_asset(&ctx.accounts.asset)?.asset_state()?;
+    
+    msg!("Asset state query successful");
+    msg!("Denom: {}", params.denom);
+    msg!("State: {:?}", state);
+    
+    Ok(state)
+}
@@ -23,8 +23,15 @@ pub fn handler(ctx: Context<GetConfig>, _params: GetConfigParams) -> Result<Conf
     let config_response = ConfigResponse {
         admin: state.admin,
         oracle_address: state.oracle_address,
-        asset_count: state.collateral_data.len() as u32,
+        asset_count: state.asset_count,
         last_update: state.last_update,
+        guardian: state.guardian,
+        paused: state.paused,
+        risk_manager: state.risk_manager,
+        oracle_manager: state.oracle_manager,
+        pending_admin: state.pending_admin,
+        timelock_delay: state.timelock_delay,
+        updaters: state.updaters.clone(),
     };
     
     msg!("Config query successful");
@@ -32,6 +39,11 @@ pub fn handler(ctx: Context<GetConfig>, _params: GetConfigParams) -> Result<Conf
     msg!("Oracle Address: {}", state.oracle_address);
     msg!("Asset Count: {}", config_response.asset_count);
     msg!("Last Update: {}", config_response.last_update);
+    msg!("Guardian: {}", config_response.guardian);
+    msg!("Paused: {:#010b}", config_response.paused);
+    msg!("Oracle Manager: {}", config_response.oracle_manager);
+    msg!("Timelock Delay: {}s", config_response.timelock_delay);
+    msg!("Price Keepers: {}", config_response.updaters.len());
     msg!("Pyth Configuration: Hardcoded (60s staleness, 1000 confidence)");
     
     Ok(config_response)
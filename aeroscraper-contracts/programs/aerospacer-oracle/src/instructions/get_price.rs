@@ -1,103 +1,95 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
-use crate::error::AerospacerOracleError;
-use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::adapters::{self, AssetPrice};
+use crate::registry;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetPriceParams {
     pub denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: GetPriceParams)]
 pub struct GetPrice<'info> {
+    /// Oracle state, for the configured Pyth program that must own push price accounts
     #[account(
         seeds = [b"state"],
         bump
     )]
     pub state: Account<'info, OracleStateAccount>,
     
-    /// CHECK: This is the Pyth price account that contains the price data
+    /// CHECK: Asset config PDA for the denom, read with registry::load_asset. When passed
+    /// writable, the read is recorded as the asset's last good price and feed status, and
+    /// PriceRecorded is emitted
+    #[account(
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Price account for the asset's primary source (Pyth push/pull or Switchboard
+    /// feed), checked by its adapter; ignored for fixed prices
     pub pyth_price_account: AccountInfo<'info>,
     
     /// CHECK: Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
+    
+    // remaining_accounts: price accounts of the asset's extra sources, in order; for a
+    // composite, then the other asset's config PDA and its price accounts
 }
 
-pub fn handler(ctx: Context<GetPrice>, params: GetPriceParams) -> Result<PriceResponse> {
-    let state = &ctx.accounts.state;
-    let _clock = &ctx.accounts.clock;
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GetPrice<'info>>, params: GetPriceParams) -> Result<PriceResponse> {
+    let (collateral_data, price) = read_asset_price(
+        &ctx.accounts.asset,
+        &ctx.accounts.pyth_price_account,
+        ctx.remaining_accounts,
+        &ctx.accounts.state.oracle_address,
+        &ctx.accounts.clock,
+    )?;
+    if ctx.accounts.asset.is_writable {
+        emit_cpi!(PriceRecorded::new(params.denom.clone(), &price, ctx.accounts.clock.unix_timestamp));
+    }
     
-    // Find the collateral data for the requested denom
-    let collateral_data = state.collateral_data
-        .iter()
-        .find(|d| d.denom == params.denom)
-        .ok_or(AerospacerOracleError::PriceFeedNotFound)?;
+    price_response(params.denom, &collateral_data, &price)
+}
 
-    // PRODUCTION PYTH INTEGRATION CODE
-    // Use Pyth SDK to load and validate price feed data
-    let price_feed = SolanaPriceAccount::account_info_to_feed(&ctx.accounts.pyth_price_account)
-        .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
-    
-    // Get price with hardcoded staleness validation for mainnet (60 seconds)
-    // let current_time = clock.unix_timestamp;
-    // let price = price_feed.get_price_no_older_than(current_time, 60)
-    //     .ok_or(AerospacerOracleError::PriceTooOld)?;
-    
-    // Get the latest available price data (no staleness validation for devnet testing)
-    let price = price_feed.get_price_unchecked();
+/// Load an asset and read its price from its configured sources (the last good price if
+/// they cannot be used), recording the read when the asset account is writable
+pub fn read_asset_price<'info>(
+    asset_info: &AccountInfo<'info>,
+    primary_price_account: &AccountInfo<'info>,
+    extra_price_accounts: &[AccountInfo<'info>],
+    pyth_program: &Pubkey,
+    clock: &Clock,
+) -> Result<(AssetConfig, AssetPrice)> {
+    // Only this asset's config is read, straight from its PDA
+    let mut collateral_data = registry::load_asset(asset_info)?;
 
-    // Validate price data integrity with lenient confidence for devnet testing
-    require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
-    require!(price.conf >= 100, AerospacerOracleError::PythPriceValidationFailed); // Reduced from 1000 to 100 for devnet
-    
-    let price_exponent = (-price.expo) as u8;
-    let token_decimals = collateral_data.decimal;
-    
-    // CRITICAL FIX: Calculate decimal to produce micro-USD (6 decimals) collateral values
-    // Formula: decimal = token_decimals + price_exponent - 6
-    // This ensures calculate_collateral_value returns values in micro-USD units,
-    // which is required for calculate_collateral_ratio's 10^12 scaling to work correctly.
-    //
-    // Example for SOL:
-    //   token_decimals = 9 (SOL has 9 decimals)
-    //   price_exponent = 8 (Pyth price has exponent -8)
-    //   target_decimal = 9 + 8 - 6 = 11
-    //
-    // This makes: collateral_value = (amount × price) / 10^11
-    // With amount in lamports (10^-9 SOL) and price as Pyth raw value:
-    //   collateral_value = (lamports × price) / 10^11
-    //                    = (SOL × 10^9 × price × 10^-8) / 10^11
-    //                    = (SOL × price) / 10^10
-    //                    = USD / 10^6  (since SOL × price = USD)
-    //                    = micro-USD ✓
-    const TARGET_USD_DECIMALS: u8 = 6; // micro-USD (10^-6 USD)
-    
-    // Validate token has sufficient precision for micro-USD calculation
-    // Reject tokens with total_precision < 6 (extremely rare in practice)
-    let total_precision = token_decimals.saturating_add(price_exponent);
-    require!(
-        total_precision >= TARGET_USD_DECIMALS,
-        AerospacerOracleError::InvalidPriceData
-    );
+    let mut price_accounts = vec![primary_price_account.clone()];
+    price_accounts.extend_from_slice(extra_price_accounts);
+    let price = adapters::read_price_or_last_good(&collateral_data, &price_accounts, pyth_program, clock)?;
+    if asset_info.is_writable {
+        registry::record_price(asset_info, &mut collateral_data, &price)?;
+    }
     
-    let adjusted_decimal = total_precision - TARGET_USD_DECIMALS;
+    Ok((collateral_data, price))
+}
+
+/// Build the response for a price of `collateral_data`
+pub fn price_response(denom: String, collateral_data: &AssetConfig, price: &AssetPrice) -> Result<PriceResponse> {
+    let response = PriceResponse::new(denom, collateral_data, price)?;
 
     msg!("Price query successful");
-    msg!("Denom: {}", params.denom);
-    msg!("Token decimal: {}", token_decimals);
-    msg!("Price exponent: {}", price_exponent);
-    msg!("Adjusted decimal (for micro-USD): {}", adjusted_decimal);
-    msg!("Publish Time: {}", price.publish_time);
-    msg!("Price: {} ± {} x 10^{}", price.price, price.conf, price.expo);
-    msg!("Real Pyth data extracted successfully using official SDK");
+    debug_msg!("Denom: {}", response.denom);
+    debug_msg!("Token decimal: {}", response.decimal);
+    debug_msg!("Publish Time: {}", price.publish_time);
+    debug_msg!("Price: {} ± {} x 10^{}", price.price, price.conf, price.expo);
+    debug_msg!("EMA price: {} ± {} x 10^{}", price.ema_price, price.ema_conf, price.expo);
+    debug_msg!("Normalized price: {} ± {} (18 decimals)", response.normalized_price, response.normalized_confidence);
+    debug_msg!("Sources used: {:#05b}", price.sources_used);
+    debug_msg!("Feed status: {:?}", price.status);
     
-    Ok(PriceResponse {
-        denom: params.denom,
-        price: price.price,
-        decimal: adjusted_decimal, // Adjusted to produce micro-USD collateral values
-        timestamp: price.publish_time,
-        confidence: price.conf,
-        exponent: price.expo,
-    })
-}
\ No newline at end of file
+    Ok(response)
+}
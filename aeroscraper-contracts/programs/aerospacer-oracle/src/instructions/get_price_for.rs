@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::adapters::AssetPrice;
+use super::get_price::{price_response, read_asset_price};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetPriceForParams {
+    pub denom: String,
+    
+    /// What the price is for; the asset's price policy picks spot, EMA or TWAP for it
+    pub purpose: PricePurpose,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: GetPriceForParams)]
+pub struct GetPriceFor<'info> {
+    /// Oracle state, for the configured Pyth program that must own push price accounts
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Asset config PDA for the denom, read with registry::load_asset. When passed
+    /// writable, the spot read is recorded as the asset's last good price and feed status,
+    /// and PriceRecorded is emitted
+    #[account(
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Price account for the asset's primary source, checked by its adapter
+    pub pyth_price_account: AccountInfo<'info>,
+    
+    /// CHECK: Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+    
+    /// The asset's price history, required when the purpose uses a TWAP
+    #[account(
+        seeds = [b"history", params.denom.as_bytes()],
+        bump
+    )]
+    pub price_history: Option<AccountLoader<'info, PriceHistory>>,
+    
+    // remaining_accounts: price accounts of the asset's extra sources, in order; for a
+    // composite, then the other asset's config PDA and its price accounts
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GetPriceFor<'info>>, params: GetPriceForParams) -> Result<PriceResponse> {
+    let clock = &ctx.accounts.clock;
+    
+    // The spot read runs in every mode, so the feed status and last good price stay current
+    let (collateral_data, spot) = read_asset_price(
+        &ctx.accounts.asset,
+        &ctx.accounts.pyth_price_account,
+        ctx.remaining_accounts,
+        &ctx.accounts.state.oracle_address,
+        clock,
+    )?;
+    if ctx.accounts.asset.is_writable {
+        emit_cpi!(PriceRecorded::new(params.denom.clone(), &spot, clock.unix_timestamp));
+    }
+    
+    let policy = collateral_data.price_policy()?;
+    let mode = policy.mode(params.purpose);
+    let price = match mode {
+        PriceMode::Spot => spot,
+        PriceMode::Ema => {
+            require!(spot.ema_price > 0, AerospacerOracleError::InvalidPriceData);
+            AssetPrice {
+                price: spot.ema_price,
+                conf: spot.ema_conf,
+                ..spot
+            }
+        }
+        PriceMode::Twap => {
+            let history = ctx.accounts.price_history
+                .as_ref()
+                .ok_or(AerospacerOracleError::PriceHistoryRequired)?
+                .load()?;
+            let latest = history.latest().ok_or(AerospacerOracleError::InsufficientPriceHistory)?;
+            if !cfg!(feature = "devnet") {
+                require!(
+                    clock.unix_timestamp.saturating_sub(latest.publish_time) <= collateral_data.max_staleness_secs as i64,
+                    AerospacerOracleError::PriceTooOld
+                );
+            }
+            
+            let (price, conf, expo, observations) = history.twap(clock.unix_timestamp, policy.twap_window_secs)?;
+            require!(price > 0, AerospacerOracleError::InvalidPriceData);
+            debug_msg!("TWAP over {}s from {} observations", policy.twap_window_secs, observations);
+            AssetPrice {
+                price,
+                conf,
+                expo,
+                publish_time: latest.publish_time,
+                // Keep the EMA in the same exponent as the price it is reported with
+                ema_price: rescale(spot.ema_price as i128, spot.expo, expo)?,
+                ema_conf: rescale(spot.ema_conf as i128, spot.expo, expo)?,
+                ..spot
+            }
+        }
+    };
+    
+    msg!("Price for {:?}: {:?}", params.purpose, mode);
+    price_response(params.denom, &collateral_data, &price)
+}
+
+/// Bring `value` from exponent `from` to exponent `to`
+fn rescale<T: TryFrom<i128>>(value: i128, from: i32, to: i32) -> Result<T> {
+    let factor = 10i128
+        .checked_pow(from.abs_diff(to))
+        .ok_or(AerospacerOracleError::Overflow)?;
+    let value = if from >= to {
+        value.checked_mul(factor).ok_or(AerospacerOracleError::Overflow)?
+    } else {
+        value / factor
+    };
+    T::try_from(value).map_err(|_| AerospacerOracleError::Overflow.into())
+}
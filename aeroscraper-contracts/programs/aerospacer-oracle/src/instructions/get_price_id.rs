@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::*;
-use crate::error::AerospacerOracleError;
+use crate::registry;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetPriceIdParams {
@@ -11,25 +10,20 @@ pub struct GetPriceIdParams {
 #[derive(Accounts)]
 #[instruction(params: GetPriceIdParams)]
 pub struct GetPriceId<'info> {
+    /// CHECK: Asset config PDA for the denom, read with registry::load_asset
     #[account(
-        seeds = [b"state"],
+        seeds = [b"asset", params.denom.as_bytes()],
         bump
     )]
-    pub state: Account<'info, OracleStateAccount>,
+    pub asset: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<GetPriceId>, params: GetPriceIdParams) -> Result<String> {
-    let state = &ctx.accounts.state;
-    
-    // Find the collateral data for the requested denom
-    let collateral_data = state.collateral_data
-        .iter()
-        .find(|d| d.denom == params.denom)
-        .ok_or(AerospacerOracleError::PriceFeedNotFound)?;
+    let price_id = registry::load_asset(&ctx.accounts.asset)?.price_id();
     
     msg!("Price ID query successful");
     msg!("Denom: {}", params.denom);
-    msg!("Price ID: {}", collateral_data.price_id);
+    msg!("Price ID: {}", price_id);
     
-    Ok(collateral_data.price_id.clone())
+    Ok(price_id)
 }
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetTwapParams {
+    pub denom: String,
+    
+    /// Averaging window in seconds, ending now (1..=MAX_TWAP_WINDOW_SECS)
+    pub window_secs: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(params: GetTwapParams)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [b"history", params.denom.as_bytes()],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    /// CHECK: Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<GetTwap>, params: GetTwapParams) -> Result<TwapResponse> {
+    require!(
+        params.window_secs > 0 && params.window_secs <= MAX_TWAP_WINDOW_SECS,
+        AerospacerOracleError::InvalidPricePolicy
+    );
+    
+    let history = ctx.accounts.price_history.load()?;
+    let latest = history.latest().ok_or(AerospacerOracleError::InsufficientPriceHistory)?;
+    let (price, confidence, exponent, observations) =
+        history.twap(ctx.accounts.clock.unix_timestamp, params.window_secs)?;
+    
+    msg!("TWAP query successful");
+    msg!("Denom: {}", params.denom);
+    msg!("TWAP over {}s: {} ± {} x 10^{} ({} observations)", params.window_secs, price, confidence, exponent, observations);
+    
+    Ok(TwapResponse {
+        denom: params.denom,
+        price,
+        confidence,
+        exponent,
+        window_secs: params.window_secs,
+        observations,
+        latest_publish_time: latest.publish_time,
+    })
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Role};
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    /// Role to assign
+    pub role: Role,
+    
+    /// New role holder (replaces any current holder)
+    pub account: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: GrantRoleParams)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+    require!(
+        params.account != Pubkey::default(),
+        AerospacerOracleError::InvalidAddress
+    );
+    
+    let state = &mut ctx.accounts.state;
+    let previous = state.role_holder(params.role);
+    state.set_role_holder(params.role, params.account);
+    state.last_update = ctx.accounts.clock.unix_timestamp;
+    
+    msg!("Role {:?} granted to {} (previous holder: {})", params.role, params.account, previous);
+    
+    emit_cpi!(RoleChanged {
+        role: params.role,
+        previous_holder: previous,
+        new_holder: params.account,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::registry;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitPriceHistoryParams {
+    /// Asset denomination to keep a price history for
+    pub denom: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: InitPriceHistoryParams)]
+pub struct InitPriceHistory<'info> {
+    /// Oracle manager; pays the history account's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OracleManager, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Asset config PDA for the denom; the asset has to be configured
+    #[account(
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Price history PDA for the denom - created by registry::create_price_history
+    #[account(
+        mut,
+        seeds = [b"history", params.denom.as_bytes()],
+        bump
+    )]
+    pub price_history: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitPriceHistory>, params: InitPriceHistoryParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    apply(
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.price_history.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &params,
+    )?;
+    
+    emit_cpi!(PriceHistoryCreated {
+        authority: ctx.accounts.authority.key(),
+        denom: params.denom,
+        capacity: PRICE_HISTORY_LEN as u16,
+    });
+    
+    Ok(())
+}
+
+/// Create the asset's price history, funded by `payer` (shared with timelocked execution)
+pub fn apply<'info>(
+    asset: &AccountInfo<'info>,
+    price_history: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    params: &InitPriceHistoryParams,
+) -> Result<()> {
+    let (address, _) = AssetConfig::get_pda(&params.denom, &crate::ID);
+    require_keys_eq!(asset.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    let config = registry::load_asset(asset)?;
+    
+    registry::create_price_history(price_history, &config, payer, system_program, &params.denom)?;
+    
+    msg!("Price history created for {}", params.denom);
+    msg!("Capacity: {} observations", PRICE_HISTORY_LEN);
+    
+    Ok(())
+}
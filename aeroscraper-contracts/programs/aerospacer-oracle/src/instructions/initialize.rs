@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::OracleStateAccount;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Versioned};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeParams {
@@ -7,6 +8,7 @@ pub struct InitializeParams {
     pub oracle_address: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: InitializeParams)]
 pub struct Initialize<'info> {
@@ -35,8 +37,19 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // Initialize state with admin and oracle address
     state.admin = ctx.accounts.admin.key();
     state.oracle_address = params.oracle_address;
-    state.collateral_data = Vec::new(); // Initialize empty vector
+    state.collateral_data = Vec::new(); // Legacy list, assets live in AssetConfig PDAs
     state.last_update = clock.unix_timestamp;
+    state.guardian = Pubkey::default();
+    state.paused = 0;
+    state.risk_manager = Pubkey::default();
+    state.oracle_manager = Pubkey::default();
+    state.reserved = Pubkey::default();
+    state.pending_admin = Pubkey::default();
+    state.timelock_delay = 0; // Disabled until the owner queues a SetTimelockDelay change
+    state.next_change_id = 0;
+    state.version = OracleStateAccount::VERSION;
+    state.asset_count = 0;
+    state.updaters = Vec::new(); // No keepers: only the admin and oracle manager push prices
     
     msg!("Aerospacer Oracle initialized successfully");
     msg!("Admin: {}", state.admin);
@@ -46,5 +59,11 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     msg!("Pyth min confidence: 1000 (hardcoded)");
     msg!("Ready to configure collateral assets");
     
+    emit_cpi!(OracleInitialized {
+        admin: state.admin,
+        params: OracleParams::from(&**state),
+        timestamp: clock.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
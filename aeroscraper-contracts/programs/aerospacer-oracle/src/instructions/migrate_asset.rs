@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::registry;
+use crate::instructions::SetDataParams;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MigrateAssetParams {
+    /// Denom of the legacy collateral_data entry to move
+    pub denom: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: MigrateAssetParams)]
+pub struct MigrateAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Asset config PDA for the denom - checked by registry::write_asset
+    #[account(
+        mut,
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Drain one entry of the pre-PDA asset list into its AssetConfig account
+pub fn handler(ctx: Context<MigrateAsset>, params: MigrateAssetParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    let migrated = apply(
+        &mut ctx.accounts.state,
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &params,
+    )?;
+    if let Some(current) = migrated {
+        emit_cpi!(PriceConfigured::new(ctx.accounts.admin.key(), &current, None)?);
+    }
+    
+    Ok(())
+}
+
+/// Move the legacy entry for `params.denom` into `asset`, funded by `payer` (shared with
+/// timelocked execution). Returns the new configuration, None if the entry was only dropped
+pub fn apply<'info>(
+    state: &mut OracleStateAccount,
+    asset: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    params: &MigrateAssetParams,
+) -> Result<Option<AssetConfig>> {
+    // asset_count is only meaningful once migrate_state has run
+    require!(
+        state.version() >= OracleStateAccount::VERSION,
+        AerospacerOracleError::StateNotMigrated
+    );
+    let (address, _) = AssetConfig::get_pda(&params.denom, &crate::ID);
+    require_keys_eq!(asset.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    
+    let index = state.collateral_data
+        .iter()
+        .position(|d| d.denom == params.denom)
+        .ok_or(AerospacerOracleError::CollateralDataNotFound)?;
+    let legacy = state.collateral_data.remove(index);
+    
+    // An asset reconfigured through set_data since the upgrade already has a newer PDA
+    if asset.owner == &crate::ID {
+        msg!("Dropped legacy entry for {}: asset already configured", params.denom);
+        return Ok(None);
+    }
+    
+    // The legacy list predates per-asset price limits and only held Pyth push feeds
+    let config = SetDataParams {
+        denom: legacy.denom,
+        decimal: legacy.decimal,
+        price_id: legacy.price_id,
+        pyth_price_account: legacy.pyth_price_account,
+        max_staleness_secs: DEFAULT_MAX_STALENESS_SECS,
+        max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+        source: PriceSource::PythPush,
+        extra_sources: Vec::new(),
+        min_sources: 1,
+        max_deviation_bps: 0,
+        price_policy: PricePolicy::SPOT,
+        price_bounds: PriceBounds::NONE,
+        composite: CompositePrice::NONE,
+        peg_band_bps: 0,
+    };
+    let (current, _) = registry::write_asset(
+        state,
+        asset,
+        payer,
+        system_program,
+        &config,
+        legacy.configured_at,
+    )?;
+    
+    msg!("Migrated asset {} to its own account", config.denom);
+    msg!("Legacy entries left: {}", state.collateral_data.len());
+    msg!("Total assets: {}", state.asset_count);
+    
+    Ok(Some(current))
+}
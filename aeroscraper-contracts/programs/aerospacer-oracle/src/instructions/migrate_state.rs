@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::events::*;
+use crate::state::{OracleStateAccount, Versioned};
+use crate::error::AerospacerOracleError;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Loaded as OracleStateAccount after the resize, since an older layout may be
+    /// too short to deserialize. The discriminator and admin are checked then
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub state: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow the oracle state account to the current layout and upgrade it in place
+pub fn handler(ctx: Context<MigrateState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let new_len = 8 + OracleStateAccount::LEN;
+    let from_v0 = state_info.data_len() == OracleStateAccount::V0_ACCOUNT_LEN;
+    
+    // The admin tops up rent for the extra bytes. If the signer turns out not to be
+    // the admin the whole instruction reverts, resize included
+    if state_info.data_len() < new_len {
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(state_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: state_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        state_info.resize(new_len)?;
+    }
+    
+    // Only v0 accounts have that size. Whatever lies after their last field is stale,
+    // so it is cleared before the appended fields are read from it
+    if from_v0 {
+        OracleStateAccount::clear_v0_slack(&mut state_info.try_borrow_mut_data()?)?;
+    }
+    
+    let mut state = OracleStateAccount::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+    require!(
+        state.admin == ctx.accounts.admin.key(),
+        AerospacerOracleError::Unauthorized
+    );
+    
+    let from_version = state.version;
+    require!(state.upgrade(), AerospacerOracleError::AccountAlreadyMigrated);
+    {
+        let mut data = state_info.try_borrow_mut_data()?;
+        let mut cursor: &mut [u8] = &mut data[..];
+        state.try_serialize(&mut cursor)?;
+        // A shrinking collateral_data leaves stale bytes past the end, so clear them
+        // before later fields are appended there
+        cursor.fill(0);
+    }
+    
+    msg!("Oracle state migrated: v{} -> v{} ({} bytes)", from_version, state.version, state_info.data_len());
+    
+    emit_cpi!(StateMigrated {
+        admin: state.admin,
+        from_version,
+        to_version: state.version,
+        data_len: state_info.data_len() as u64,
+    });
+    
+    Ok(())
+}
@@ -4,12 +4,31 @@ pub mod set_data;
 pub mod set_data_batch;
 pub mod remove_data;
 pub mod get_price;
+pub mod get_price_for;
+pub mod get_twap;
+pub mod init_price_history;
 pub mod get_config;
 pub mod get_all_denoms;
 pub mod get_price_id;
 pub mod get_all_prices;
 pub mod check_denom;
 pub mod update_pyth_price;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod set_paused;
+pub mod set_peg_frozen;
+pub mod set_asset_state;
+pub mod get_asset_state;
+pub mod add_updater;
+pub mod remove_updater;
+pub mod queue_change;
+pub mod execute_change;
+pub mod cancel_change;
+pub mod migrate_state;
+pub mod migrate_asset;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -24,6 +43,12 @@ pub use remove_data::*;
 #[allow(ambiguous_glob_reexports)]
 pub use get_price::*;
 #[allow(ambiguous_glob_reexports)]
+pub use get_price_for::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_twap::*;
+#[allow(ambiguous_glob_reexports)]
+pub use init_price_history::*;
+#[allow(ambiguous_glob_reexports)]
 pub use get_config::*;
 #[allow(ambiguous_glob_reexports)]
 pub use get_all_denoms::*;
@@ -34,4 +59,36 @@ pub use get_all_prices::*;
 #[allow(ambiguous_glob_reexports)]
 pub use check_denom::*;
 #[allow(ambiguous_glob_reexports)]
-pub use update_pyth_price::*;
\ No newline at end of file
+pub use update_pyth_price::*;
+#[allow(ambiguous_glob_reexports)]
+pub use grant_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use accept_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_admin_transfer::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_paused::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_peg_frozen::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_asset_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_asset_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_updater::*;
+#[allow(ambiguous_glob_reexports)]
+pub use remove_updater::*;
+#[allow(ambiguous_glob_reexports)]
+pub use queue_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_asset::*;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::OracleStateAccount;
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAdminParams {
+    /// Proposed new administrator (e.g. a multisig or governance PDA)
+    pub new_admin: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: ProposeAdminParams)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        params.new_admin != Pubkey::default() && params.new_admin != state.admin,
+        AerospacerOracleError::InvalidAddress
+    );
+    
+    // Overwrites any earlier proposal; admin rights only move once the new key accepts
+    state.pending_admin = params.new_admin;
+    state.last_update = ctx.accounts.clock.unix_timestamp;
+    
+    msg!("Admin transfer proposed: {} -> {}", state.admin, params.new_admin);
+    
+    emit_cpi!(AdminTransferProposed {
+        admin: state.admin,
+        pending_admin: params.new_admin,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::instructions::set_peg_frozen;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueueChangeParams {
+    /// Change to apply once the timelock delay has elapsed
+    pub change: TimelockedChange,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: QueueChangeParams)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = QueuedChange::space(&params.change),
+        seeds = [b"timelock", state.next_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+    let proposer = ctx.accounts.proposer.key();
+    let clock = &ctx.accounts.clock;
+    let state = &mut ctx.accounts.state;
+    
+    // Same role requirements as the direct instructions
+    match &params.change {
+        TimelockedChange::UpdateOracleAddress(_)
+        | TimelockedChange::SetData(_)
+        | TimelockedChange::SetDataBatch(_)
+        | TimelockedChange::RemoveData(_)
+        | TimelockedChange::AddUpdater(_)
+        | TimelockedChange::RemoveUpdater(_)
+        | TimelockedChange::InitPriceHistory(_) => {
+            require!(
+                state.has_role(Role::OracleManager, &proposer),
+                AerospacerOracleError::Unauthorized
+            );
+        }
+        TimelockedChange::SetTimelockDelay(delay) => {
+            require!(
+                state.admin == proposer,
+                AerospacerOracleError::Unauthorized
+            );
+            require!(
+                (0..=MAX_TIMELOCK_DELAY).contains(delay),
+                AerospacerOracleError::InvalidTimelockDelay
+            );
+        }
+        TimelockedChange::SetAssetState(_) => {
+            require!(
+                state.has_role(Role::RiskManager, &proposer),
+                AerospacerOracleError::Unauthorized
+            );
+        }
+        TimelockedChange::SetPegFrozen(change) => {
+            set_peg_frozen::authorize(state, &proposer, change)?;
+        }
+        TimelockedChange::MigrateAsset(_) => {
+            require!(
+                state.admin == proposer,
+                AerospacerOracleError::Unauthorized
+            );
+        }
+    }
+    
+    let queued_change = &mut ctx.accounts.queued_change;
+    queued_change.id = state.next_change_id;
+    queued_change.proposer = proposer;
+    queued_change.queued_at = clock.unix_timestamp;
+    queued_change.eta = clock.unix_timestamp
+        .checked_add(state.timelock_delay)
+        .ok_or(AerospacerOracleError::Overflow)?;
+    queued_change.set_change(&params.change)?;
+    queued_change.version = QueuedChange::VERSION;
+    
+    state.next_change_id = state.next_change_id
+        .checked_add(1)
+        .ok_or(AerospacerOracleError::Overflow)?;
+    
+    msg!("Change queued successfully");
+    msg!("Change ID: {}", queued_change.id);
+    msg!("Proposer: {}", proposer);
+    msg!("Executable at: {}", queued_change.eta);
+    
+    emit_cpi!(ChangeQueued {
+        id: queued_change.id,
+        proposer,
+        queued_at: queued_change.queued_at,
+        eta: queued_change.eta,
+    });
+    
+    Ok(())
+}
@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
+use crate::registry;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct RemoveDataParams {
     /// Asset denomination to remove (e.g., "inj", "atom")
     pub collateral_denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: RemoveDataParams)]
 pub struct RemoveData<'info> {
@@ -18,39 +21,87 @@ pub struct RemoveData<'info> {
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+        constraint = state.has_role(Role::OracleManager, &admin.key()) @ AerospacerOracleError::Unauthorized
     )]
     pub state: Account<'info, OracleStateAccount>,
     
+    /// CHECK: Asset config PDA to close - checked by registry::remove_asset
+    #[account(
+        mut,
+        seeds = [b"asset", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Asset at the last registry index, moved into the freed slot (the same
+    /// account as `asset` if that is the last one) - checked by registry::remove_asset
+    #[account(mut)]
+    pub last_asset: UncheckedAccount<'info>,
+    
     /// CHECK: Clock sysvar for timestamp
     pub clock: Sysvar<'info, Clock>,
 }
 
 pub fn handler(ctx: Context<RemoveData>, params: RemoveDataParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
-    let clock = &ctx.accounts.clock;
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_ASSET_CONFIG),
+        AerospacerOracleError::OperationPaused
+    );
+    
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
     
+    let removed = apply(
+        &mut ctx.accounts.state,
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.last_asset.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &params,
+        &ctx.accounts.clock,
+    )?;
+    
+    emit_cpi!(PriceConfigRemoved {
+        authority: ctx.accounts.admin.key(),
+        denom: removed.denom,
+        price_id: removed.price_id,
+        pyth_price_account: removed.pyth_price_account,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Remove one asset configuration by closing its PDA, refunding the rent to
+/// `rent_receiver` (shared with timelocked execution). Returns the removed configuration
+pub fn apply<'info>(
+    state: &mut OracleStateAccount,
+    asset: &AccountInfo<'info>,
+    last_asset: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    params: &RemoveDataParams,
+    clock: &Clock,
+) -> Result<CollateralData> {
     // Validate input parameters
     require!(!params.collateral_denom.is_empty(), AerospacerOracleError::InvalidCollateralData);
     
-    // Find and remove the collateral data
-    if let Some(index) = state.collateral_data.iter().position(|d| d.denom == params.collateral_denom) {
-        let removed_data = state.collateral_data.remove(index);
-        msg!("Removed collateral data for: {}", params.collateral_denom);
-        msg!("Removed price ID: {}", removed_data.price_id);
-        msg!("Removed decimal: {}", removed_data.decimal);
-        msg!("Removed Pyth price account: {}", removed_data.pyth_price_account);
-    } else {
-        return Err(AerospacerOracleError::CollateralDataNotFound.into());
-    }
+    // Close the asset's PDA and compact the registry
+    let removed_data = registry::remove_asset(state, asset, last_asset, rent_receiver, &params.collateral_denom)?;
+    msg!("Removed collateral data for: {}", params.collateral_denom);
+    msg!("Removed price ID: {}", removed_data.price_id);
+    msg!("Removed decimal: {}", removed_data.decimal);
+    msg!("Removed Pyth price account: {}", removed_data.pyth_price_account);
     
     // Update last update timestamp
     state.last_update = clock.unix_timestamp;
     
     msg!("Remove data successful");
     msg!("Removed denom: {}", params.collateral_denom);
-    msg!("Remaining assets: {}", state.collateral_data.len());
+    msg!("Remaining assets: {}", state.asset_count);
     msg!("Updated at: {}", clock.unix_timestamp);
     
-    Ok(())
+    Ok(removed_data)
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Role};
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoveUpdaterParams {
+    /// Price keeper to remove
+    pub updater: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: RemoveUpdaterParams)]
+pub struct RemoveUpdater<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OracleManager, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RemoveUpdater>, params: RemoveUpdaterParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    let state = &mut ctx.accounts.state;
+    apply(state, &params, &ctx.accounts.clock)?;
+    
+    emit_cpi!(UpdaterChanged {
+        authority: ctx.accounts.authority.key(),
+        updater: params.updater,
+        added: false,
+        updater_count: state.updaters.len() as u8,
+    });
+    
+    Ok(())
+}
+
+/// Remove a price keeper (shared with timelocked execution)
+pub fn apply(state: &mut OracleStateAccount, params: &RemoveUpdaterParams, clock: &Clock) -> Result<()> {
+    let index = state.updaters
+        .iter()
+        .position(|updater| *updater == params.updater)
+        .ok_or(AerospacerOracleError::UpdaterNotFound)?;
+    state.updaters.remove(index);
+    state.last_update = clock.unix_timestamp;
+    
+    // Removing the last keeper leaves price updates to the admin and oracle manager
+    msg!("Price keeper removed: {} ({} registered)", params.updater, state.updaters.len());
+    
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Role};
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    /// Role to clear
+    pub role: Role,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: RevokeRoleParams)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let previous = state.role_holder(params.role);
+    state.set_role_holder(params.role, Pubkey::default());
+    state.last_update = ctx.accounts.clock.unix_timestamp;
+    
+    msg!("Role {:?} revoked from {}", params.role, previous);
+    
+    emit_cpi!(RoleChanged {
+        role: params.role,
+        previous_holder: previous,
+        new_holder: Pubkey::default(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{AssetConfig, AssetState, OracleStateAccount, Role};
+use crate::error::AerospacerOracleError;
+use crate::registry;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetAssetStateParams {
+    /// Asset denomination (e.g., "inj", "atom")
+    pub denom: String,
+    
+    /// Lifecycle state to move the asset to
+    pub state: AssetState,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SetAssetStateParams)]
+pub struct SetAssetState<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::RiskManager, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Asset config PDA - checked by registry::load_asset
+    #[account(
+        mut,
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SetAssetState>, params: SetAssetStateParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    let previous_state = apply(
+        &mut ctx.accounts.state,
+        &ctx.accounts.asset.to_account_info(),
+        &params,
+        &ctx.accounts.clock,
+    )?;
+    
+    emit_cpi!(AssetStateChanged {
+        authority: ctx.accounts.authority.key(),
+        denom: params.denom,
+        previous_state,
+        state: params.state,
+    });
+    
+    Ok(())
+}
+
+/// Move the asset in `info` to its new lifecycle state (shared with timelocked execution).
+/// Returns the previous state
+pub fn apply(state: &mut OracleStateAccount, info: &AccountInfo, params: &SetAssetStateParams, clock: &Clock) -> Result<AssetState> {
+    let (address, _) = AssetConfig::get_pda(&params.denom, &crate::ID);
+    require_keys_eq!(info.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    let mut asset = registry::load_asset(info)?;
+    let previous_state = asset.asset_state()?;
+    
+    registry::set_asset_state(info, &mut asset, params.state)?;
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Asset state for {}: {:?} -> {:?}", params.denom, previous_state, params.state);
+    
+    Ok(previous_state)
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
+use crate::registry;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SetDataParams {
     /// Asset denomination (e.g., "inj", "atom")
     pub denom: String,
@@ -10,13 +12,155 @@ pub struct SetDataParams {
     /// Decimal precision for price calculations (6, 18, etc.)
     pub decimal: u8,
     
-    /// Pyth Network price feed identifier (hex format)
+    /// Feed identifier (hex format): the Pyth price feed id or the Switchboard feed hash.
+    /// May be empty for a fixed price
     pub price_id: String,
     
-    /// Pyth price account address for this asset
+    /// Price account for this asset: the Pyth push price account or the Switchboard feed.
+    /// Pyth pull updates are matched by price_id instead
     pub pyth_price_account: Pubkey,
+    
+    /// Oldest price accepted for this asset, in seconds since its publish time
+    pub max_staleness_secs: u32,
+    
+    /// Widest confidence interval accepted, as basis points of the price (1..=10000)
+    pub max_confidence_bps: u16,
+    
+    /// Where the asset's price is read from
+    pub source: PriceSource,
+    
+    /// Further feeds to take the median with, at most MAX_PRICE_SOURCES - 1
+    pub extra_sources: Vec<ExtraSourceParams>,
+    
+    /// Fresh sources required to report a price (1..=number of sources)
+    pub min_sources: u8,
+    
+    /// Widest spread allowed between fresh sources, as basis points of their median.
+    /// Required (1..=10000) when there are extra sources
+    pub max_deviation_bps: u16,
+    
+    /// Price mode (spot, EMA or TWAP) used for borrowing, withdrawal and liquidation
+    pub price_policy: PricePolicy,
+    
+    /// Absolute price bounds and the change limit versus the last good price
+    pub price_bounds: PriceBounds,
+    
+    /// Another asset whose price this one's own feed is multiplied or divided by
+    pub composite: CompositePrice,
+    
+    /// Band around 1 USD, in basis points, within which the asset reports exactly 1 USD
+    /// (0 = not pegged, at most MAX_PEG_BAND_BPS)
+    pub peg_band_bps: u16,
+}
+
+/// An additional price feed for an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExtraSourceParams {
+    /// Feed kind; a fixed price cannot be an extra source
+    pub source: PriceSource,
+    
+    /// Pyth feed id or Switchboard feed hash (64 hex characters)
+    pub price_id: String,
+    
+    /// Pyth push price account or Switchboard feed address
+    pub price_account: Pubkey,
+}
+
+impl ExtraSourceParams {
+    /// Feed id as bytes; only valid after validate_price_id
+    pub fn feed_id(&self) -> [u8; 32] {
+        let mut id = [0u8; 32];
+        let _ = hex::decode_to_slice(&self.price_id, &mut id);
+        id
+    }
+}
+
+/// Check a price_id is a 32-byte feed id in hex
+fn validate_price_id(price_id: &str) -> Result<()> {
+    require!(!price_id.is_empty(), AerospacerOracleError::InvalidCollateralData);
+    if price_id.len() != 64 || !price_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AerospacerOracleError::InvalidPriceId.into());
+    }
+    Ok(())
+}
+
+/// Push, Switchboard and stake pool sources are pinned to their price account, so it must be given
+fn validate_price_account(source: &PriceSource, price_account: &Pubkey) -> Result<()> {
+    if matches!(
+        source,
+        PriceSource::PythPush | PriceSource::SwitchboardOnDemand | PriceSource::SplStakePool { .. }
+    ) {
+        require!(*price_account != Pubkey::default(), AerospacerOracleError::InvalidPriceSource);
+    }
+    Ok(())
 }
 
+impl SetDataParams {
+    /// Check the configuration before it is stored
+    pub fn validate(&self) -> Result<()> {
+        registry::validate_denom(&self.denom)?;
+        require!(self.decimal > 0, AerospacerOracleError::InvalidCollateralData);
+        require!(self.max_staleness_secs > 0, AerospacerOracleError::InvalidCollateralData);
+        require!(
+            self.max_confidence_bps > 0 && self.max_confidence_bps <= BPS_DENOMINATOR,
+            AerospacerOracleError::InvalidCollateralData
+        );
+        
+        // Extra sources are feeds to aggregate with the primary one
+        let source_count = 1 + self.extra_sources.len();
+        require!(source_count <= MAX_PRICE_SOURCES, AerospacerOracleError::InvalidPriceSource);
+        require!(
+            self.min_sources >= 1 && self.min_sources as usize <= source_count,
+            AerospacerOracleError::InvalidPriceSource
+        );
+        if source_count > 1 {
+            require!(
+                self.max_deviation_bps > 0 && self.max_deviation_bps <= BPS_DENOMINATOR,
+                AerospacerOracleError::InvalidPriceSource
+            );
+        }
+        for extra in &self.extra_sources {
+            require!(
+                !matches!(extra.source, PriceSource::Fixed { .. } | PriceSource::SplStakePool { .. }),
+                AerospacerOracleError::InvalidPriceSource
+            );
+            validate_price_id(&extra.price_id)?;
+            validate_price_account(&extra.source, &extra.price_account)?;
+        }
+        validate_price_account(&self.source, &self.pyth_price_account)?;
+        
+        self.price_policy.validate()?;
+        self.price_bounds.validate()?;
+        self.composite.validate(&self.denom)?;
+        require!(self.peg_band_bps <= MAX_PEG_BAND_BPS, AerospacerOracleError::InvalidPegConfig);
+        
+        if let PriceSource::Fixed { price, exponent } = self.source {
+            require!(price > 0, AerospacerOracleError::InvalidPriceSource);
+            require!((-18..=18).contains(&exponent), AerospacerOracleError::InvalidPriceSource);
+            require!(self.extra_sources.is_empty(), AerospacerOracleError::InvalidPriceSource);
+            if self.price_id.is_empty() {
+                return Ok(());
+            }
+        }
+        
+        // A stake pool rate is in SOL, so it is multiplied by the SOL asset to get a USD price
+        if let PriceSource::SplStakePool { haircut_bps } = self.source {
+            require!(haircut_bps < BPS_DENOMINATOR, AerospacerOracleError::InvalidPriceSource);
+            require!(self.extra_sources.is_empty(), AerospacerOracleError::InvalidPriceSource);
+            require!(
+                self.composite.op == CompositeOp::Multiply,
+                AerospacerOracleError::InvalidCompositePrice
+            );
+            if self.price_id.is_empty() {
+                return Ok(());
+            }
+        }
+        
+        validate_price_id(&self.price_id)
+    }
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: SetDataParams)]
 pub struct SetData<'info> {
@@ -27,43 +171,68 @@ pub struct SetData<'info> {
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+        constraint = state.has_role(Role::OracleManager, &admin.key()) @ AerospacerOracleError::Unauthorized
     )]
     pub state: Account<'info, OracleStateAccount>,
     
+    /// CHECK: Asset config PDA, created on first configuration - checked by registry::write_asset
+    #[account(
+        mut,
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
     /// CHECK: Clock sysvar for timestamp
     pub clock: Sysvar<'info, Clock>,
 }
 
 pub fn handler(ctx: Context<SetData>, params: SetDataParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
-    let clock = &ctx.accounts.clock;
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_ASSET_CONFIG),
+        AerospacerOracleError::OperationPaused
+    );
     
-    // Validate input parameters
-    require!(!params.denom.is_empty(), AerospacerOracleError::InvalidCollateralData);
-    require!(params.decimal > 0, AerospacerOracleError::InvalidCollateralData);
-    require!(!params.price_id.is_empty(), AerospacerOracleError::InvalidCollateralData);
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
     
-    // Validate price_id format (should be a valid hex string)
-    if params.price_id.len() != 64 || !params.price_id.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(AerospacerOracleError::InvalidPriceId.into());
-    }
+    let (current, previous) = apply(
+        &mut ctx.accounts.state,
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &params,
+        &ctx.accounts.clock,
+    )?;
+    emit_cpi!(PriceConfigured::new(ctx.accounts.admin.key(), &current, previous.as_ref())?);
     
-    // Create new collateral data with timestamp
-    let collateral_data = CollateralData {
-        denom: params.denom.clone(),
-        decimal: params.decimal,
-        price_id: params.price_id.clone(),
-        configured_at: clock.unix_timestamp,
-        pyth_price_account: params.pyth_price_account,
-    };
-    
-    // Check if denom already exists and update, otherwise add new
-    if let Some(index) = state.collateral_data.iter().position(|d| d.denom == params.denom) {
-        state.collateral_data[index] = collateral_data;
+    Ok(())
+}
+
+/// Validate and store one asset configuration in its PDA, which `payer` funds if the
+/// asset is new (shared with timelocked execution). Returns the new and previous configuration
+pub fn apply<'info>(
+    state: &mut OracleStateAccount,
+    asset: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    params: &SetDataParams,
+    clock: &Clock,
+) -> Result<(AssetConfig, Option<AssetConfig>)> {
+    // Validate input parameters
+    params.validate()?;
+    
+    // Update the asset's PDA, creating it for a new denom
+    let (current, previous) = registry::write_asset(state, asset, payer, system_program, params, clock.unix_timestamp)?;
+    if previous.is_some() {
         msg!("Updated collateral data for: {}", params.denom);
     } else {
-        state.collateral_data.push(collateral_data);
         msg!("Added new collateral data for: {}", params.denom);
     }
     
@@ -75,8 +244,12 @@ pub fn handler(ctx: Context<SetData>, params: SetDataParams) -> Result<()> {
     msg!("Decimal: {}", params.decimal);
     msg!("Price ID: {}", params.price_id);
     msg!("Pyth Price Account: {}", params.pyth_price_account);
+    msg!("Max staleness: {} seconds", params.max_staleness_secs);
+    msg!("Max confidence: {} bps", params.max_confidence_bps);
+    msg!("Price source: {:?}", params.source);
+    msg!("Extra sources: {} (min fresh {}, max deviation {} bps)", params.extra_sources.len(), params.min_sources, params.max_deviation_bps);
     msg!("Configured at: {}", clock.unix_timestamp);
-    msg!("Total assets: {}", state.collateral_data.len());
+    msg!("Total assets: {}", state.asset_count);
     
-    Ok(())
+    Ok((current, previous))
 }
\ No newline at end of file
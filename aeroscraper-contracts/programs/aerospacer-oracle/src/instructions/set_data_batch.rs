@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
+use crate::registry;
+use crate::instructions::SetDataParams;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SetDataBatchParams {
-    /// Vector of collateral asset data to configure
-    pub data: Vec<CollateralData>,
+    /// Asset configurations, each as set_data would take it
+    pub data: Vec<SetDataParams>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: SetDataBatchParams)]
 pub struct SetDataBatch<'info> {
@@ -18,53 +22,86 @@ pub struct SetDataBatch<'info> {
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+        constraint = state.has_role(Role::OracleManager, &admin.key()) @ AerospacerOracleError::Unauthorized
     )]
     pub state: Account<'info, OracleStateAccount>,
     
+    pub system_program: Program<'info, System>,
+    
     /// CHECK: Clock sysvar for timestamp
     pub clock: Sysvar<'info, Clock>,
+    
+    // remaining_accounts: the asset config PDA of each entry, in the order of params.data
 }
 
-pub fn handler(ctx: Context<SetDataBatch>, params: SetDataBatchParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
-    let clock = &ctx.accounts.clock;
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SetDataBatch<'info>>, params: SetDataBatchParams) -> Result<()> {
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_ASSET_CONFIG),
+        AerospacerOracleError::OperationPaused
+    );
+    
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
+    
+    let configured = apply(
+        &mut ctx.accounts.state,
+        ctx.remaining_accounts,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &params,
+        &ctx.accounts.clock,
+    )?;
     
+    for (current, previous) in configured {
+        emit_cpi!(PriceConfigured::new(ctx.accounts.admin.key(), &current, previous.as_ref())?);
+    }
+    
+    Ok(())
+}
+
+/// Validate and store a batch of asset configurations, one asset PDA per entry in
+/// `assets` (shared with timelocked execution). Returns the new and previous configurations
+pub fn apply<'info>(
+    state: &mut OracleStateAccount,
+    assets: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    params: &SetDataBatchParams,
+    clock: &Clock,
+) -> Result<Vec<(AssetConfig, Option<AssetConfig>)>> {
     let data_len = params.data.len();
     
     // Validate batch data
     require!(data_len > 0, AerospacerOracleError::InvalidBatchData);
     require!(data_len <= 100, AerospacerOracleError::InvalidBatchData); // Limit batch size
+    require!(assets.len() == data_len, AerospacerOracleError::InvalidBatchData);
+    
+    let mut configured = Vec::with_capacity(data_len);
     
     // Process each collateral data entry
-    for collateral_data in params.data {
+    for (collateral_data, asset) in params.data.iter().zip(assets) {
         // Validate individual data entries
-        require!(!collateral_data.denom.is_empty(), AerospacerOracleError::InvalidCollateralData);
-        require!(collateral_data.decimal > 0, AerospacerOracleError::InvalidCollateralData);
-        require!(!collateral_data.price_id.is_empty(), AerospacerOracleError::InvalidCollateralData);
+        collateral_data.validate()?;
         
-        // Validate price_id format (should be a valid hex string)
-        if collateral_data.price_id.len() != 64 || !collateral_data.price_id.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Err(AerospacerOracleError::InvalidPriceId.into());
-        }
-        
-        // Create new collateral data with timestamp
-        let new_collateral_data = CollateralData {
-            denom: collateral_data.denom.clone(),
-            decimal: collateral_data.decimal,
-            price_id: collateral_data.price_id.clone(),
-            configured_at: clock.unix_timestamp,
-            pyth_price_account: collateral_data.pyth_price_account,
-        };
-        
-        // Check if denom already exists and update, otherwise add new
-        if let Some(index) = state.collateral_data.iter().position(|d| d.denom == collateral_data.denom) {
-            state.collateral_data[index] = new_collateral_data;
+        // Update the asset's PDA, creating it for a new denom
+        let (current, previous) = registry::write_asset(
+            state,
+            asset,
+            payer,
+            system_program,
+            collateral_data,
+            clock.unix_timestamp,
+        )?;
+        if previous.is_some() {
             msg!("Updated collateral data for: {}", collateral_data.denom);
         } else {
-            state.collateral_data.push(new_collateral_data);
             msg!("Added new collateral data for: {}", collateral_data.denom);
         }
+        configured.push((current, previous));
     }
     
     // Update last update timestamp
@@ -72,8 +109,8 @@ pub fn handler(ctx: Context<SetDataBatch>, params: SetDataBatchParams) -> Result
     
     msg!("Set data batch successful");
     msg!("Processed {} collateral data entries", data_len);
-    msg!("Total assets: {}", state.collateral_data.len());
+    msg!("Total assets: {}", state.asset_count);
     msg!("Updated at: {}", clock.unix_timestamp);
     
-    Ok(())
+    Ok(configured)
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{OracleStateAccount, PAUSE_ALL, Role};
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPausedParams {
+    /// Full replacement bitmask of PAUSE_* flags
+    pub paused: u8,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SetPausedParams)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Guardian, &authority.key()) @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let clock = &ctx.accounts.clock;
+    
+    require!(
+        params.paused & !PAUSE_ALL == 0,
+        AerospacerOracleError::InvalidPauseFlags
+    );
+    
+    // The guardian can only add pause flags; clearing them requires the owner
+    if ctx.accounts.authority.key() != state.admin {
+        require!(
+            params.paused & state.paused == state.paused,
+            AerospacerOracleError::Unauthorized
+        );
+    }
+    
+    msg!("Pause flags updated: {:#010b} -> {:#010b}", state.paused, params.paused);
+    
+    emit_cpi!(PauseChanged {
+        authority: ctx.accounts.authority.key(),
+        paused_before: state.paused,
+        paused_after: params.paused,
+    });
+    
+    state.paused = params.paused;
+    state.last_update = clock.unix_timestamp;
+    
+    Ok(())
+}
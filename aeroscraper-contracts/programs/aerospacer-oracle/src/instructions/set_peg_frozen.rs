@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{AssetConfig, OracleStateAccount, Role};
+use crate::error::AerospacerOracleError;
+use crate::registry;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetPegFrozenParams {
+    /// Pegged asset denomination (e.g., "usdc")
+    pub denom: String,
+    
+    /// Whether the asset's price is frozen
+    pub frozen: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SetPegFrozenParams)]
+pub struct SetPegFrozen<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Asset config PDA - checked by registry::load_asset
+    #[account(
+        mut,
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SetPegFrozen>, params: SetPegFrozenParams) -> Result<()> {
+    authorize(&ctx.accounts.state, &ctx.accounts.authority.key(), &params)?;
+    
+    // Freezing is the guardian's emergency brake and takes effect at once; once a delay is
+    // configured, unfreezing has to go through queue_change
+    if !params.frozen {
+        require!(
+            ctx.accounts.state.timelock_delay == 0,
+            AerospacerOracleError::TimelockActive
+        );
+    }
+    
+    apply(
+        &mut ctx.accounts.state,
+        &ctx.accounts.asset.to_account_info(),
+        &params,
+        &ctx.accounts.clock,
+    )?;
+    
+    emit_cpi!(PegFreezeChanged {
+        authority: ctx.accounts.authority.key(),
+        denom: params.denom,
+        frozen: params.frozen,
+    });
+    
+    Ok(())
+}
+
+/// The guardian can only freeze; unfreezing requires the owner
+pub fn authorize(state: &OracleStateAccount, signer: &Pubkey, params: &SetPegFrozenParams) -> Result<()> {
+    require!(
+        state.has_role(Role::Guardian, signer),
+        AerospacerOracleError::Unauthorized
+    );
+    if *signer != state.admin {
+        require!(params.frozen, AerospacerOracleError::Unauthorized);
+    }
+    
+    Ok(())
+}
+
+/// Freeze or unfreeze the pegged asset in `info` (shared with timelocked execution)
+pub fn apply(state: &mut OracleStateAccount, info: &AccountInfo, params: &SetPegFrozenParams, clock: &Clock) -> Result<()> {
+    let (address, _) = AssetConfig::get_pda(&params.denom, &crate::ID);
+    require_keys_eq!(info.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    let mut asset = registry::load_asset(info)?;
+    
+    // Only pegged assets can be frozen; unfreezing stays possible if the band was removed
+    require!(
+        asset.peg_band_bps > 0 || !params.frozen,
+        AerospacerOracleError::InvalidPegConfig
+    );
+    
+    registry::set_peg_frozen(info, &mut asset, params.frozen)?;
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Peg {} for: {}", if params.frozen { "frozen" } else { "unfrozen" }, params.denom);
+    
+    Ok(())
+}
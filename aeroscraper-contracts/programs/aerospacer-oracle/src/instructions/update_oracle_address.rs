@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::OracleStateAccount;
+use crate::events::*;
+use crate::state::{OracleStateAccount, Role};
 use crate::error::AerospacerOracleError;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UpdateOracleAddressParams {
     pub new_oracle_address: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: UpdateOracleAddressParams)]
 pub struct UpdateOracleAddress<'info> {
@@ -17,7 +19,7 @@ pub struct UpdateOracleAddress<'info> {
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+        constraint = state.has_role(Role::OracleManager, &admin.key()) @ AerospacerOracleError::Unauthorized
     )]
     pub state: Account<'info, OracleStateAccount>,
     
@@ -26,9 +28,28 @@ pub struct UpdateOracleAddress<'info> {
 }
 
 pub fn handler(ctx: Context<UpdateOracleAddress>, params: UpdateOracleAddressParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
-    let clock = &ctx.accounts.clock;
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerOracleError::TimelockActive
+    );
     
+    msg!("Updated by: {}", ctx.accounts.admin.key());
+    let before = OracleParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state, &params, &ctx.accounts.clock)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.admin.key(),
+        before,
+        after: OracleParams::from(&*ctx.accounts.state),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Replace the external oracle provider address (shared with timelocked execution)
+pub fn apply(state: &mut OracleStateAccount, params: &UpdateOracleAddressParams, clock: &Clock) -> Result<()> {
     // Update the oracle address
     state.oracle_address = params.new_oracle_address;
     
@@ -37,7 +58,6 @@ pub fn handler(ctx: Context<UpdateOracleAddress>, params: UpdateOracleAddressPar
     
     msg!("Oracle address updated successfully");
     msg!("New oracle address: {}", params.new_oracle_address);
-    msg!("Updated by admin: {}", ctx.accounts.admin.key());
     msg!("Updated at: {}", clock.unix_timestamp);
     
     Ok(())
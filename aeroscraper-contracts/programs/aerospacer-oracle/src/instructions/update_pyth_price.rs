@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
-use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::adapters;
+use crate::registry;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UpdatePythPriceParams {
@@ -9,53 +11,71 @@ pub struct UpdatePythPriceParams {
     pub denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: UpdatePythPriceParams)]
 pub struct UpdatePythPrice<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    /// Registered price keeper (see OracleStateAccount::updaters). The price itself is
+    /// read and validated from the asset's own sources
+    pub updater: Signer<'info>,
     
     #[account(
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+        constraint = state.can_update_prices(&updater.key()) @ AerospacerOracleError::Unauthorized
     )]
     pub state: Account<'info, OracleStateAccount>,
     
-    /// CHECK: Pyth price account to update from
+    /// CHECK: Asset config PDA for the denom, read with registry::load_asset; the price
+    /// read is recorded as its last good price
+    #[account(
+        mut,
+        seeds = [b"asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub asset: UncheckedAccount<'info>,
+    
+    /// CHECK: Price account for the asset's primary source, checked by its adapter
     pub pyth_price_account: AccountInfo<'info>,
     
     /// CHECK: Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
+    
+    /// The asset's price history; the price is appended when it is passed
+    #[account(
+        mut,
+        seeds = [b"history", params.denom.as_bytes()],
+        bump
+    )]
+    pub price_history: Option<AccountLoader<'info, PriceHistory>>,
+    
+    // remaining_accounts: price accounts of the asset's extra sources, in order; for a
+    // composite, then the other asset's config PDA and its price accounts
 }
 
-pub fn handler(ctx: Context<UpdatePythPrice>, params: UpdatePythPriceParams) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, UpdatePythPrice<'info>>, params: UpdatePythPriceParams) -> Result<()> {
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_PRICE_UPDATES),
+        AerospacerOracleError::OperationPaused
+    );
+    
     let state = &mut ctx.accounts.state;
     let clock = &ctx.accounts.clock;
     
-    // Find the collateral data for the requested denom
-    let _collateral_data = state.collateral_data
-        .iter_mut()
-        .find(|d| d.denom == params.denom)
-        .ok_or(AerospacerOracleError::PriceFeedNotFound)?;
+    // The asset has to be configured
+    let mut collateral_data = registry::load_asset(&ctx.accounts.asset)?;
 
-    // PRODUCTION PYTH INTEGRATION CODE
-    let price_feed = SolanaPriceAccount::account_info_to_feed(&ctx.accounts.pyth_price_account)
-        .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
+    let mut price_accounts = vec![ctx.accounts.pyth_price_account.clone()];
+    price_accounts.extend_from_slice(ctx.remaining_accounts);
+    let price = adapters::read_price(&collateral_data, &price_accounts, &state.oracle_address, clock)?;
+    registry::record_price(&ctx.accounts.asset, &mut collateral_data, &price)?;
     
-    // Get latest price with hardcoded staleness validation for mainnet (60 seconds)
-    // let current_time = clock.unix_timestamp;
-    // let price = price_feed.get_price_no_older_than(current_time, 60)
-    //     .ok_or(AerospacerOracleError::PriceTooOld)?;
-
-    // Get the latest available price data (no staleness validation for devnet testing)
-    let price = price_feed.get_price_unchecked();
-
-    // Validate price data integrity with hardcoded confidence
-    require!(price.price > 0, AerospacerOracleError::PythPriceValidationFailed);
-    require!(price.conf >= 100, AerospacerOracleError::PythPriceValidationFailed);
-
+    if let Some(history) = &ctx.accounts.price_history {
+        let recorded = history.load_mut()?.push(&price);
+        debug_msg!("Price history updated: {}", recorded);
+    }
     
     // Update the last update timestamp
     state.last_update = clock.unix_timestamp;
@@ -66,5 +86,14 @@ pub fn handler(ctx: Context<UpdatePythPrice>, params: UpdatePythPriceParams) ->
     msg!("Publish Time: {}", price.publish_time);
     msg!("Updated at: {}", clock.unix_timestamp);
     
+    emit_cpi!(PriceUpdated {
+        denom: params.denom,
+        price: price.price,
+        confidence: price.conf,
+        exponent: price.expo,
+        publish_time: price.publish_time,
+        timestamp: clock.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -1,12 +1,28 @@
+// #[program] expands to IDL instructions that call the deprecated AccountInfo::realloc
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
+// Diagnostic logging for intermediate values. Compiled to a no-op unless the
+// `verbose-logs` feature is enabled, since each msg! costs compute units.
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
+pub mod adapters;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod registry;
 pub mod state;
 pub mod msg;
 
 use instructions::*;
-use crate::state::{PriceResponse, ConfigResponse, OracleStateAccount};
+use crate::state::{AssetState, PriceResponse, TwapResponse, ConfigResponse, OracleStateAccount};
 
 declare_id!("8Fu4YnUkfmrGQ3PTVoPfsAGjQ6NistGsiKpBEkPhzA2K");
 
@@ -38,7 +54,7 @@ pub mod aerospacer_oracle {
     }
 
     /// Set configuration for multiple collateral assets in batch (admin only)
-    pub fn set_data_batch(ctx: Context<SetDataBatch>, params: SetDataBatchParams) -> Result<()> {
+    pub fn set_data_batch<'info>(ctx: Context<'_, '_, 'info, 'info, SetDataBatch<'info>>, params: SetDataBatchParams) -> Result<()> {
         instructions::set_data_batch::handler(ctx, params)
     }
 
@@ -48,16 +64,31 @@ pub mod aerospacer_oracle {
     }
 
     /// Get real-time price for a specific collateral asset using Pyth SDK
-    pub fn get_price(ctx: Context<GetPrice>, params: GetPriceParams) -> Result<PriceResponse> {
+    pub fn get_price<'info>(ctx: Context<'_, '_, 'info, 'info, GetPrice<'info>>, params: GetPriceParams) -> Result<PriceResponse> {
         instructions::get_price::handler(ctx, params)
     }
 
+    /// Get the price an asset's price policy sets for a purpose: spot, EMA or TWAP
+    pub fn get_price_for<'info>(ctx: Context<'_, '_, 'info, 'info, GetPriceFor<'info>>, params: GetPriceForParams) -> Result<PriceResponse> {
+        instructions::get_price_for::handler(ctx, params)
+    }
+
+    /// Get the time-weighted average price of an asset over a window of its price history
+    pub fn get_twap(ctx: Context<GetTwap>, params: GetTwapParams) -> Result<TwapResponse> {
+        instructions::get_twap::handler(ctx, params)
+    }
+
+    /// Create the price history ring buffer of an asset (oracle manager, who funds the rent)
+    pub fn init_price_history(ctx: Context<InitPriceHistory>, params: InitPriceHistoryParams) -> Result<()> {
+        instructions::init_price_history::handler(ctx, params)
+    }
+
     /// Get configuration information (admin, oracle address, asset count, last update)
     pub fn get_config(ctx: Context<GetConfig>, params: GetConfigParams) -> Result<ConfigResponse> {
         instructions::get_config::handler(ctx, params)
     }
 
-    /// Get all supported asset denominations
+    /// Get the denominations of a page of assets, passed as remaining accounts
     pub fn get_all_denoms(ctx: Context<GetAllDenoms>, params: GetAllDenomsParams) -> Result<Vec<String>> {
         instructions::get_all_denoms::handler(ctx, params)
     }
@@ -67,7 +98,7 @@ pub mod aerospacer_oracle {
         instructions::get_price_id::handler(ctx, params)
     }
 
-    /// Get real-time prices for ALL supported collateral assets using Pyth SDK
+    /// Get real-time prices for a page of collateral assets using Pyth SDK
     pub fn get_all_prices(ctx: Context<GetAllPrices>, params: GetAllPricesParams) -> Result<Vec<PriceResponse>> {
         instructions::get_all_prices::handler(ctx, params)
     }
@@ -77,10 +108,91 @@ pub mod aerospacer_oracle {
         instructions::check_denom::handler(ctx, params)
     }
 
-    /// Update Pyth price feed for a specific asset (admin only)
-    pub fn update_pyth_price(ctx: Context<UpdatePythPrice>, params: UpdatePythPriceParams) -> Result<()> {
+    /// Read and record the latest price of an asset, appending it to its price history
+    /// (price keepers, admin or oracle manager)
+    pub fn update_pyth_price<'info>(ctx: Context<'_, '_, 'info, 'info, UpdatePythPrice<'info>>, params: UpdatePythPriceParams) -> Result<()> {
         instructions::update_pyth_price::handler(ctx, params)
     }
+
+    /// Grant a role (risk manager, oracle manager, fee manager, guardian) to an account (owner only)
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::grant_role::handler(ctx, params)
+    }
+
+    /// Revoke a role, leaving it unassigned (owner only)
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::revoke_role::handler(ctx, params)
+    }
+
+    /// Propose a new admin; takes effect only once accepted (admin only)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+        instructions::propose_admin::handler(ctx, params)
+    }
+
+    /// Accept a pending admin transfer (proposed admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Withdraw a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::cancel_admin_transfer::handler(ctx)
+    }
+
+    /// Pause or unpause operation classes (guardian can only pause, owner can do both)
+    pub fn set_paused(ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+        instructions::set_paused::handler(ctx, params)
+    }
+
+    /// Freeze or unfreeze a pegged asset's price (guardian can only freeze, owner can do both)
+    pub fn set_peg_frozen(ctx: Context<SetPegFrozen>, params: SetPegFrozenParams) -> Result<()> {
+        instructions::set_peg_frozen::handler(ctx, params)
+    }
+
+    /// Move an asset between lifecycle states (risk manager)
+    pub fn set_asset_state(ctx: Context<SetAssetState>, params: SetAssetStateParams) -> Result<()> {
+        instructions::set_asset_state::handler(ctx, params)
+    }
+
+    /// Get an asset's lifecycle state (permissionless)
+    pub fn get_asset_state(ctx: Context<GetAssetState>, params: GetAssetStateParams) -> Result<AssetState> {
+        instructions::get_asset_state::handler(ctx, params)
+    }
+
+    /// Register a price keeper allowed to call update_pyth_price (oracle manager)
+    pub fn add_updater(ctx: Context<AddUpdater>, params: AddUpdaterParams) -> Result<()> {
+        instructions::add_updater::handler(ctx, params)
+    }
+
+    /// Remove a price keeper (oracle manager)
+    pub fn remove_updater(ctx: Context<RemoveUpdater>, params: RemoveUpdaterParams) -> Result<()> {
+        instructions::remove_updater::handler(ctx, params)
+    }
+
+    /// Queue a parameter change behind the timelock delay (role required by the change)
+    pub fn queue_change(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+        instructions::queue_change::handler(ctx, params)
+    }
+
+    /// Apply a queued change once its delay has elapsed (permissionless)
+    pub fn execute_change<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteChange<'info>>, params: ExecuteChangeParams) -> Result<()> {
+        instructions::execute_change::handler(ctx, params)
+    }
+
+    /// Drop a queued change before it executes (guardian)
+    pub fn cancel_change(ctx: Context<CancelChange>, params: CancelChangeParams) -> Result<()> {
+        instructions::cancel_change::handler(ctx, params)
+    }
+
+    /// Resize the oracle state account and upgrade it to the current layout version (admin only)
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        instructions::migrate_state::handler(ctx)
+    }
+
+    /// Move one asset from the legacy state list into its own PDA (admin only)
+    pub fn migrate_asset(ctx: Context<MigrateAsset>, params: MigrateAssetParams) -> Result<()> {
+        instructions::migrate_asset::handler(ctx, params)
+    }
 }
 
 /// Helper functions for PDA derivation
@@ -0,0 +1,261 @@
+//! Asset config PDAs (see state::AssetConfig): creation, lookup and removal, plus
+//! creation of their price histories
+//!
+//! Shared by the direct asset instructions and timelocked execution, which passes
+//! the asset accounts through remaining_accounts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+use bytemuck::Zeroable;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::instructions::SetDataParams;
+use crate::adapters::AssetPrice;
+
+/// Check a denom can key an asset PDA
+pub fn validate_denom(denom: &str) -> Result<()> {
+    require!(!denom.is_empty(), AerospacerOracleError::InvalidCollateralData);
+    require!(denom.len() <= MAX_DENOM_LEN, AerospacerOracleError::InvalidCollateralData);
+    Ok(())
+}
+
+/// Read an asset config account owned by this program. Accounts created under an
+/// older, shorter layout read the fields they lack as zero
+pub fn load_asset(info: &AccountInfo) -> Result<AssetConfig> {
+    require_keys_eq!(*info.owner, crate::ID, AerospacerOracleError::InvalidAssetAccount);
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() > 8 && data[..8] == *AssetConfig::DISCRIMINATOR,
+        AerospacerOracleError::InvalidAssetAccount
+    );
+    let mut asset = AssetConfig::zeroed();
+    let body = &data[8..];
+    let len = body.len().min(std::mem::size_of::<AssetConfig>());
+    bytemuck::bytes_of_mut(&mut asset)[..len].copy_from_slice(&body[..len]);
+    Ok(asset)
+}
+
+/// Read the asset expected at `index` of a page
+pub fn load_asset_at(info: &AccountInfo, index: u32) -> Result<AssetConfig> {
+    let asset = load_asset(info)?;
+    require!(asset.index == index, AerospacerOracleError::InvalidAssetAccount);
+    Ok(asset)
+}
+
+/// Write an asset config back; a shorter account keeps only the fields it has room for
+fn store_asset(info: &AccountInfo, asset: &AssetConfig) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    let body = &mut data[8..];
+    let len = body.len().min(std::mem::size_of::<AssetConfig>());
+    body[..len].copy_from_slice(&bytemuck::bytes_of(asset)[..len]);
+    Ok(())
+}
+
+/// Record a price read on the asset's account (see AssetConfig::record_price)
+pub fn record_price(info: &AccountInfo, asset: &mut AssetConfig, price: &AssetPrice) -> Result<()> {
+    asset.record_price(price);
+    store_asset(info, asset)
+}
+
+/// Freeze or unfreeze a pegged asset's price on its account
+pub fn set_peg_frozen(info: &AccountInfo, asset: &mut AssetConfig, frozen: bool) -> Result<()> {
+    asset.peg_frozen = frozen as u8;
+    store_asset(info, asset)
+}
+
+/// Move an asset to another lifecycle state on its account
+pub fn set_asset_state(info: &AccountInfo, asset: &mut AssetConfig, state: AssetState) -> Result<()> {
+    asset.asset_state = state as u8;
+    store_asset(info, asset)
+}
+
+/// Grow an asset account created under an older layout to the current size, with
+/// `payer` covering the extra rent
+fn grow_asset<'info>(
+    asset_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if asset_info.data_len() >= AssetConfig::LEN {
+        return Ok(());
+    }
+    let rent = Rent::get()?.minimum_balance(AssetConfig::LEN);
+    let top_up = rent.saturating_sub(asset_info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: asset_info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    asset_info.resize(AssetConfig::LEN)?;
+    Ok(())
+}
+
+/// Create a program-owned PDA of `space` bytes with `payer` funding its rent
+fn create_pda<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    if info.lamports() == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: info.clone() },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+        return Ok(());
+    }
+    
+    // Someone already sent lamports to the address, which makes create_account fail
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: info.clone() },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: info.clone() },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )?;
+    Ok(())
+}
+
+/// Create or update the PDA for `params.denom`. A new asset takes the next registry
+/// index and `payer` funds its rent. Returns the new and previous configuration, None if new
+pub fn write_asset<'info>(
+    state: &mut OracleStateAccount,
+    asset_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    params: &SetDataParams,
+    configured_at: i64,
+) -> Result<(AssetConfig, Option<AssetConfig>)> {
+    validate_denom(&params.denom)?;
+    let (address, bump) = AssetConfig::get_pda(&params.denom, &crate::ID);
+    require_keys_eq!(asset_info.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    
+    if asset_info.owner == &crate::ID {
+        let mut asset = load_asset(asset_info)?;
+        let previous = asset;
+        asset.upgrade();
+        asset.set(params, configured_at);
+        grow_asset(asset_info, payer, system_program)?;
+        store_asset(asset_info, &asset)?;
+        return Ok((asset, Some(previous)));
+    }
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[b"asset", params.denom.as_bytes(), &[bump]]];
+    create_pda(asset_info, payer, system_program, signer_seeds, AssetConfig::LEN)?;
+    
+    let mut asset = AssetConfig::zeroed();
+    asset.set(params, configured_at);
+    asset.index = state.asset_count;
+    asset.bump = bump;
+    asset.version = AssetConfig::VERSION;
+    asset_info.try_borrow_mut_data()?[..8].copy_from_slice(AssetConfig::DISCRIMINATOR);
+    store_asset(asset_info, &asset)?;
+    
+    state.asset_count = state.asset_count
+        .checked_add(1)
+        .ok_or(AerospacerOracleError::Overflow)?;
+    
+    Ok((asset, None))
+}
+
+/// Close the PDA for `denom` and refund its rent to `rent_receiver`. The asset at the
+/// last registry index (`last_info`, the same account if it is the one removed) moves
+/// into the freed slot. Returns the removed configuration
+pub fn remove_asset<'info>(
+    state: &mut OracleStateAccount,
+    asset_info: &AccountInfo<'info>,
+    last_info: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    denom: &str,
+) -> Result<CollateralData> {
+    validate_denom(denom)?;
+    let (address, _) = AssetConfig::get_pda(denom, &crate::ID);
+    require_keys_eq!(asset_info.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    require!(
+        asset_info.owner == &crate::ID,
+        AerospacerOracleError::CollateralDataNotFound
+    );
+    
+    let removed = load_asset(asset_info)?;
+    let last_index = state.asset_count
+        .checked_sub(1)
+        .ok_or(AerospacerOracleError::CollateralDataNotFound)?;
+    if removed.index != last_index {
+        let mut last = load_asset_at(last_info, last_index)?;
+        last.index = removed.index;
+        store_asset(last_info, &last)?;
+    }
+    
+    let refund = rent_receiver.lamports()
+        .checked_add(asset_info.lamports())
+        .ok_or(AerospacerOracleError::Overflow)?;
+    **rent_receiver.try_borrow_mut_lamports()? = refund;
+    **asset_info.try_borrow_mut_lamports()? = 0;
+    asset_info.assign(&anchor_lang::system_program::ID);
+    asset_info.resize(0)?;
+    
+    state.asset_count = last_index;
+    
+    Ok(removed.to_collateral_data())
+}
+
+/// Create the price history PDA of a configured asset, with `payer` funding its rent
+pub fn create_price_history<'info>(
+    history_info: &AccountInfo<'info>,
+    asset: &AssetConfig,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    denom: &str,
+) -> Result<()> {
+    let (address, bump) = PriceHistory::get_pda(denom, &crate::ID);
+    require_keys_eq!(history_info.key(), address, AerospacerOracleError::InvalidAssetAccount);
+    require!(
+        history_info.owner != &crate::ID,
+        AerospacerOracleError::PriceHistoryExists
+    );
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[b"history", denom.as_bytes(), &[bump]]];
+    create_pda(history_info, payer, system_program, signer_seeds, PriceHistory::LEN)?;
+    
+    let mut data = history_info.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(PriceHistory::DISCRIMINATOR);
+    let history: &mut PriceHistory = bytemuck::from_bytes_mut(&mut data[8..]);
+    history.denom = asset.denom;
+    history.bump = bump;
+    history.version = PriceHistory::VERSION;
+    
+    Ok(())
+}
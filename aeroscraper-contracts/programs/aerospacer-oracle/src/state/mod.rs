@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use crate::error::AerospacerOracleError;
+use crate::adapters::AssetPrice;
+use crate::instructions::{
+    UpdateOracleAddressParams, SetDataParams, SetDataBatchParams, RemoveDataParams, SetAssetStateParams,
+    SetPegFrozenParams, AddUpdaterParams, RemoveUpdaterParams, InitPriceHistoryParams, MigrateAssetParams,
+};
 
 /// Main oracle state account containing all configuration and data
 #[account]
@@ -9,32 +16,814 @@ pub struct OracleStateAccount {
     /// External oracle provider address (e.g., Pyth Network)
     pub oracle_address: Pubkey,
     
-    /// Vector of supported collateral assets and their configuration
+    /// Legacy asset list from before per-asset PDAs (see AssetConfig). Only drained
+    /// by migrate_asset; new configuration never lands here
     pub collateral_data: Vec<CollateralData>,
     
     /// Timestamp of last state update
     pub last_update: i64,
+    
+    /// Guardian role: can pause operation classes
+    pub guardian: Pubkey,
+    
+    /// Bitmask of paused operation classes (see PAUSE_* constants)
+    pub paused: u8,
+    
+    /// Risk manager role
+    pub risk_manager: Pubkey,
+    
+    /// Oracle manager role: asset configuration and price updates
+    pub oracle_manager: Pubkey,
+    
+    /// Unused; the oracle has no fee settings, so it has no fee manager role. Kept so the
+    /// fields after it stay in place
+    pub reserved: Pubkey,
+    
+    /// Proposed new administrator awaiting acceptance (Pubkey::default() when none)
+    pub pending_admin: Pubkey,
+    
+    /// Seconds between queueing and executing a change (0 allows direct admin instructions)
+    pub timelock_delay: i64,
+    
+    /// Id (and PDA seed) of the next queued change
+    pub next_change_id: u64,
+    
+    /// Layout version (see Versioned); upgraded by migrate_state
+    pub version: u8,
+    
+    /// Number of AssetConfig PDAs, whose indices run 0..asset_count
+    pub asset_count: u32,
+    
+    /// Price keepers allowed to call update_pyth_price (at most MAX_UPDATERS), besides the
+    /// admin and oracle manager. While empty, only those two may push a price
+    pub updaters: Vec<Pubkey>,
 }
 
 impl OracleStateAccount {
     /// Calculate required account space
     /// admin: 32 bytes (Pubkey)
     /// oracle_address: 32 bytes (Pubkey) 
-    /// collateral_data: 4000 bytes (Vec<CollateralData> with room for ~20 assets)
+    /// collateral_data: 4000 bytes (legacy Vec<CollateralData>, empty once migrated)
     /// last_update: 8 bytes (i64)
-    /// Total: 8 + 32 + 32 + 4000 + 8 = 4080 bytes
-    pub const LEN: usize = 8 + 32 + 32 + 4000 + 8;
+    /// guardian: 32 bytes (Pubkey)
+    /// paused: 1 byte (u8)
+    /// risk_manager: 32 bytes (Pubkey)
+    /// oracle_manager: 32 bytes (Pubkey)
+    /// reserved: 32 bytes (Pubkey, formerly fee_manager)
+    /// pending_admin: 32 bytes (Pubkey)
+    /// timelock_delay: 8 bytes (i64)
+    /// next_change_id: 8 bytes (u64)
+    /// version: 1 byte (u8)
+    /// asset_count: 4 bytes (u32)
+    /// updaters: 4 + 8 × 32 bytes (Vec<Pubkey>, MAX_UPDATERS entries)
+    /// Total: 8 (discriminator) + 32 + 32 + 4000 + 8 + 32 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 4 + 260 = 4522 bytes
+    pub const LEN: usize = 8 + 32 + 32 + 4000 + 8 + 32 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 4 + (4 + 32 * MAX_UPDATERS);
+    
+    /// Size of an account allocated by the v0 program, which only had admin,
+    /// oracle_address, collateral_data and last_update (its LEN also counted the
+    /// discriminator twice)
+    pub const V0_ACCOUNT_LEN: usize = 8 + 8 + 32 + 32 + 4000 + 8;
+    
+    /// Zero every byte after last_update in the data of a v0 account. remove_data shrank
+    /// collateral_data without clearing what it left behind, and the fields appended since
+    /// would otherwise be read from those stale bytes
+    pub fn clear_v0_slack(data: &mut [u8]) -> Result<()> {
+        let mut body = &data[8..];
+        <(Pubkey, Pubkey, Vec<CollateralData>, i64)>::deserialize(&mut body)?;
+        let end = data.len() - body.len();
+        data[end..].fill(0);
+        Ok(())
+    }
     
     pub fn seeds() -> [&'static [u8]; 1] {
         [b"state"]
     }
     
+    /// Check whether an operation class is currently paused
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused & operation != 0
+    }
+
+    /// Current holder of a role (Pubkey::default() when unassigned)
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::RiskManager => self.risk_manager,
+            Role::OracleManager => self.oracle_manager,
+            Role::Guardian => self.guardian,
+        }
+    }
+    
+    /// Assign a role holder (Pubkey::default() revokes it)
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::RiskManager => self.risk_manager = holder,
+            Role::OracleManager => self.oracle_manager = holder,
+            Role::Guardian => self.guardian = holder,
+        }
+    }
+    
+    /// The owner can act in every role, since it could grant any role to itself anyway
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        *key == self.admin || (*key != Pubkey::default() && *key == self.role_holder(role))
+    }
+    
+    /// Keepers may push prices, as may the admin and oracle manager; with no keeper
+    /// registered only the latter can
+    pub fn can_update_prices(&self, key: &Pubkey) -> bool {
+        self.updaters.contains(key) || self.has_role(Role::OracleManager, key)
+    }
+    
+    /// Look up an asset in the legacy list by denom
+    pub fn find_collateral_data(&self, denom: &str) -> Option<&CollateralData> {
+        self.collateral_data.iter().find(|d| d.denom == denom)
+    }
+    
     /// Derive the oracle state PDA
     pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&Self::seeds(), program_id)
     }
 }
 
+/// Account layout versioning
+/// 
+/// Every account ends with a `version` byte. Accounts written before it existed read 0
+/// there from their allocation slack. Later fields are appended after `version` and get
+/// their defaults in `upgrade`; migrate_state resizes the state account before upgrading it.
+pub trait Versioned {
+    /// Current layout version
+    const VERSION: u8;
+    
+    /// Layout version the account was last written with
+    fn version(&self) -> u8;
+    
+    /// Bring an account written under an older layout up to VERSION.
+    /// Returns false if it was already current
+    fn upgrade(&mut self) -> bool;
+}
+
+impl Versioned for OracleStateAccount {
+    const VERSION: u8 = 3;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v1: roles, pause mask, pending admin and timelock are appended, all unset
+        if self.version < 1 {
+            self.guardian = Pubkey::default();
+            self.paused = 0;
+            self.risk_manager = Pubkey::default();
+            self.oracle_manager = Pubkey::default();
+            self.reserved = Pubkey::default();
+            self.pending_admin = Pubkey::default();
+            self.timelock_delay = 0;
+            self.next_change_id = 0;
+        }
+        // v2: assets move to AssetConfig PDAs; legacy entries are counted as migrate_asset moves them
+        if self.version < 2 {
+            self.asset_count = 0;
+        }
+        // v3: the keeper list is appended, empty
+        if self.version < 3 {
+            self.updaters.clear();
+        }
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for AssetConfig {
+    const VERSION: u8 = 1;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v1: the first layout; every asset account is created at it
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for PriceHistory {
+    const VERSION: u8 = 1;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for QueuedChange {
+    const VERSION: u8 = 2;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v2: the change is stored as length-prefixed bytes, so v1 queued changes no longer
+        // load; drain the queue before upgrading (see ACCOUNT_MIGRATION.md)
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+/// Access-control roles; the owner (`admin`) implicitly holds every role
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Risk parameters for collateral assets
+    RiskManager,
+    /// Asset configuration, oracle provider address and price updates
+    OracleManager,
+    /// Pausing operation classes
+    Guardian,
+}
+
+/// Where an asset's price comes from (see crate::adapters)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceSource {
+    /// Legacy Pyth push oracle price account
+    PythPush,
+    /// Pyth pull oracle `PriceUpdateV2` account, posted by the Pyth receiver program
+    PythPull,
+    /// Switchboard on-demand pull feed
+    SwitchboardOnDemand,
+    /// Price set by the oracle manager: `price` × 10^`exponent`
+    Fixed { price: i64, exponent: i32 },
+    /// SPL stake pool exchange rate in SOL per pool token, less `haircut_bps`; priced in
+    /// USD as a composite multiplied by the SOL asset
+    SplStakePool { haircut_bps: u16 },
+}
+
+impl PriceSource {
+    /// Value of AssetConfig::source for each variant; zeroed accounts read as PythPush
+    pub const PYTH_PUSH: u8 = 0;
+    pub const PYTH_PULL: u8 = 1;
+    pub const SWITCHBOARD_ON_DEMAND: u8 = 2;
+    pub const FIXED: u8 = 3;
+    pub const SPL_STAKE_POOL: u8 = 4;
+    
+    /// Stored kind byte; a fixed price's value and a stake pool's haircut are stored separately
+    pub fn kind(&self) -> u8 {
+        match self {
+            PriceSource::PythPush => Self::PYTH_PUSH,
+            PriceSource::PythPull => Self::PYTH_PULL,
+            PriceSource::SwitchboardOnDemand => Self::SWITCHBOARD_ON_DEMAND,
+            PriceSource::Fixed { .. } => Self::FIXED,
+            PriceSource::SplStakePool { .. } => Self::SPL_STAKE_POOL,
+        }
+    }
+    
+    /// Rebuild a source from its stored kind byte, fixed price and haircut
+    pub fn from_kind(kind: u8, fixed_price: i64, fixed_exponent: i32, haircut_bps: u16) -> Result<Self> {
+        match kind {
+            Self::PYTH_PUSH => Ok(PriceSource::PythPush),
+            Self::PYTH_PULL => Ok(PriceSource::PythPull),
+            Self::SWITCHBOARD_ON_DEMAND => Ok(PriceSource::SwitchboardOnDemand),
+            Self::FIXED => Ok(PriceSource::Fixed { price: fixed_price, exponent: fixed_exponent }),
+            Self::SPL_STAKE_POOL => Ok(PriceSource::SplStakePool { haircut_bps }),
+            _ => err!(AerospacerOracleError::InvalidPriceSource),
+        }
+    }
+}
+
+/// Health of an asset's price feed, as in Liquity's PriceFeed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceFeedStatus {
+    /// The price came from the sources, the primary one included
+    Working,
+    
+    /// The primary source was left out; the price came from the extra sources
+    UsingFallback,
+    
+    /// The sources stopped publishing; the last good price is served
+    Frozen,
+    
+    /// The sources report prices that cannot be used (non-positive, too uncertain or
+    /// disagreeing); the last good price is served
+    Broken,
+}
+
+impl PriceFeedStatus {
+    /// Whether the price was read from the sources just now, rather than the last good one
+    pub fn is_live(&self) -> bool {
+        matches!(self, PriceFeedStatus::Working | PriceFeedStatus::UsingFallback)
+    }
+    
+    /// Rebuild a status from its stored byte
+    pub fn from_u8(status: u8) -> Result<Self> {
+        match status {
+            0 => Ok(PriceFeedStatus::Working),
+            1 => Ok(PriceFeedStatus::UsingFallback),
+            2 => Ok(PriceFeedStatus::Frozen),
+            3 => Ok(PriceFeedStatus::Broken),
+            _ => err!(AerospacerOracleError::InvalidPriceData),
+        }
+    }
+}
+
+/// Lifecycle of a collateral asset, for winding it down without making its troves
+/// unpriceable. The oracle serves prices in every state; the protocol decides which
+/// operations each state allows
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AssetState {
+    /// Every operation
+    Active,
+    
+    /// No new debt or collateral: troves can only be repaid and closed, redeemed against
+    /// and liquidated
+    BorrowDisabled,
+    
+    /// Troves can only be liquidated
+    LiquidationOnly,
+    
+    /// No operation
+    Frozen,
+}
+
+impl AssetState {
+    /// Rebuild a state from its stored byte
+    pub fn from_u8(state: u8) -> Result<Self> {
+        match state {
+            0 => Ok(AssetState::Active),
+            1 => Ok(AssetState::BorrowDisabled),
+            2 => Ok(AssetState::LiquidationOnly),
+            3 => Ok(AssetState::Frozen),
+            _ => err!(AerospacerOracleError::InvalidAssetAccount),
+        }
+    }
+}
+
+/// Which price of an asset a decision uses
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceMode {
+    /// The latest price
+    Spot,
+    
+    /// The sources' exponential moving average (the latest price for sources without one)
+    Ema,
+    
+    /// Time-weighted average over the asset's price history (see PriceHistory)
+    Twap,
+}
+
+impl PriceMode {
+    /// Rebuild a mode from its stored byte
+    pub fn from_u8(mode: u8) -> Result<Self> {
+        match mode {
+            0 => Ok(PriceMode::Spot),
+            1 => Ok(PriceMode::Ema),
+            2 => Ok(PriceMode::Twap),
+            _ => err!(AerospacerOracleError::InvalidPricePolicy),
+        }
+    }
+}
+
+/// What a protocol needs a price for (see get_price_for)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricePurpose {
+    /// Opening a position or borrowing more against it
+    Borrow,
+    
+    /// Withdrawing collateral
+    Withdraw,
+    
+    /// Deciding whether a position can be liquidated
+    Liquidation,
+}
+
+/// Price mode an asset uses for each purpose
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PricePolicy {
+    pub borrow: PriceMode,
+    pub withdraw: PriceMode,
+    pub liquidation: PriceMode,
+    
+    /// TWAP window in seconds, used by Twap modes (1..=MAX_TWAP_WINDOW_SECS)
+    pub twap_window_secs: u32,
+}
+
+impl PricePolicy {
+    /// Spot prices for every purpose
+    pub const SPOT: Self = Self {
+        borrow: PriceMode::Spot,
+        withdraw: PriceMode::Spot,
+        liquidation: PriceMode::Spot,
+        twap_window_secs: 0,
+    };
+    
+    pub fn mode(&self, purpose: PricePurpose) -> PriceMode {
+        match purpose {
+            PricePurpose::Borrow => self.borrow,
+            PricePurpose::Withdraw => self.withdraw,
+            PricePurpose::Liquidation => self.liquidation,
+        }
+    }
+    
+    /// Check the TWAP window is usable if any purpose relies on it
+    pub fn validate(&self) -> Result<()> {
+        let uses_twap = [self.borrow, self.withdraw, self.liquidation]
+            .contains(&PriceMode::Twap);
+        if uses_twap {
+            require!(
+                self.twap_window_secs > 0 && self.twap_window_secs <= MAX_TWAP_WINDOW_SECS,
+                AerospacerOracleError::InvalidPricePolicy
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Exponent of the absolute price bounds: 100_000_000 is 1 USD
+pub const PRICE_BOUND_EXPONENT: i32 = -8;
+
+/// Limits a live price must meet before it is reported or recorded. A price outside
+/// them is treated like a frozen feed (see adapters::read_price)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceBounds {
+    /// Lowest and highest accepted price, scaled by 10^PRICE_BOUND_EXPONENT (0 = unbounded)
+    pub min_price: i64,
+    pub max_price: i64,
+    
+    /// Largest move from the last good price, in basis points, per `change_window_secs`
+    /// since it was published (0 = no limit)
+    pub max_change_bps: u16,
+    pub change_window_secs: u32,
+}
+
+impl PriceBounds {
+    /// No bounds and no change limit
+    pub const NONE: Self = Self {
+        min_price: 0,
+        max_price: 0,
+        max_change_bps: 0,
+        change_window_secs: 0,
+    };
+    
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_price >= 0 && self.max_price >= 0,
+            AerospacerOracleError::InvalidPriceBounds
+        );
+        if self.min_price > 0 && self.max_price > 0 {
+            require!(self.min_price < self.max_price, AerospacerOracleError::InvalidPriceBounds);
+        }
+        if self.max_change_bps > 0 {
+            require!(self.change_window_secs > 0, AerospacerOracleError::InvalidPriceBounds);
+        }
+        Ok(())
+    }
+}
+
+/// Price a pegged asset reports while its feed stays within its band, scaled by
+/// 10^PRICE_BOUND_EXPONENT: 1 USD
+pub const PEG_PRICE: i64 = 100_000_000;
+
+/// Widest peg band, in basis points of the peg
+pub const MAX_PEG_BAND_BPS: u16 = 1_000;
+
+/// How a composite asset's price is derived from its own feed and another asset's price
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CompositeOp {
+    /// Not a composite: the asset's own feed is its price
+    None,
+    /// Own feed × the other asset's price, e.g. mSOL/SOL × SOL/USD
+    Multiply,
+    /// Own feed ÷ the other asset's price, for a feed quoted in a currency the other asset
+    /// prices per dollar, e.g. SOL/JPY ÷ USD/JPY (an asset on the yen-per-dollar FX feed)
+    Divide,
+}
+
+impl CompositeOp {
+    pub fn from_u8(op: u8) -> Result<Self> {
+        match op {
+            0 => Ok(CompositeOp::None),
+            1 => Ok(CompositeOp::Multiply),
+            2 => Ok(CompositeOp::Divide),
+            _ => err!(AerospacerOracleError::InvalidCompositePrice),
+        }
+    }
+}
+
+/// Derives an asset's price from its own (aggregated) feed and the price of another
+/// configured asset, which must not be a composite itself
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompositePrice {
+    pub op: CompositeOp,
+    
+    /// Denom of the other asset; empty when `op` is None
+    pub denom: String,
+}
+
+impl CompositePrice {
+    /// A plain asset, priced by its own feed alone
+    pub const NONE: Self = Self {
+        op: CompositeOp::None,
+        denom: String::new(),
+    };
+    
+    pub fn validate(&self, own_denom: &str) -> Result<()> {
+        if self.op == CompositeOp::None {
+            require!(self.denom.is_empty(), AerospacerOracleError::InvalidCompositePrice);
+        } else {
+            require!(
+                !self.denom.is_empty() && self.denom.len() <= MAX_DENOM_LEN && self.denom != own_denom,
+                AerospacerOracleError::InvalidCompositePrice
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Longest TWAP window, so the observations it spans fit in a PriceHistory
+pub const MAX_TWAP_WINDOW_SECS: u32 = 3_600;
+
+/// Observations a PriceHistory holds before overwriting the oldest
+pub const PRICE_HISTORY_LEN: usize = 64;
+
+/// One price recorded in a PriceHistory
+#[zero_copy]
+pub struct Observation {
+    pub price: i64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub expo: i32,
+    
+    /// Alignment padding
+    pub padding: [u8; 4],
+}
+
+/// Ring buffer of an asset's recent prices (seeds: ["history", denom]), appended to by
+/// update_pyth_price and read by get_twap and TWAP price modes
+#[account(zero_copy)]
+pub struct PriceHistory {
+    /// Asset denomination, UTF-8 padded with zeros
+    pub denom: [u8; 32],
+    
+    /// Slot the next observation is written to
+    pub head: u32,
+    
+    /// Observations stored, at most PRICE_HISTORY_LEN
+    pub count: u32,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Layout version (see Versioned)
+    pub version: u8,
+    
+    /// Alignment padding
+    pub padding: [u8; 6],
+    
+    pub observations: [Observation; PRICE_HISTORY_LEN],
+}
+
+impl PriceHistory {
+    /// discriminator + fields (zero-copy accounts are sized exactly)
+    pub const LEN: usize = 8 + std::mem::size_of::<PriceHistory>();
+    
+    /// Derive the price history PDA for a denom
+    pub fn get_pda(denom: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"history", denom.as_bytes()], program_id)
+    }
+    
+    /// Most recent observation
+    pub fn latest(&self) -> Option<&Observation> {
+        (self.count > 0).then(|| {
+            let index = (self.head as usize + PRICE_HISTORY_LEN - 1) % PRICE_HISTORY_LEN;
+            &self.observations[index]
+        })
+    }
+    
+    /// Append a live price, unless it is no newer than the latest observation.
+    /// Returns whether it was recorded
+    pub fn push(&mut self, price: &AssetPrice) -> bool {
+        if self.latest().is_some_and(|latest| price.publish_time <= latest.publish_time) {
+            return false;
+        }
+        self.observations[self.head as usize] = Observation {
+            price: price.price,
+            conf: price.conf,
+            publish_time: price.publish_time,
+            expo: price.expo,
+            padding: [0; 4],
+        };
+        self.head = ((self.head as usize + 1) % PRICE_HISTORY_LEN) as u32;
+        self.count = (self.count + 1).min(PRICE_HISTORY_LEN as u32);
+        true
+    }
+    
+    /// Time-weighted average price and confidence over the `window` seconds before `now`.
+    /// Each observation holds from its publish time until the next one (the latest until
+    /// `now`), and the oldest one used must be published at or before the window start.
+    /// Values are brought to the finest exponent among the observations used. Returns
+    /// (price, conf, expo, observations used)
+    pub fn twap(&self, now: i64, window: u32) -> Result<(i64, u64, i32, u32)> {
+        require!(window > 0, AerospacerOracleError::InvalidPricePolicy);
+        let start = now.saturating_sub(window as i64);
+        
+        // Newest first, until one covers the window start
+        let mut used = Vec::new();
+        for age in 0..self.count as usize {
+            let index = (self.head as usize + PRICE_HISTORY_LEN - 1 - age) % PRICE_HISTORY_LEN;
+            let observation = &self.observations[index];
+            used.push(observation);
+            if observation.publish_time <= start {
+                break;
+            }
+        }
+        require!(
+            used.last().is_some_and(|oldest| oldest.publish_time <= start),
+            AerospacerOracleError::InsufficientPriceHistory
+        );
+        
+        let expo = used.iter().map(|o| o.expo).min().unwrap_or_default();
+        let (mut price_sum, mut conf_sum) = (0i128, 0i128);
+        let mut end = now;
+        for observation in &used {
+            let begin = observation.publish_time.max(start);
+            let weight = (end - begin).max(0) as i128;
+            let factor = 10i128
+                .checked_pow((observation.expo - expo) as u32)
+                .ok_or(AerospacerOracleError::Overflow)?;
+            let scale = factor.checked_mul(weight).ok_or(AerospacerOracleError::Overflow)?;
+            price_sum = (observation.price as i128)
+                .checked_mul(scale)
+                .and_then(|term| price_sum.checked_add(term))
+                .ok_or(AerospacerOracleError::Overflow)?;
+            conf_sum = (observation.conf as i128)
+                .checked_mul(scale)
+                .and_then(|term| conf_sum.checked_add(term))
+                .ok_or(AerospacerOracleError::Overflow)?;
+            end = begin;
+        }
+        
+        let window = window as i128;
+        Ok((
+            i64::try_from(price_sum / window).map_err(|_| AerospacerOracleError::Overflow)?,
+            u64::try_from(conf_sum / window).map_err(|_| AerospacerOracleError::Overflow)?,
+            expo,
+            used.len() as u32,
+        ))
+    }
+}
+
+/// Most price sources an asset can aggregate, the primary one included
+pub const MAX_PRICE_SOURCES: usize = 3;
+
+/// An additional price feed of an asset (see AssetConfig::extra_sources)
+#[zero_copy]
+pub struct SourceSlot {
+    /// Price account: the Pyth push price account or Switchboard feed
+    pub price_account: Pubkey,
+    
+    /// Pyth feed id or Switchboard feed hash
+    pub feed_id: [u8; 32],
+    
+    /// Source kind (see PriceSource); never Fixed
+    pub kind: u8,
+    
+    /// Alignment padding
+    pub padding: [u8; 7],
+}
+
+/// One price source of an asset, as the adapters read it
+#[derive(Clone, Copy, Debug)]
+pub struct SourceConfig {
+    pub source: PriceSource,
+    pub price_account: Pubkey,
+    
+    /// Feed id, zero for a fixed price
+    pub feed_id: [u8; 32],
+    
+    /// Pyth oracle program that owns push price accounts (OracleStateAccount::oracle_address)
+    pub pyth_program: Pubkey,
+}
+
+/// Most price keepers the oracle state holds
+pub const MAX_UPDATERS: usize = 8;
+
+/// Pause flag: halts update_pyth_price
+pub const PAUSE_PRICE_UPDATES: u8 = 1 << 0;
+
+/// Pause flag: halts set_data, set_data_batch and remove_data
+pub const PAUSE_ASSET_CONFIG: u8 = 1 << 1;
+
+/// All oracle pause flags combined
+pub const PAUSE_ALL: u8 = PAUSE_PRICE_UPDATES | PAUSE_ASSET_CONFIG;
+
+/// Upper bound for the timelock delay (30 days)
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Parameter change waiting in the timelock
+/// 
+/// Anyone can execute it once `eta` has passed; the guardian can cancel it before then.
+/// Rent is returned to the proposer either way.
+#[account]
+pub struct QueuedChange {
+    /// Sequential id, also the PDA seed
+    pub id: u64,
+    
+    /// Account that queued the change and paid its rent
+    pub proposer: Pubkey,
+    
+    /// Timestamp the change was queued
+    pub queued_at: i64,
+    
+    /// Earliest execution timestamp
+    pub eta: i64,
+    
+    /// CHANGE_VERSION the payload was encoded under
+    pub change_version: u8,
+    
+    /// Borsh-encoded TimelockedChange. Kept as bytes so the account still loads (and can be
+    /// cancelled) after an upgrade changes the shape of a change's parameters
+    pub change: Vec<u8>,
+    
+    /// Layout version (see Versioned)
+    pub version: u8,
+}
+
+impl QueuedChange {
+    /// discriminator + id + proposer + queued_at + eta + change_version + payload length + version
+    pub const BASE_LEN: usize = 8 + 8 + 32 + 8 + 8 + 1 + 4 + 1;
+    
+    /// Encoding of TimelockedChange payloads. Bump it whenever a variant or any of the
+    /// parameter structs it carries changes shape
+    pub const CHANGE_VERSION: u8 = 1;
+    
+    /// Payload size varies by variant, so the account is sized to the change it holds
+    pub fn space(change: &TimelockedChange) -> usize {
+        Self::BASE_LEN + change.try_to_vec().map_or(0, |data| data.len())
+    }
+    
+    /// Store a change under the current encoding
+    pub fn set_change(&mut self, change: &TimelockedChange) -> Result<()> {
+        self.change_version = Self::CHANGE_VERSION;
+        self.change = change.try_to_vec()?;
+        Ok(())
+    }
+    
+    /// Decode the queued change; one queued under another encoding has to be cancelled
+    /// and queued again
+    pub fn change(&self) -> Result<TimelockedChange> {
+        require!(
+            self.change_version == Self::CHANGE_VERSION,
+            AerospacerOracleError::StaleQueuedChange
+        );
+        TimelockedChange::try_from_slice(&self.change)
+            .map_err(|_| AerospacerOracleError::StaleQueuedChange.into())
+    }
+}
+
+/// Oracle parameter changes that go through the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TimelockedChange {
+    /// Replace the oracle provider address (oracle manager)
+    UpdateOracleAddress(UpdateOracleAddressParams),
+    /// Add or update one asset's price feed (oracle manager)
+    SetData(SetDataParams),
+    /// Add or update several assets' price feeds (oracle manager)
+    SetDataBatch(SetDataBatchParams),
+    /// Remove an asset (oracle manager)
+    RemoveData(RemoveDataParams),
+    /// Change the timelock delay itself (owner only)
+    SetTimelockDelay(i64),
+    /// Move an asset to another lifecycle state (risk manager)
+    SetAssetState(SetAssetStateParams),
+    /// Unfreeze a pegged asset (owner only; the guardian freezes directly)
+    SetPegFrozen(SetPegFrozenParams),
+    /// Register a price keeper (oracle manager)
+    AddUpdater(AddUpdaterParams),
+    /// Remove a price keeper (oracle manager)
+    RemoveUpdater(RemoveUpdaterParams),
+    /// Start recording an asset's price history (oracle manager)
+    InitPriceHistory(InitPriceHistoryParams),
+    /// Move a legacy collateral_data entry into its own account (owner only)
+    MigrateAsset(MigrateAssetParams),
+}
+
 /// Collateral asset data structure for oracle integration
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CollateralData {
@@ -55,16 +844,369 @@ pub struct CollateralData {
     pub pyth_price_account: Pubkey,
 }
 
+/// Longest denom an asset PDA can be keyed by (the PDA seed limit)
+pub const MAX_DENOM_LEN: usize = 32;
+
+/// Price age limit given to legacy assets moved by migrate_asset
+pub const DEFAULT_MAX_STALENESS_SECS: u32 = 60;
+
+/// Confidence-to-price limit (basis points) given to legacy assets moved by migrate_asset
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 200;
+
+/// Basis point denominator
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Price feed configuration for one collateral asset, stored in its own PDA
+/// (seeds: ["asset", denom]) and read zero-copy
+/// 
+/// `index` keeps the assets numbered 0..asset_count without gaps; removing an
+/// asset moves the last one into its slot. get_all_denoms and get_all_prices
+/// page over that range.
+/// 
+/// New fields are appended when the reserved bytes run out. An account created under a
+/// shorter layout reads the missing bytes as zero (registry::load_asset) and is grown on
+/// its next write.
+#[account(zero_copy)]
+pub struct AssetConfig {
+    /// Asset denomination, UTF-8 padded with zeros
+    pub denom: [u8; 32],
+    
+    /// Feed identifier (64 hex characters): the Pyth price feed id, or the Switchboard feed hash
+    pub price_id: [u8; 64],
+    
+    /// Price account read for this asset (Pyth price account or Switchboard feed; unused for fixed prices)
+    pub pyth_price_account: Pubkey,
+    
+    /// Timestamp when this asset was last configured
+    pub configured_at: i64,
+    
+    /// Position in the asset registry
+    pub index: u32,
+    
+    /// Decimal precision for price calculations (6, 18, etc.)
+    pub decimal: u8,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Layout version (see Versioned)
+    pub version: u8,
+    
+    /// Price source kind (see PriceSource)
+    pub source: u8,
+    
+    /// Oldest price accepted, in seconds between its publish time and the current clock
+    pub max_staleness_secs: u32,
+    
+    /// Widest confidence interval accepted, as basis points of the price
+    pub max_confidence_bps: u16,
+    
+    /// Alignment padding
+    pub padding: [u8; 2],
+    
+    /// Fixed price mantissa, for PriceSource::Fixed
+    pub fixed_price: i64,
+    
+    /// Fixed price exponent, for PriceSource::Fixed
+    pub fixed_exponent: i32,
+    
+    /// Exponent of the last good price
+    pub last_good_expo: i32,
+    
+    /// Last price read from the sources while they were live, 0 if there is none yet.
+    /// Kept across reconfiguration; served when the feed is frozen or broken
+    pub last_good_price: i64,
+    
+    /// Confidence of the last good price
+    pub last_good_conf: u64,
+    
+    /// Publish time of the last good price
+    pub last_good_publish_time: i64,
+    
+    /// Feed status at the last recorded read (see PriceFeedStatus)
+    pub price_status: u8,
+    
+    /// Price mode for borrowing (see PriceMode)
+    pub borrow_price_mode: u8,
+    
+    /// Price mode for withdrawing collateral
+    pub withdraw_price_mode: u8,
+    
+    /// Price mode for liquidation
+    pub liquidation_price_mode: u8,
+    
+    /// TWAP window for the Twap price modes, in seconds
+    pub twap_window_secs: u32,
+    
+    /// Alignment padding
+    pub padding3: [u8; 2],
+    
+    /// Largest move from the last good price per change window, in basis points (0 = no limit)
+    pub max_change_bps: u16,
+    
+    /// Window the change limit applies to, in seconds
+    pub change_window_secs: u32,
+    
+    /// Lowest and highest accepted price, scaled by 10^PRICE_BOUND_EXPONENT (0 = unbounded)
+    pub min_price: i64,
+    pub max_price: i64,
+    
+    /// Denom of the asset a composite price is derived from (zero when not a composite)
+    pub composite_denom: [u8; 32],
+    
+    /// CompositeOp applied to the own feed and that asset's price
+    pub composite_op: u8,
+    
+    /// Alignment padding
+    pub padding4: [u8; 7],
+    
+    /// Haircut on the exchange rate, in basis points, for PriceSource::SplStakePool
+    pub haircut_bps: u16,
+    
+    /// Band around PEG_PRICE within which the asset reports exactly the peg, in basis
+    /// points (0 = not pegged)
+    pub peg_band_bps: u16,
+    
+    /// Non-zero while the guardian has frozen the pegged asset's price
+    pub peg_frozen: u8,
+    
+    /// AssetState byte (0 = active)
+    pub asset_state: u8,
+    
+    /// Zeroed space for future per-asset settings
+    pub reserved: [u8; 2],
+    
+    /// Sources aggregated with the primary one, the first `source_count - 1` in use
+    pub extra_sources: [SourceSlot; MAX_PRICE_SOURCES - 1],
+    
+    /// Configured sources, the primary one included
+    pub source_count: u8,
+    
+    /// Fresh sources required to report a price
+    pub min_sources: u8,
+    
+    /// Widest spread allowed between fresh sources, as basis points of their median
+    pub max_deviation_bps: u16,
+    
+    /// Alignment padding
+    pub padding2: [u8; 4],
+}
+
+impl AssetConfig {
+    /// discriminator + fields (zero-copy accounts are sized exactly)
+    pub const LEN: usize = 8 + std::mem::size_of::<AssetConfig>();
+    
+    /// Derive the asset config PDA for a denom
+    pub fn get_pda(denom: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"asset", denom.as_bytes()], program_id)
+    }
+    
+    pub fn denom(&self) -> String {
+        Self::unpad(&self.denom)
+    }
+    
+    pub fn price_id(&self) -> String {
+        Self::unpad(&self.price_id)
+    }
+    
+    /// Overwrite the feed configuration, keeping index and bump
+    pub fn set(&mut self, params: &SetDataParams, configured_at: i64) {
+        self.denom = [0; 32];
+        self.denom[..params.denom.len()].copy_from_slice(params.denom.as_bytes());
+        self.price_id = [0; 64];
+        self.price_id[..params.price_id.len()].copy_from_slice(params.price_id.as_bytes());
+        self.pyth_price_account = params.pyth_price_account;
+        self.configured_at = configured_at;
+        self.decimal = params.decimal;
+        self.max_staleness_secs = params.max_staleness_secs;
+        self.max_confidence_bps = params.max_confidence_bps;
+        let (fixed_price, fixed_exponent) = match params.source {
+            PriceSource::Fixed { price, exponent } => (price, exponent),
+            _ => (0, 0),
+        };
+        self.source = params.source.kind();
+        self.fixed_price = fixed_price;
+        self.fixed_exponent = fixed_exponent;
+        self.haircut_bps = match params.source {
+            PriceSource::SplStakePool { haircut_bps } => haircut_bps,
+            _ => 0,
+        };
+        
+        self.extra_sources = [SourceSlot::zeroed(); MAX_PRICE_SOURCES - 1];
+        for (slot, extra) in self.extra_sources.iter_mut().zip(&params.extra_sources) {
+            slot.price_account = extra.price_account;
+            slot.feed_id = extra.feed_id();
+            slot.kind = extra.source.kind();
+        }
+        self.source_count = 1 + params.extra_sources.len() as u8;
+        self.min_sources = params.min_sources;
+        self.max_deviation_bps = params.max_deviation_bps;
+        
+        self.borrow_price_mode = params.price_policy.borrow as u8;
+        self.withdraw_price_mode = params.price_policy.withdraw as u8;
+        self.liquidation_price_mode = params.price_policy.liquidation as u8;
+        self.twap_window_secs = params.price_policy.twap_window_secs;
+        
+        self.min_price = params.price_bounds.min_price;
+        self.max_price = params.price_bounds.max_price;
+        self.max_change_bps = params.price_bounds.max_change_bps;
+        self.change_window_secs = params.price_bounds.change_window_secs;
+        
+        self.peg_band_bps = params.peg_band_bps;
+        
+        self.composite_op = params.composite.op as u8;
+        self.composite_denom = [0; 32];
+        self.composite_denom[..params.composite.denom.len()].copy_from_slice(params.composite.denom.as_bytes());
+    }
+    
+    /// Price mode for each purpose
+    pub fn price_policy(&self) -> Result<PricePolicy> {
+        Ok(PricePolicy {
+            borrow: PriceMode::from_u8(self.borrow_price_mode)?,
+            withdraw: PriceMode::from_u8(self.withdraw_price_mode)?,
+            liquidation: PriceMode::from_u8(self.liquidation_price_mode)?,
+            twap_window_secs: self.twap_window_secs,
+        })
+    }
+    
+    /// Sanity bounds on the asset's price
+    pub fn price_bounds(&self) -> PriceBounds {
+        PriceBounds {
+            min_price: self.min_price,
+            max_price: self.max_price,
+            max_change_bps: self.max_change_bps,
+            change_window_secs: self.change_window_secs,
+        }
+    }
+    
+    /// How the asset's price derives from another asset's, if at all
+    pub fn composite(&self) -> Result<CompositePrice> {
+        Ok(CompositePrice {
+            op: CompositeOp::from_u8(self.composite_op)?,
+            denom: Self::unpad(&self.composite_denom),
+        })
+    }
+    
+    /// The asset's lifecycle state
+    pub fn asset_state(&self) -> Result<AssetState> {
+        AssetState::from_u8(self.asset_state)
+    }
+    
+    /// The asset's primary price source
+    pub fn source(&self) -> Result<PriceSource> {
+        PriceSource::from_kind(self.source, self.fixed_price, self.fixed_exponent, self.haircut_bps)
+    }
+    
+    /// Primary feed id decoded from price_id, zero when there is none (fixed prices)
+    pub fn feed_id(&self) -> Result<[u8; 32]> {
+        let mut id = [0u8; 32];
+        let price_id = self.price_id();
+        if !price_id.is_empty() {
+            hex::decode_to_slice(price_id, &mut id)
+                .map_err(|_| AerospacerOracleError::InvalidPriceId)?;
+        }
+        Ok(id)
+    }
+    
+    /// Number of configured sources; accounts older than v5 have only the primary one
+    pub fn source_total(&self) -> usize {
+        self.source_count.max(1) as usize
+    }
+    
+    /// Fresh sources required; accounts older than v5 read 0
+    pub fn min_fresh_sources(&self) -> usize {
+        self.min_sources.max(1) as usize
+    }
+    
+    /// All configured sources, the primary one first
+    pub fn sources(&self, pyth_program: Pubkey) -> Result<Vec<SourceConfig>> {
+        let mut sources = vec![SourceConfig {
+            source: self.source()?,
+            price_account: self.pyth_price_account,
+            feed_id: self.feed_id()?,
+            pyth_program,
+        }];
+        for slot in &self.extra_sources[..self.source_total() - 1] {
+            sources.push(SourceConfig {
+                source: PriceSource::from_kind(slot.kind, 0, 0, 0)?,
+                price_account: slot.price_account,
+                feed_id: slot.feed_id,
+                pyth_program,
+            });
+        }
+        Ok(sources)
+    }
+    
+    /// Record the outcome of a price read: a live price becomes the last good one
+    pub fn record_price(&mut self, price: &AssetPrice) {
+        if price.status.is_live() {
+            self.last_good_price = price.price;
+            self.last_good_conf = price.conf;
+            self.last_good_expo = price.expo;
+            self.last_good_publish_time = price.publish_time;
+        }
+        self.price_status = price.status as u8;
+    }
+    
+    /// The last good price, served as `status`; None until a live price has been recorded
+    pub fn last_good(&self, status: PriceFeedStatus) -> Option<AssetPrice> {
+        (self.last_good_price > 0).then_some(AssetPrice {
+            price: self.last_good_price,
+            conf: self.last_good_conf,
+            expo: self.last_good_expo,
+            publish_time: self.last_good_publish_time,
+            ema_price: self.last_good_price,
+            ema_conf: self.last_good_conf,
+            sources_used: 0,
+            status,
+        })
+    }
+    
+    
+    /// Borsh view of the configuration, as used in events and the legacy list
+    pub fn to_collateral_data(&self) -> CollateralData {
+        CollateralData {
+            denom: self.denom(),
+            decimal: self.decimal,
+            price_id: self.price_id(),
+            configured_at: self.configured_at,
+            pyth_price_account: self.pyth_price_account,
+        }
+    }
+    
+    fn unpad(bytes: &[u8]) -> String {
+        let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..len]).into_owned()
+    }
+}
+
+/// Decimals of normalized prices: USD per whole token, 10^18 = 1 USD
+pub const NORMALIZED_PRICE_DECIMALS: u32 = 18;
+
+/// Bring a price mantissa at exponent `expo` to NORMALIZED_PRICE_DECIMALS
+pub fn normalize_price(value: i128, expo: i32) -> Result<u128> {
+    let value = u128::try_from(value).map_err(|_| AerospacerOracleError::InvalidPriceData)?;
+    let shift = expo + NORMALIZED_PRICE_DECIMALS as i32;
+    let factor = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(AerospacerOracleError::Overflow)?;
+    if shift >= 0 {
+        Ok(value.checked_mul(factor).ok_or(AerospacerOracleError::Overflow)?)
+    } else {
+        Ok(value / factor)
+    }
+}
+
 /// Price response containing real-time asset price data
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct PriceResponse {
     /// Asset denomination (e.g., "inj", "atom")
     pub denom: String,
     
-    /// Current real-time price from oracle (scaled by decimals)
+    /// Current real-time price from oracle, as a mantissa at `exponent`
     pub price: i64,
     
-    /// Decimal precision for price calculations
+    /// The token's decimals, to value base units with the normalized price
     pub decimal: u8,
     
     /// Timestamp when price was fetched
@@ -75,6 +1217,73 @@ pub struct PriceResponse {
     
     /// Price exponent (from Pyth)
     pub exponent: i32,
+    
+    /// The asset's staleness limit, so CPI callers can apply the same bound
+    pub max_staleness_secs: u32,
+    
+    /// Sources the price was aggregated from, bit i set for source i (0 = primary);
+    /// 0 for the last good price
+    pub sources_used: u8,
+    
+    /// Feed status; anything but Working and UsingFallback means the last good price
+    /// was served because the sources could not be used
+    pub status: PriceFeedStatus,
+    
+    /// Exponential moving average price and its confidence, with the same exponent as
+    /// `price` (the spot price for sources without an EMA)
+    pub ema_price: i64,
+    pub ema_confidence: u64,
+    
+    /// `price`, `confidence` and `ema_price` in USD per whole token with
+    /// NORMALIZED_PRICE_DECIMALS decimals, whatever the feed's exponent
+    pub normalized_price: u128,
+    pub normalized_confidence: u128,
+    pub normalized_ema_price: u128,
+    
+    /// The asset's lifecycle state, which decides what the protocol allows with it
+    pub asset_state: AssetState,
+}
+
+impl PriceResponse {
+    /// Response for a price of `asset`, with its normalized values
+    pub fn new(denom: String, asset: &AssetConfig, price: &AssetPrice) -> Result<Self> {
+        Ok(PriceResponse {
+            denom,
+            price: price.price,
+            decimal: asset.decimal,
+            timestamp: price.publish_time,
+            confidence: price.conf,
+            exponent: price.expo,
+            max_staleness_secs: asset.max_staleness_secs,
+            sources_used: price.sources_used,
+            status: price.status,
+            ema_price: price.ema_price,
+            ema_confidence: price.ema_conf,
+            normalized_price: normalize_price(price.price as i128, price.expo)?,
+            normalized_confidence: normalize_price(price.conf as i128, price.expo)?,
+            normalized_ema_price: normalize_price(price.ema_price as i128, price.expo)?,
+            asset_state: asset.asset_state()?,
+        })
+    }
+}
+
+/// Time-weighted average price over a window of an asset's price history
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TwapResponse {
+    pub denom: String,
+    
+    /// Average price and confidence, scaled by 10^exponent
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    
+    pub window_secs: u32,
+    
+    /// Observations the average was taken over
+    pub observations: u32,
+    
+    /// Publish time of the newest observation, so callers can judge how current it is
+    pub latest_publish_time: i64,
 }
 
 /// Configuration response containing contract settings
@@ -91,4 +1300,137 @@ pub struct ConfigResponse {
     
     /// Timestamp of last configuration update
     pub last_update: i64,
-}
\ No newline at end of file
+    
+    /// Emergency guardian address
+    pub guardian: Pubkey,
+    
+    /// Bitmask of paused operation classes
+    pub paused: u8,
+    
+    /// Risk manager role holder
+    pub risk_manager: Pubkey,
+    
+    /// Oracle manager role holder
+    pub oracle_manager: Pubkey,
+    
+    /// Proposed administrator awaiting acceptance
+    pub pending_admin: Pubkey,
+    
+    /// Current timelock delay in seconds
+    pub timelock_delay: i64,
+    
+    /// Registered price keepers
+    pub updaters: Vec<Pubkey>,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn collateral(denom: &str) -> CollateralData {
+        CollateralData {
+            denom: denom.to_string(),
+            decimal: 9,
+            price_id: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".to_string(),
+            configured_at: 1_700_000_000,
+            pyth_price_account: Pubkey::new_unique(),
+        }
+    }
+    
+    /// A v0 account as the baseline program left it after removing the second of three assets
+    fn v0_account_data() -> Vec<u8> {
+        let admin = Pubkey::new_unique();
+        let oracle_address = Pubkey::new_unique();
+        let mut data = vec![0u8; OracleStateAccount::V0_ACCOUNT_LEN];
+        data[..8].copy_from_slice(OracleStateAccount::DISCRIMINATOR);
+        
+        let full = vec![collateral("SOL"), collateral("ETH"), collateral("BTC")];
+        (admin, oracle_address, full, 1_700_000_000i64).serialize(&mut &mut data[8..]).unwrap();
+        let shrunk = vec![collateral("SOL"), collateral("BTC")];
+        (admin, oracle_address, shrunk, 1_700_000_100i64).serialize(&mut &mut data[8..]).unwrap();
+        
+        data.resize(8 + OracleStateAccount::LEN, 0);
+        data
+    }
+    
+    #[test]
+    fn test_v0_stale_bytes_read_as_later_fields() {
+        // Without clearing, the removed asset's bytes show up in the appended fields
+        let data = v0_account_data();
+        let state = OracleStateAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_ne!(state.guardian, Pubkey::default());
+    }
+    
+    #[test]
+    fn test_v0_migration_clears_stale_bytes() {
+        let mut data = v0_account_data();
+        OracleStateAccount::clear_v0_slack(&mut data).unwrap();
+        
+        let mut state = OracleStateAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(state.version, 0);
+        assert!(state.upgrade());
+        
+        assert_eq!(state.collateral_data.len(), 2);
+        assert_eq!(state.collateral_data[1].denom, "BTC");
+        assert_eq!(state.last_update, 1_700_000_100);
+        assert_eq!(state.guardian, Pubkey::default());
+        assert_eq!(state.paused, 0);
+        assert_eq!(state.risk_manager, Pubkey::default());
+        assert_eq!(state.oracle_manager, Pubkey::default());
+        assert_eq!(state.pending_admin, Pubkey::default());
+        assert_eq!(state.timelock_delay, 0);
+        assert_eq!(state.next_change_id, 0);
+        assert_eq!(state.asset_count, 0);
+        assert!(state.updaters.is_empty());
+        assert_eq!(state.version, OracleStateAccount::VERSION);
+        assert!(!state.upgrade());
+    }
+    
+    #[test]
+    fn test_upgrade_resets_fields_added_after_v0() {
+        let mut data = v0_account_data();
+        OracleStateAccount::clear_v0_slack(&mut data).unwrap();
+        let mut state = OracleStateAccount::try_deserialize(&mut &data[..]).unwrap();
+        
+        // Whatever an older program left in the appended fields is not kept
+        state.guardian = Pubkey::new_unique();
+        state.paused = 0xff;
+        state.timelock_delay = -1;
+        state.asset_count = 7;
+        state.updaters = vec![Pubkey::new_unique()];
+        assert!(state.upgrade());
+        
+        assert_eq!(state.guardian, Pubkey::default());
+        assert_eq!(state.paused, 0);
+        assert_eq!(state.timelock_delay, 0);
+        assert_eq!(state.asset_count, 0);
+        assert!(state.updaters.is_empty());
+    }
+    
+    fn observation(price: i64, expo: i32, publish_time: i64) -> Observation {
+        Observation { price, conf: 0, publish_time, expo, padding: [0; 4] }
+    }
+    
+    #[test]
+    fn test_twap_weights_observations_by_time() {
+        let mut history = PriceHistory::zeroed();
+        history.observations[0] = observation(100, -2, 0);
+        history.observations[1] = observation(3_000, -3, 50);
+        history.head = 2;
+        history.count = 2;
+        
+        // 1.00 for the first half of the window, 3.000 for the second
+        let (price, _, expo, used) = history.twap(100, 100).unwrap();
+        assert_eq!((price, expo, used), (2_000, -3, 2));
+    }
+    
+    #[test]
+    fn test_twap_overflow_is_an_error() {
+        let mut history = PriceHistory::zeroed();
+        history.observations[0] = observation(i64::MAX, 0, 0);
+        history.observations[1] = observation(1, -30, 50);
+        history.head = 2;
+        history.count = 2;
+        
+        assert!(history.twap(100, 100).is_err());
+    }
+}
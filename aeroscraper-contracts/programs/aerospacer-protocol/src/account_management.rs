@@ -3,8 +3,8 @@ use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
 use crate::state::*;
 use crate::error::*;
 
-/// Account management utilities for the protocol
-/// This module provides clean, type-safe account loading and management
+// Account management utilities for the protocol
+// This module provides clean, type-safe account loading and management
 
 /// Context for managing user trove accounts
 #[derive(Accounts)]
@@ -89,4 +89,58 @@ pub enum AerospacerProtocolError {
     
     #[msg("Invalid snapshot account - does not match expected PDA")]
     InvalidSnapshotAccount,
-}
\ No newline at end of file
+    
+    #[msg("Operation is paused")]
+    OperationPaused,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Protocol is shut down")]
+    ProtocolShutdown,
+    
+    #[msg("Protocol is not shut down")]
+    ProtocolNotShutdown,
+    
+    #[msg("Settlement redemptions are not open yet")]
+    SettlementNotOpen,
+    
+    #[msg("Settlement price can no longer be fixed")]
+    SettlementPriceWindowClosed,
+    
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    
+    #[msg("Parameter changes must be queued through the timelock")]
+    TimelockActive,
+    
+    #[msg("Queued change is not executable yet")]
+    ChangeNotReady,
+    
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    
+    #[msg("Unsupported stablecoin mint decimals")]
+    UnsupportedStablecoinDecimals,
+    
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Price data is too old (exceeds staleness threshold)")]
+    PriceTooOld,
+    
+    #[msg("Oracle is serving its last good price; only risk-reducing operations are allowed")]
+    PriceFeedNotLive,
+    
+    #[msg("The collateral's lifecycle state does not allow this operation")]
+    AssetStateRestricted,
+    
+    #[msg("Troves can no longer be settled once settlement redemptions are open")]
+    SettlementWindowClosed,
+    
+    #[msg("Queued change was encoded by an older program version; cancel and queue it again")]
+    StaleQueuedChange,
+    
+    #[msg("Fee distribution is paused; operations that charge a fee are unavailable until it resumes")]
+    FeeDistributionPaused,
+}
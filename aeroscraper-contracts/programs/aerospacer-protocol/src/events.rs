@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, Role};
+
+// Typed events for indexers, emitted via emit_cpi! so they survive log truncation.
+// Amounts use the same units as the accounts they come from (aUSD in base units of the
+// stablecoin mint, collateral in its native decimals, ICR in micro-percent).
+
+// Snapshot of the configurable protocol parameters (before/after pairs in ParamsChanged)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProtocolParams {
+    pub oracle_helper_addr: Pubkey,
+    pub oracle_state_addr: Pubkey,
+    pub fee_distributor_addr: Pubkey,
+    pub fee_state_addr: Pubkey,
+    pub minimum_collateral_ratio: u64,
+    pub protocol_fee: u8,
+    pub timelock_delay: i64,
+}
+
+impl From<&StateAccount> for ProtocolParams {
+    fn from(state: &StateAccount) -> Self {
+        Self {
+            oracle_helper_addr: state.oracle_helper_addr,
+            oracle_state_addr: state.oracle_state_addr,
+            fee_distributor_addr: state.fee_distributor_addr,
+            fee_state_addr: state.fee_state_addr,
+            minimum_collateral_ratio: state.minimum_collateral_ratio,
+            protocol_fee: state.protocol_fee,
+            timelock_delay: state.timelock_delay,
+        }
+    }
+}
+
+// Trove position at one point in time (before/after pairs in TroveAdjusted)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TroveSnapshot {
+    pub debt: u64,
+    pub collateral: u64,
+    pub icr: u64,                    // 0 when the trove has no debt
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TroveAdjustment {
+    AddCollateral,
+    RemoveCollateral,
+    Borrow,
+    Repay,
+}
+
+#[event]
+pub struct ProtocolInitialized {
+    pub admin: Pubkey,
+    pub stable_coin_mint: Pubkey,
+    pub stable_coin_decimals: u8,
+    pub minimum_collateral_ratio: u64,
+    pub protocol_fee: u8,
+    pub params: ProtocolParams,
+}
+
+#[event]
+pub struct TroveOpened {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub collateral_amount: u64,
+    pub loan_amount: u64,            // Minted to the owner before the fee is taken
+    pub fee_amount: u64,
+    pub debt: u64,
+    pub icr: u64,
+    pub price: u128,                 // Normalized oracle price: USD per whole token, 18 decimals
+}
+
+#[event]
+pub struct TroveAdjusted {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub adjustment: TroveAdjustment,
+    pub amount: u64,                 // Collateral or aUSD moved by this adjustment
+    pub fee_amount: u64,             // Only non-zero for Borrow
+    pub before: TroveSnapshot,
+    pub after: TroveSnapshot,
+    pub price: u128,                 // Normalized oracle price; 0 when the adjustment did not need a price
+}
+
+#[event]
+pub struct TroveClosed {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub debt_repaid: u64,
+    pub collateral_returned: u64,
+}
+
+#[event]
+pub struct TroveLiquidated {
+    pub owner: Pubkey,
+    pub liquidator: Pubkey,
+    pub collateral_denom: String,
+    pub debt: u64,
+    pub collateral: u64,
+    pub icr: u64,
+    pub price: u128,                 // Normalized oracle price: USD per whole token, 18 decimals
+    pub debt_absorbed_by_pool: u64,  // Remainder was redistributed to active troves
+}
+
+#[event]
+pub struct TrovesLiquidated {
+    pub liquidator: Pubkey,
+    pub collateral_denom: String,
+    pub liquidated_count: u32,
+    pub total_debt: u64,
+    pub total_collateral: u64,
+}
+
+#[event]
+pub struct TroveRedeemed {
+    pub owner: Pubkey,
+    pub redeemer: Pubkey,
+    pub collateral_denom: String,
+    pub debt_redeemed: u64,
+    pub collateral_redeemed: u64,
+    pub debt_after: u64,
+}
+
+#[event]
+pub struct Redemption {
+    pub redeemer: Pubkey,
+    pub collateral_denom: String,
+    pub amount: u64,                 // Gross aUSD offered, including the fee
+    pub fee_amount: u64,
+    pub collateral_received: u64,
+    pub troves_redeemed: u32,
+}
+
+#[event]
+pub struct StakeChanged {
+    pub owner: Pubkey,
+    pub deposit_before: u64,         // Compounded deposit before the change
+    pub deposit_after: u64,
+    pub total_stake_before: u64,
+    pub total_stake_after: u64,
+}
+
+#[event]
+pub struct GainsWithdrawn {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StablecoinTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ParamsChanged {
+    pub authority: Pubkey,           // Signer of the direct instruction, or proposer of a timelocked change
+    pub before: ProtocolParams,
+    pub after: ProtocolParams,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub role: Role,
+    pub previous_holder: Pubkey,
+    pub new_holder: Pubkey,          // Pubkey::default() when revoked
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub authority: Pubkey,
+    pub paused_before: u8,
+    pub paused_after: u8,
+}
+
+#[event]
+pub struct ChangeQueued {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ChangeExecuted {
+    pub id: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ChangeCancelled {
+    pub id: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct StateMigrated {
+    pub admin: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub data_len: u64,               // Account size after the resize
+}
+
+#[event]
+pub struct SettlementPriceFixed {
+    pub admin: Pubkey,
+    pub collateral_denom: String,
+    pub price: u128,                 // Normalized oracle price: USD per whole token, 18 decimals
+    pub shutdown_timestamp: i64,
+    pub settled_denom_count: u8,
+}
+
+#[event]
+pub struct TroveSettled {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub debt_settled: u64,
+    pub collateral_reserved: u64,    // Moved into the aUSD backing pool
+    pub collateral_remaining: u64,   // Left for the owner to withdraw
+}
+
+#[event]
+pub struct SettledCollateralWithdrawn {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SettlementRedeemed {
+    pub redeemer: Pubkey,
+    pub amount: u64,                 // aUSD burned
+    pub outstanding_supply: u64,     // aUSD supply before the burn
+    pub payouts: Vec<(String, u64)>,
+}
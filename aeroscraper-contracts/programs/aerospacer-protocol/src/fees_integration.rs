@@ -1,16 +1,34 @@
 use anchor_lang::prelude::*;
 use crate::utils::*;
 use crate::error::*;
+use aerospacer_fees::state::{FeeStateAccount, PAUSE_FEE_DISTRIBUTION};
+
+/// Fee percentage to charge on an operation. While the fee program's guardian has paused
+/// fee distribution, an operation that owes a fee is rejected rather than run without one
+pub fn checked_protocol_fee(protocol_fee: u8, fees_state: &AccountInfo) -> Result<u8> {
+    if protocol_fee == 0 {
+        return Ok(0);
+    }
+    let data = fees_state.try_borrow_data()?;
+    let fee_state = FeeStateAccount::try_deserialize(&mut &data[..])?;
+    require!(
+        !fee_state.is_paused(PAUSE_FEE_DISTRIBUTION),
+        AerospacerProtocolError::FeeDistributionPaused
+    );
+    Ok(protocol_fee)
+}
 
 /// Process protocol fee collection and distribution via CPI to aerospacer-fees
 /// This function handles the complete fee flow:
 /// 1. Calculate fee amount
 /// 2. Call distribute_fee instruction via CPI (which handles token transfers)
 /// 3. Return net amount after fee
+#[allow(clippy::too_many_arguments)]
 pub fn process_protocol_fee<'info>(
     operation_amount: u64,
     protocol_fee_percentage: u8,
     fees_program: AccountInfo<'info>,
+    fees_event_authority: AccountInfo<'info>,
     payer: AccountInfo<'info>,
     fees_state: AccountInfo<'info>,
     payer_token_account: AccountInfo<'info>,
@@ -26,14 +44,15 @@ pub fn process_protocol_fee<'info>(
         return Ok(operation_amount);
     }
     
-    msg!("Processing protocol fee: {} aUSD ({}%)", fee_amount, protocol_fee_percentage);
-    msg!("Operation amount: {} aUSD", operation_amount);
+    debug_msg!("Processing protocol fee: {} aUSD ({}%)", fee_amount, protocol_fee_percentage);
+    debug_msg!("Operation amount: {} aUSD", operation_amount);
     
     // Call distribute_fee instruction via CPI
     // The fee contract will handle transferring tokens from payer_token_account
     // to the appropriate destinations (stability pool or fee addresses)
     distribute_fee_via_cpi(
         &fees_program,
+        &fees_event_authority,
         &payer,
         &fees_state,
         &payer_token_account,
@@ -44,7 +63,7 @@ pub fn process_protocol_fee<'info>(
         fee_amount,
     )?;
     
-    msg!("Fee distributed successfully: {} aUSD", fee_amount);
+    debug_msg!("Fee distributed successfully: {} aUSD", fee_amount);
     
     // Return net amount after fee
     calculate_net_amount_after_fee(operation_amount, protocol_fee_percentage)
@@ -93,14 +112,16 @@ pub fn validate_fees_accounts<'info>(
         AerospacerProtocolError::Unauthorized
     );
     
-    msg!("All fees contract accounts validated successfully");
+    debug_msg!("All fees contract accounts validated successfully");
     Ok(())
 }
 
 /// Call distribute_fee instruction on aerospacer-fees contract via CPI
 /// The fee contract will transfer tokens from payer to destinations directly
+#[allow(clippy::too_many_arguments)]
 fn distribute_fee_via_cpi<'info>(
     fees_program: &AccountInfo<'info>,
+    fees_event_authority: &AccountInfo<'info>,
     payer: &AccountInfo<'info>,
     fees_state: &AccountInfo<'info>,
     payer_token_account: &AccountInfo<'info>,
@@ -114,10 +135,10 @@ fn distribute_fee_via_cpi<'info>(
     use anchor_lang::solana_program::program::invoke;
     use anchor_lang::solana_program::hash::hash;
     
-    msg!("Distributing fee via aerospacer-fees contract CPI");
-    msg!("Fee amount: {} aUSD", fee_amount);
-    msg!("Fees program: {}", fees_program.key());
-    msg!("Fees state: {}", fees_state.key());
+    debug_msg!("Distributing fee via aerospacer-fees contract CPI");
+    debug_msg!("Fee amount: {} aUSD", fee_amount);
+    debug_msg!("Fees program: {}", fees_program.key());
+    debug_msg!("Fees state: {}", fees_state.key());
     
     // Build DistributeFeeParams
     #[derive(AnchorSerialize)]
@@ -147,6 +168,9 @@ fn distribute_fee_via_cpi<'info>(
         anchor_lang::solana_program::instruction::AccountMeta::new(*fee_address_1_token_account.key, false),   // ✅ fee_address_1_token_account as writable, not signer
         anchor_lang::solana_program::instruction::AccountMeta::new(*fee_address_2_token_account.key, false),   // ✅ fee_address_2_token_account as writable, not signer
         anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*token_program.key, false),       // ✅ token_program as readonly
+        // distribute_fee is #[event_cpi]: its event authority and program id follow the declared accounts
+        anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*fees_event_authority.key, false),
+        anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*fees_program.key, false),
     ];
     
     // Create instruction
@@ -167,11 +191,12 @@ fn distribute_fee_via_cpi<'info>(
         fee_address_1_token_account.to_account_info(),
         fee_address_2_token_account.to_account_info(),
         token_program.to_account_info(),
+        fees_event_authority.to_account_info(),
     ];
     
     invoke(&ix, &account_infos)?;
     
-    msg!("Fee distribution CPI completed successfully");
+    debug_msg!("Fee distribution CPI completed successfully");
     Ok(())
 }
 
@@ -188,10 +213,10 @@ pub fn initialize_fees_contract_if_needed<'info>(
     
     // Check if fees state account is already initialized
     if fees_state.data_is_empty() {
-        msg!("Initializing aerospacer-fees contract via CPI...");
-        msg!("Fees program: {}", fees_program.key());
-        msg!("Admin: {}", admin.key());
-        msg!("Fees state: {}", fees_state.key());
+        debug_msg!("Initializing aerospacer-fees contract via CPI...");
+        debug_msg!("Fees program: {}", fees_program.key());
+        debug_msg!("Admin: {}", admin.key());
+        debug_msg!("Fees state: {}", fees_state.key());
         
         // Calculate instruction discriminator: first 8 bytes of SHA256("global:initialize")
         let preimage = b"global:initialize";
@@ -227,9 +252,9 @@ pub fn initialize_fees_contract_if_needed<'info>(
         
         invoke(&ix, &account_infos)?;
         
-        msg!("Fees contract initialization CPI completed successfully");
+        debug_msg!("Fees contract initialization CPI completed successfully");
     } else {
-        msg!("Fees contract already initialized");
+        debug_msg!("Fees contract already initialized");
     }
     
     Ok(())
@@ -240,9 +265,9 @@ pub fn get_fees_config<'info>(
     fees_program: &AccountInfo<'info>,
     fees_state: &AccountInfo<'info>,
 ) -> Result<FeesConfigResponse> {
-    msg!("Getting fees contract configuration via CPI...");
-    msg!("Fees program: {}", fees_program.key());
-    msg!("Fees state: {}", fees_state.key());
+    debug_msg!("Getting fees contract configuration via CPI...");
+    debug_msg!("Fees program: {}", fees_program.key());
+    debug_msg!("Fees state: {}", fees_state.key());
     
     // Build CPI instruction for get_config
     use anchor_lang::solana_program::instruction::Instruction;
@@ -298,10 +323,10 @@ pub fn get_fees_config<'info>(
     
     let config: FeeConfigResponse = FeeConfigResponse::deserialize(&mut &return_data.1[..])?;
     
-    msg!("Fees config retrieved successfully:");
-    msg!("  Admin: {}", config.admin);
-    msg!("  Stake enabled: {}", config.is_stake_enabled);
-    msg!("  Total fees collected: {}", config.total_fees_collected);
+    debug_msg!("Fees config retrieved successfully:");
+    debug_msg!("  Admin: {}", config.admin);
+    debug_msg!("  Stake enabled: {}", config.is_stake_enabled);
+    debug_msg!("  Total fees collected: {}", config.total_fees_collected);
     
     // Convert to our local response type
     Ok(FeesConfigResponse {
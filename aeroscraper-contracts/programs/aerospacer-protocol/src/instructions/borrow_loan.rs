@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, MintTo};
 use crate::state::*;
 use crate::error::*;
@@ -16,6 +17,7 @@ pub struct BorrowLoanParams {
     pub next_node_id: Option<Pubkey>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: BorrowLoanParams)]
 pub struct BorrowLoan<'info> {
@@ -106,14 +108,37 @@ pub struct BorrowLoan<'info> {
     
     /// CHECK: Oracle state account - validated against state
     #[account(
-        mut,
         constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
     
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: AccountInfo<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: AccountInfo<'info>,
+    
     /// CHECK: Pyth price account for collateral price feed
     pub pyth_price_account: AccountInfo<'info>,
     
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Oracle price history PDA for the collateral denom, needed if its price policy
+    /// uses a TWAP here - validated by the oracle program
+    pub price_history: Option<UncheckedAccount<'info>>,
+    
     /// Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
 
@@ -124,6 +149,9 @@ pub struct BorrowLoan<'info> {
     )]
     pub fees_program: AccountInfo<'info>,
     
+    /// CHECK: Event authority PDA of the fees program - validated by the fees program
+    pub fees_event_authority: AccountInfo<'info>,
+    
     /// CHECK: Fees state account - validated against state
     #[account(
         mut,
@@ -150,6 +178,22 @@ pub struct BorrowLoan<'info> {
 
 
 pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.liquidity_threshold.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.total_collateral_amount.upgrade();
+    
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_MINTING),
+        AerospacerProtocolError::OperationPaused
+    );
+    require!(
+        !ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolShutdown
+    );
+    
     // Validate input parameters
     require!(
         params.loan_amount > 0,
@@ -157,7 +201,7 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
     );
     
     require!(
-        params.loan_amount >= MINIMUM_LOAN_AMOUNT,
+        params.loan_amount >= ctx.accounts.state.minimum_loan_amount(),
         AerospacerProtocolError::LoanAmountBelowMinimum
     );
     
@@ -172,6 +216,13 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
         AerospacerProtocolError::TroveDoesNotExist
     );
     
+    // Trove position before the operation, for the TroveAdjusted event
+    let before = TroveSnapshot {
+        debt: ctx.accounts.user_debt_amount.amount,
+        collateral: ctx.accounts.user_collateral_amount.amount,
+        icr: ctx.accounts.liquidity_threshold.ratio,
+    };
+    
     // Create context structs for clean architecture
     let mut trove_ctx = TroveContext {
         user: ctx.accounts.user.clone(),
@@ -192,12 +243,20 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
     let oracle_ctx = OracleContext {
         oracle_program: ctx.accounts.oracle_program.clone(),
         oracle_state: ctx.accounts.oracle_state.clone(),
+        oracle_asset: ctx.accounts.oracle_asset.clone(),
+        oracle_event_authority: ctx.accounts.oracle_event_authority.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        extra_price_accounts: extra_price_accounts(&[
+            &ctx.accounts.secondary_price_account,
+            &ctx.accounts.tertiary_price_account,
+        ]),
+        price_history: ctx.accounts.price_history.as_ref().map(|a| a.to_account_info()),
         clock: ctx.accounts.clock.to_account_info(),
     };
     
     // Calculate fee amount for distribution
-    let fee_amount = calculate_protocol_fee(params.loan_amount, ctx.accounts.state.protocol_fee)?;
+    let protocol_fee = checked_protocol_fee(ctx.accounts.state.protocol_fee, &ctx.accounts.fees_state)?;
+    let fee_amount = calculate_protocol_fee(params.loan_amount, protocol_fee)?;
     
     // CRITICAL: Record FULL gross amount as debt (including fee)
     // This ensures all minted tokens have matching debt liability
@@ -218,7 +277,7 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
         
         msg!("Validating ICR ordering with {} neighbor account(s)", ctx.remaining_accounts.len());
         
-        let prev_icr = if ctx.remaining_accounts.len() >= 1 {
+        let prev_icr = if !ctx.remaining_accounts.is_empty() {
             let prev_lt = &ctx.remaining_accounts[0];
             let prev_data = prev_lt.try_borrow_data()?;
             let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
@@ -286,8 +345,9 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
     if fee_amount > 0 {
         let net_amount = process_protocol_fee(
             params.loan_amount,
-            ctx.accounts.state.protocol_fee,
+            protocol_fee,
             ctx.accounts.fees_program.to_account_info(),
+            ctx.accounts.fees_event_authority.to_account_info(),
             ctx.accounts.user.to_account_info(),
             ctx.accounts.fees_state.to_account_info(),
             ctx.accounts.user_stablecoin_account.to_account_info(),
@@ -310,5 +370,20 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
     msg!("New ICR: {}", result.new_icr);
     msg!("Collateral amount: {}", result.new_collateral_amount);
     
+    emit_cpi!(TroveAdjusted {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom.clone(),
+        adjustment: TroveAdjustment::Borrow,
+        amount: params.loan_amount,
+        fee_amount,
+        before,
+        after: TroveSnapshot {
+            debt: result.new_debt_amount,
+            collateral: result.new_collateral_amount,
+            icr: result.new_icr,
+        },
+        price: result.price,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, QueuedChange, Role};
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelChangeParams {
+    pub id: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CancelChangeParams)]
+pub struct CancelChange<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Guardian, &authority.key()) @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: Account<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"timelock", params.id.to_le_bytes().as_ref()],
+        bump,
+        close = proposer
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Rent refund destination, must match the recorded proposer
+    #[account(
+        mut,
+        address = queued_change.proposer @ AerospacerProtocolError::InvalidAddress
+    )]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelChange>, params: CancelChangeParams) -> Result<()> {
+    msg!("Change {} cancelled by {}", params.id, ctx.accounts.authority.key());
+    
+    emit_cpi!(ChangeCancelled {
+        id: params.id,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
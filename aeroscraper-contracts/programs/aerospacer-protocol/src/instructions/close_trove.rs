@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, Burn};
 use crate::state::*;
 use crate::error::*;
+use crate::oracle::{get_asset_state_via_cpi, is_asset_removed, TroveAction};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CloseTroveParams {
     pub collateral_denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: CloseTroveParams)]
 pub struct CloseTrove<'info> {
@@ -81,17 +84,40 @@ pub struct CloseTrove<'info> {
     )]
     pub total_collateral_amount: AccountInfo<'info>,
 
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program
+    pub oracle_asset: AccountInfo<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    
     // Validate collateral denomination
     require!(
         !params.collateral_denom.is_empty(),
         AerospacerProtocolError::InvalidAmount
     );
     
+    // Lifecycle gate: closing repays the whole debt, so it follows the repay rule. An
+    // asset the oracle has removed has no state, and its troves must stay closable
+    if !is_asset_removed(&params.collateral_denom, &ctx.accounts.oracle_program, &ctx.accounts.oracle_asset) {
+        get_asset_state_via_cpi(
+            params.collateral_denom.clone(),
+            ctx.accounts.oracle_program.clone(),
+            ctx.accounts.oracle_asset.clone(),
+        )?.check(TroveAction::Repay)?;
+    }
+    
     // Apply pending redistribution rewards before closing trove
     use crate::trove_management::apply_pending_rewards;
     let total_collateral_data = ctx.accounts.total_collateral_amount.try_borrow_mut_data()?;
@@ -188,5 +214,12 @@ pub fn handler(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()>
     msg!("  Collateral returned: {} {}", collateral_amount, params.collateral_denom);
     msg!("  Total protocol debt: {}", ctx.accounts.state.total_debt_amount);
     
+    emit_cpi!(TroveClosed {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom,
+        debt_repaid: debt_amount,
+        collateral_returned: collateral_amount,
+    });
+    
     Ok(())
 }
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::{extra_price_accounts, OracleContext, PriceUse};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EmergencyShutdownParams {
+    pub collateral_denom: String, // Denom whose final price is frozen by this call
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: EmergencyShutdownParams)]
+pub struct EmergencyShutdown<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: Box<Account<'info, StateAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CollateralSettlement::LEN,
+        seeds = [b"collateral_settlement", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_settlement: Box<Account<'info, CollateralSettlement>>,
+
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+    
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: AccountInfo<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed
+    pub pyth_price_account: AccountInfo<'info>,
+    
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Shut the protocol down and freeze the final price for one collateral denom.
+// The first call flips the protocol into shutdown; it must be repeated once per
+// supported denom before the settlement grace period ends.
+pub fn handler(ctx: Context<EmergencyShutdown>, params: EmergencyShutdownParams) -> Result<()> {
+    require!(
+        !params.collateral_denom.is_empty(),
+        AerospacerProtocolError::InvalidAmount
+    );
+    
+    let now = ctx.accounts.clock.unix_timestamp;
+    let state = &mut ctx.accounts.state;
+    
+    if !state.is_shutdown {
+        state.is_shutdown = true;
+        state.shutdown_timestamp = now;
+        msg!("EMERGENCY SHUTDOWN triggered at {}", now);
+    }
+    
+    // Prices must all be frozen before redemptions open, otherwise early
+    // redeemers would miss part of the basket
+    require!(
+        now < state.shutdown_timestamp + SETTLEMENT_GRACE_PERIOD,
+        AerospacerProtocolError::SettlementPriceWindowClosed
+    );
+    
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        oracle_asset: ctx.accounts.oracle_asset.clone(),
+        oracle_event_authority: ctx.accounts.oracle_event_authority.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        extra_price_accounts: extra_price_accounts(&[
+            &ctx.accounts.secondary_price_account,
+            &ctx.accounts.tertiary_price_account,
+        ]),
+        price_history: None,
+        clock: ctx.accounts.clock.to_account_info(),
+    };
+    let price = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price, PriceUse::Live)?;
+    
+    let settlement = &mut ctx.accounts.collateral_settlement;
+    settlement.denom = params.collateral_denom.clone();
+    settlement.price = price.normalized_price;
+    settlement.decimal = price.decimal;
+    settlement.fixed_at = now;
+    settlement.backing_collateral = 0;
+    settlement.version = CollateralSettlement::VERSION;
+    
+    state.settled_denom_count = state.settled_denom_count
+        .checked_add(1)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Settlement price frozen for {}: {} (18 decimals)", settlement.denom, settlement.price);
+    msg!("Denoms frozen so far: {}", state.settled_denom_count);
+    
+    emit_cpi!(SettlementPriceFixed {
+        admin: ctx.accounts.admin.key(),
+        collateral_denom: settlement.denom.clone(),
+        price: settlement.price,
+        shutdown_timestamp: state.shutdown_timestamp,
+        settled_denom_count: state.settled_denom_count,
+    });
+    
+    Ok(())
+}
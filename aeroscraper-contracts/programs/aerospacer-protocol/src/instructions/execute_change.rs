@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, QueuedChange, TimelockedChange};
+use crate::error::AerospacerProtocolError;
+use crate::instructions::{update_protocol_addresses, update_risk_params};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteChangeParams {
+    pub id: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: ExecuteChangeParams)]
+pub struct ExecuteChange<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"timelock", params.id.to_le_bytes().as_ref()],
+        bump,
+        close = proposer
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// CHECK: Rent refund destination, must match the recorded proposer
+    #[account(
+        mut,
+        address = queued_change.proposer @ AerospacerProtocolError::InvalidAddress
+    )]
+    pub proposer: UncheckedAccount<'info>,
+    
+    /// Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: authorization happened when the change was queued
+pub fn handler(ctx: Context<ExecuteChange>, params: ExecuteChangeParams) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        ctx.accounts.clock.unix_timestamp >= queued_change.eta,
+        AerospacerProtocolError::ChangeNotReady
+    );
+    
+    let before = ProtocolParams::from(&**state);
+    match &queued_change.change()? {
+        TimelockedChange::UpdateProtocolAddresses(change) => {
+            update_protocol_addresses::apply(state, change)?;
+        }
+        TimelockedChange::SetTimelockDelay(delay) => {
+            state.timelock_delay = *delay;
+            msg!("Timelock delay updated: {} seconds", delay);
+        }
+        TimelockedChange::UpdateRiskParams(change) => {
+            update_risk_params::apply(state, change)?;
+        }
+    }
+    
+    msg!("Change {} executed by {}", params.id, ctx.accounts.executor.key());
+    
+    emit_cpi!(ParamsChanged {
+        authority: queued_change.proposer,
+        before,
+        after: ProtocolParams::from(&**state),
+    });
+    emit_cpi!(ChangeExecuted {
+        id: params.id,
+        executor: ctx.accounts.executor.key(),
+    });
+    
+    Ok(())
+}
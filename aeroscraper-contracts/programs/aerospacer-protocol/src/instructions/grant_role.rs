@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, Role};
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    pub role: Role,
+    pub account: Pubkey, // New role holder (replaces any current holder)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: Account<'info, StateAccount>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+    require!(
+        params.account != Pubkey::default(),
+        AerospacerProtocolError::InvalidAddress
+    );
+    
+    let state = &mut ctx.accounts.state;
+    let previous = state.role_holder(params.role);
+    state.set_role_holder(params.role, params.account);
+    
+    msg!("Role {:?} granted to {} (previous holder: {})", params.role, params.account, previous);
+    
+    emit_cpi!(RoleChanged {
+        role: params.role,
+        previous_holder: previous,
+        new_holder: params.account,
+    });
+    
+    Ok(())
+}
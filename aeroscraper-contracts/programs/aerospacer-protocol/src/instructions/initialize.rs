@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use crate::state::*;
+use crate::error::AerospacerProtocolError;
 use anchor_spl::token::{Token, Mint, SetAuthority, set_authority, spl_token::instruction::AuthorityType};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -11,6 +13,7 @@ pub struct InitializeParams {
     pub fee_state_addr: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: InitializeParams)]
 pub struct Initialize<'info> {
@@ -26,7 +29,11 @@ pub struct Initialize<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = (MIN_STABLE_COIN_DECIMALS..=MAX_STABLE_COIN_DECIMALS).contains(&stable_coin_mint.decimals)
+            @ AerospacerProtocolError::UnsupportedStablecoinDecimals
+    )]
     pub stable_coin_mint: Account<'info, Mint>,
     
     pub token_program: Program<'info, Token>,
@@ -40,6 +47,7 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     state.admin = ctx.accounts.admin.key();
     state.stable_coin_addr = ctx.accounts.stable_coin_mint.key();
     state.stable_coin_code_id = params.stable_coin_code_id;
+    state.stable_coin_decimals = ctx.accounts.stable_coin_mint.decimals;
     state.oracle_helper_addr = params.oracle_helper_addr;
     state.oracle_state_addr = params.oracle_state_addr;
     state.fee_distributor_addr = params.fee_distributor_addr;
@@ -53,6 +61,24 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     state.p_factor = StateAccount::SCALE_FACTOR; // 10^18
     state.epoch = 0;
     
+    // Circuit breaker starts with no guardian and nothing paused
+    state.guardian = Pubkey::default();
+    state.paused = 0;
+    state.is_shutdown = false;
+    state.shutdown_timestamp = 0;
+    state.settled_denom_count = 0;
+    
+    // Roles start unassigned; the owner holds every role implicitly
+    state.risk_manager = Pubkey::default();
+    state.oracle_manager = Pubkey::default();
+    state.fee_manager = Pubkey::default();
+    state.pending_admin = Pubkey::default();
+    
+    // Timelock starts disabled; the owner can queue a SetTimelockDelay change to enable it
+    state.timelock_delay = 0;
+    state.next_change_id = 0;
+    state.version = StateAccount::VERSION;
+    
     // Move mint authority for the stable coin mint to the protocol PDA (protocol_stablecoin_vault)
     // This matches Injective's model where the protocol contract is the minter.
     let (protocol_stablecoin_vault_pda, _bump) = Pubkey::find_program_address(
@@ -78,7 +104,7 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     
     msg!("Aerospacer Protocol initialized successfully");
     msg!("Admin: {}", state.admin);
-    msg!("Stable Coin: {}", state.stable_coin_addr);
+    msg!("Stable Coin: {} ({} decimals)", state.stable_coin_addr, state.stable_coin_decimals);
     msg!("Oracle Helper: {}", state.oracle_helper_addr);
     msg!("Oracle State: {}", state.oracle_state_addr);
     msg!("Fee Distributor: {}", state.fee_distributor_addr);
@@ -88,5 +114,14 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     msg!("P factor initialized: {}", state.p_factor);
     msg!("Epoch initialized: {}", state.epoch);
     
+    emit_cpi!(ProtocolInitialized {
+        admin: state.admin,
+        stable_coin_mint: state.stable_coin_addr,
+        stable_coin_decimals: state.stable_coin_decimals,
+        minimum_collateral_ratio: state.minimum_collateral_ratio,
+        protocol_fee: state.protocol_fee,
+        params: ProtocolParams::from(&**state),
+    });
+    
     Ok(())
 } 
\ No newline at end of file
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Burn};
 use crate::state::*;
 use crate::error::*;
-use crate::oracle::{OracleContext, PriceCalculator};
+use crate::oracle::{extra_price_accounts, OracleContext, PriceCalculator, PricePurpose, PriceUse, TroveAction, Valuation};
 use crate::trove_management::distribute_liquidation_gains_to_stakers;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -11,6 +12,7 @@ pub struct LiquidateTroveParams {
     pub collateral_denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: LiquidateTroveParams)]
 pub struct LiquidateTrove<'info> {
@@ -87,13 +89,36 @@ pub struct LiquidateTrove<'info> {
 
     /// CHECK: Oracle state account - validated against state
     #[account(
-        mut,
         constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
+    
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: AccountInfo<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: AccountInfo<'info>,
 
     /// CHECK: Pyth price account for collateral price feed
     pub pyth_price_account: AccountInfo<'info>,
+    
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Oracle price history PDA for the collateral denom, needed if its price policy
+    /// uses a TWAP here - validated by the oracle program
+    pub price_history: Option<UncheckedAccount<'info>>,
 
     pub clock: Sysvar<'info, Clock>,
 
@@ -111,6 +136,23 @@ pub struct LiquidateTrove<'info> {
 }
 
 pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.total_collateral_amount.upgrade();
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.liquidity_threshold.upgrade();
+    ctx.accounts.stability_pool_snapshot.upgrade();
+    
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_LIQUIDATION),
+        AerospacerProtocolError::OperationPaused
+    );
+    require!(
+        !ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolShutdown
+    );
+    
     // Basic input checks
     require!(!params.collateral_denom.is_empty(), AerospacerProtocolError::InvalidAmount);
 
@@ -118,7 +160,14 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
     let oracle_ctx = OracleContext {
         oracle_program: ctx.accounts.oracle_program.clone(),
         oracle_state: ctx.accounts.oracle_state.clone(),
+        oracle_asset: ctx.accounts.oracle_asset.clone(),
+        oracle_event_authority: ctx.accounts.oracle_event_authority.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        extra_price_accounts: extra_price_accounts(&[
+            &ctx.accounts.secondary_price_account,
+            &ctx.accounts.tertiary_price_account,
+        ]),
+        price_history: ctx.accounts.price_history.as_ref().map(|a| a.to_account_info()),
         clock: ctx.accounts.clock.to_account_info(),
     };
 
@@ -133,16 +182,20 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
     require!(coll_info.denom == params.collateral_denom, AerospacerProtocolError::InvalidAmount);
 
     // Price validation
-    let price = oracle_ctx.get_price(&params.collateral_denom)?;
-    oracle_ctx.validate_price(&price)?;
+    let price = oracle_ctx.get_price_for(&params.collateral_denom, PricePurpose::Liquidation)?;
+    price.asset_state.check(TroveAction::Liquidate)?;
+    oracle_ctx.validate_price(&price, PriceUse::Live)?;
 
+    // Liquidation uses the plain oracle price
     let collateral_value = PriceCalculator::calculate_collateral_value(
         coll_info.amount,
-        price.price as u64,
+        price.normalized_price,
+        price.normalized_confidence,
         price.decimal,
+        Valuation::Spot,
     )?;
 
-    let current_icr = PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount)?;
+    let current_icr = PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount, ctx.accounts.state.stable_decimals())?;
     // Use micro-percent threshold (110% = 110_000_000)
     require!(current_icr < 110_000_000, AerospacerProtocolError::CollateralBelowMinimum);
 
@@ -175,7 +228,7 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
     
     if total_stake >= debt_amount {
         // PATH 1: Stability pool has sufficient funds - burn entire debt
-        msg!("Using stability pool liquidation path (sufficient funds)");
+        debug_msg!("Using stability pool liquidation path (sufficient funds)");
         
         let burn_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -202,8 +255,8 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
         )?;
     } else if total_stake > 0 {
         // PATH 2: Partial coverage - burn only covered portion, redistribute the rest
-        msg!("Using hybrid liquidation path (partial stability pool coverage)");
-        msg!("  Pool covers: {} of {} debt", total_stake, debt_amount);
+        debug_msg!("Using hybrid liquidation path (partial stability pool coverage)");
+        debug_msg!("  Pool covers: {} of {} debt", total_stake, debt_amount);
         
         let covered_debt = total_stake;
         let uncovered_debt = debt_amount.saturating_sub(total_stake);
@@ -250,7 +303,7 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
         )?;
     } else {
         // PATH 3: Stability pool is empty - NO BURN, redistribute to all active troves
-        msg!("Using redistribution liquidation path (stability pool empty)");
+        debug_msg!("Using redistribution liquidation path (stability pool empty)");
         use crate::trove_management::redistribute_debt_and_collateral;
         redistribute_debt_and_collateral(
             &mut ctx.accounts.total_collateral_amount,
@@ -268,6 +321,17 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
         collateral_amount
     );
 
+    emit_cpi!(TroveLiquidated {
+        owner: params.target_user,
+        liquidator: ctx.accounts.liquidator.key(),
+        collateral_denom: params.collateral_denom,
+        debt: debt_amount,
+        collateral: collateral_amount,
+        icr: current_icr,
+        price: price.normalized_price,
+        debt_absorbed_by_pool: debt_amount.min(total_stake),
+    });
+
     Ok(())
 }
 
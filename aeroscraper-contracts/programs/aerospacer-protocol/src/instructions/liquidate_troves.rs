@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, Mint, TokenAccount};
 use crate::state::*;
 use crate::error::*;
@@ -21,6 +22,7 @@ pub struct LiquidateTrovesParams {
     pub collateral_denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: LiquidateTrovesParams)]
 pub struct LiquidateTroves<'info> {
@@ -70,14 +72,37 @@ pub struct LiquidateTroves<'info> {
     
     /// CHECK: Oracle state account - validated against state
     #[account(
-        mut,
         constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
     
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: AccountInfo<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: AccountInfo<'info>,
+    
     /// CHECK: Pyth price account for collateral price feed
     pub pyth_price_account: AccountInfo<'info>,
     
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Oracle price history PDA for the collateral denom, needed if its price policy
+    /// uses a TWAP here - validated by the oracle program
+    pub price_history: Option<UncheckedAccount<'info>>,
+    
     /// Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
 
@@ -98,6 +123,20 @@ pub struct LiquidateTroves<'info> {
 }
 
 pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.total_collateral_amount.upgrade();
+    ctx.accounts.stability_pool_snapshot.upgrade();
+    
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_LIQUIDATION),
+        AerospacerProtocolError::OperationPaused
+    );
+    require!(
+        !ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolShutdown
+    );
+    
     // Validate input parameters
     require!(
         !params.liquidation_list.is_empty(),
@@ -126,7 +165,7 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
     msg!("Liquidation by: {}", ctx.accounts.liquidator.key());
     
     // Validate remaining accounts for each user
-    validate_remaining_accounts(&params.liquidation_list, &ctx.remaining_accounts, &params.collateral_denom)?;
+    validate_remaining_accounts(&params.liquidation_list, ctx.remaining_accounts, &params.collateral_denom)?;
     
     // Initialize StabilityPoolSnapshot if it's newly created
     let snapshot = &mut ctx.accounts.stability_pool_snapshot;
@@ -153,7 +192,14 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
     let oracle_ctx = OracleContext {
         oracle_program: ctx.accounts.oracle_program.clone(),
         oracle_state: ctx.accounts.oracle_state.clone(),
+        oracle_asset: ctx.accounts.oracle_asset.clone(),
+        oracle_event_authority: ctx.accounts.oracle_event_authority.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        extra_price_accounts: extra_price_accounts(&[
+            &ctx.accounts.secondary_price_account,
+            &ctx.accounts.tertiary_price_account,
+        ]),
+        price_history: ctx.accounts.price_history.as_ref().map(|a| a.to_account_info()),
         clock: ctx.accounts.clock.to_account_info(),
     };
 
@@ -162,7 +208,7 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
         &mut liquidation_ctx,
         &oracle_ctx,
         params.liquidation_list.clone(),
-        &ctx.remaining_accounts,
+        ctx.remaining_accounts,
         &mut ctx.accounts.stability_pool_snapshot,
     )?;
 
@@ -183,6 +229,14 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
         msg!("Collateral gained - {}: {}", denom, amount);
     }
 
+    emit_cpi!(TrovesLiquidated {
+        liquidator: ctx.accounts.liquidator.key(),
+        collateral_denom: params.collateral_denom,
+        liquidated_count: result.liquidated_count,
+        total_debt: result.total_debt_liquidated,
+        total_collateral: result.total_collateral_gained,
+    });
+
     Ok(())
 }
 
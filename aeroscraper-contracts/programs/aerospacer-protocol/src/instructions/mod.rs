@@ -13,7 +13,22 @@ pub mod unstake;
 pub mod withdraw_liquidation_gains;
 pub mod redeem;
 pub mod update_protocol_addresses;
+pub mod update_risk_params;
 pub mod transfer_stablecoin;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod set_paused;
+pub mod emergency_shutdown;
+pub mod settle_trove;
+pub mod withdraw_settled_collateral;
+pub mod redeem_settlement;
+pub mod queue_change;
+pub mod execute_change;
+pub mod cancel_change;
+pub mod migrate_state;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -46,4 +61,34 @@ pub use redeem::*;
 #[allow(ambiguous_glob_reexports)]
 pub use update_protocol_addresses::*;
 #[allow(ambiguous_glob_reexports)]
-pub use transfer_stablecoin::*; 
\ No newline at end of file
+pub use update_risk_params::*;
+#[allow(ambiguous_glob_reexports)]
+pub use transfer_stablecoin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use grant_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_role::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use accept_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_admin_transfer::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_paused::*;
+#[allow(ambiguous_glob_reexports)]
+pub use emergency_shutdown::*;
+#[allow(ambiguous_glob_reexports)]
+pub use settle_trove::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_settled_collateral::*;
+#[allow(ambiguous_glob_reexports)]
+pub use redeem_settlement::*;
+#[allow(ambiguous_glob_reexports)]
+pub use queue_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_state::*;
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, MintTo};
 use crate::state::*;
 use crate::error::*;
 use crate::account_management::*;
 use crate::oracle::*;
 use crate::trove_management::TroveManager;
-use crate::state::{MINIMUM_LOAN_AMOUNT, MINIMUM_COLLATERAL_AMOUNT};
+use crate::state::MINIMUM_COLLATERAL_AMOUNT;
 use crate::fees_integration::*;
 use crate::utils::*;
 
@@ -18,6 +19,7 @@ pub struct OpenTroveParams {
     pub collateral_amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: OpenTroveParams)]
 pub struct OpenTrove<'info> {
@@ -113,13 +115,39 @@ pub struct OpenTrove<'info> {
     /// CHECK: Our oracle program - validated against state in handler
     pub oracle_program: UncheckedAccount<'info>,
     
-    /// CHECK: Oracle state account - validated against state in handler
-    #[account(mut)]
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
     pub oracle_state: UncheckedAccount<'info>,
     
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: UncheckedAccount<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: UncheckedAccount<'info>,
+    
     /// CHECK: Pyth price account for collateral price feed
     pub pyth_price_account: UncheckedAccount<'info>,
     
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Oracle price history PDA for the collateral denom, needed if its price policy
+    /// uses a TWAP here - validated by the oracle program
+    pub price_history: Option<UncheckedAccount<'info>>,
+    
     /// CHECK: Clock sysvar - validated in handler if needed
     pub clock: UncheckedAccount<'info>,
     
@@ -127,6 +155,9 @@ pub struct OpenTrove<'info> {
     /// CHECK: Fees program - validated against state in handler
     pub fees_program: UncheckedAccount<'info>,
     
+    /// CHECK: Event authority PDA of the fees program - validated by the fees program
+    pub fees_event_authority: UncheckedAccount<'info>,
+    
     /// CHECK: Fees state account - validated against state in handler
     #[account(mut)]
     pub fees_state: UncheckedAccount<'info>,
@@ -148,13 +179,25 @@ pub struct OpenTrove<'info> {
 }
 
 pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
-    // Validate oracle accounts
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.liquidity_threshold.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.total_collateral_amount.upgrade();
+    
+    // Circuit breaker
     require!(
-        ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
-        AerospacerProtocolError::Unauthorized
+        !ctx.accounts.state.is_paused(PAUSE_MINTING),
+        AerospacerProtocolError::OperationPaused
     );
     require!(
-        ctx.accounts.oracle_state.key() == ctx.accounts.state.oracle_state_addr,
+        !ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolShutdown
+    );
+    
+    // Validate oracle accounts
+    require!(
+        ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
         AerospacerProtocolError::Unauthorized
     );
     
@@ -175,7 +218,7 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     );
     
     require!(
-        params.loan_amount >= MINIMUM_LOAN_AMOUNT,
+        params.loan_amount >= ctx.accounts.state.minimum_loan_amount(),
         AerospacerProtocolError::LoanAmountBelowMinimum
     );
     
@@ -222,10 +265,11 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     ctx.accounts.liquidity_threshold.ratio = 0; // Will be set below
     
     // Calculate opening fee BEFORE trove operations
-    let fee_amount = calculate_protocol_fee(params.loan_amount, ctx.accounts.state.protocol_fee)?;
+    let protocol_fee = checked_protocol_fee(ctx.accounts.state.protocol_fee, &ctx.accounts.fees_state)?;
+    let fee_amount = calculate_protocol_fee(params.loan_amount, protocol_fee)?;
     let net_loan_amount = params.loan_amount.saturating_sub(fee_amount);
     
-    msg!("Opening fee: {} aUSD ({}%)", fee_amount, ctx.accounts.state.protocol_fee);
+    msg!("Opening fee: {} aUSD ({}%)", fee_amount, protocol_fee);
     msg!("Net loan amount: {} aUSD", net_loan_amount);
     
     // Create contexts in scoped block to reduce stack usage
@@ -250,7 +294,14 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
         let oracle_ctx = OracleContext {
             oracle_program: ctx.accounts.oracle_program.to_account_info(),
             oracle_state: ctx.accounts.oracle_state.to_account_info(),
+            oracle_asset: ctx.accounts.oracle_asset.to_account_info(),
+            oracle_event_authority: ctx.accounts.oracle_event_authority.to_account_info(),
             pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
+            extra_price_accounts: extra_price_accounts(&[
+                &ctx.accounts.secondary_price_account,
+                &ctx.accounts.tertiary_price_account,
+            ]),
+            price_history: ctx.accounts.price_history.as_ref().map(|a| a.to_account_info()),
             clock: ctx.accounts.clock.to_account_info(),
         };
         
@@ -279,7 +330,7 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
         
         msg!("Validating ICR ordering with {} neighbor account(s)", ctx.remaining_accounts.len());
         
-        let prev_icr = if ctx.remaining_accounts.len() >= 1 {
+        let prev_icr = if !ctx.remaining_accounts.is_empty() {
             // First account is previous neighbor's LiquidityThreshold
             let prev_lt = &ctx.remaining_accounts[0];
             let prev_data = prev_lt.try_borrow_data()?;
@@ -376,8 +427,9 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     if fee_amount > 0 {
         let _net_amount = process_protocol_fee(
             params.loan_amount,
-            ctx.accounts.state.protocol_fee,
+            protocol_fee,
             ctx.accounts.fees_program.to_account_info(),
+            ctx.accounts.fees_event_authority.to_account_info(),
             ctx.accounts.user.to_account_info(),
             ctx.accounts.fees_state.to_account_info(),
             ctx.accounts.user_stablecoin_account.to_account_info(),
@@ -398,5 +450,16 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     msg!("Collateral: {} {}", params.collateral_amount, params.collateral_denom);
     msg!("ICR: {}", result.new_icr);
     
+    emit_cpi!(TroveOpened {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom,
+        collateral_amount: params.collateral_amount,
+        loan_amount: params.loan_amount,
+        fee_amount,
+        debt: result.new_debt_amount,
+        icr: result.new_icr,
+        price: result.price,
+    });
+    
     Ok(())
 }
\ No newline at end of file
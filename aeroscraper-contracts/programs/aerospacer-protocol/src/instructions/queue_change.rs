@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, QueuedChange, TimelockedChange, Versioned, MAX_TIMELOCK_DELAY};
+use crate::error::AerospacerProtocolError;
+use crate::instructions::{update_protocol_addresses, update_risk_params};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueueChangeParams {
+    pub change: TimelockedChange,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: QueueChangeParams)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, StateAccount>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = QueuedChange::space(&params.change),
+        seeds = [b"timelock", state.next_change_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+    
+    /// Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+    let proposer = ctx.accounts.proposer.key();
+    let state = &mut ctx.accounts.state;
+    
+    // Same role requirements as the direct instructions
+    match &params.change {
+        TimelockedChange::UpdateProtocolAddresses(change) => {
+            update_protocol_addresses::authorize(state, &proposer, change)?;
+        }
+        TimelockedChange::SetTimelockDelay(delay) => {
+            require!(
+                state.admin == proposer,
+                AerospacerProtocolError::Unauthorized
+            );
+            require!(
+                (0..=MAX_TIMELOCK_DELAY).contains(delay),
+                AerospacerProtocolError::InvalidTimelockDelay
+            );
+        }
+        TimelockedChange::UpdateRiskParams(change) => {
+            update_risk_params::authorize(state, &proposer, change)?;
+        }
+    }
+    
+    let now = ctx.accounts.clock.unix_timestamp;
+    let queued_change = &mut ctx.accounts.queued_change;
+    queued_change.id = state.next_change_id;
+    queued_change.proposer = proposer;
+    queued_change.queued_at = now;
+    queued_change.eta = now
+        .checked_add(state.timelock_delay)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    queued_change.set_change(&params.change)?;
+    queued_change.version = QueuedChange::VERSION;
+    
+    state.next_change_id = state.next_change_id
+        .checked_add(1)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Change {} queued by {}, executable at {}", queued_change.id, proposer, queued_change.eta);
+    
+    emit_cpi!(ChangeQueued {
+        id: queued_change.id,
+        proposer,
+        queued_at: queued_change.queued_at,
+        eta: queued_change.eta,
+    });
+    
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, Burn};
 use crate::state::*;
 use crate::error::*;
 use crate::fees_integration::*;
+use crate::oracle::{get_asset_state_via_cpi, TroveAction};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RedeemParams {
@@ -11,6 +13,7 @@ pub struct RedeemParams {
     // NOTE: prev_node_id and next_node_id removed - using off-chain sorted list architecture
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: RedeemParams)]
 pub struct Redeem<'info> {
@@ -101,6 +104,9 @@ pub struct Redeem<'info> {
         constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
+    
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program
+    pub oracle_asset: AccountInfo<'info>,
 
     // Fee distribution accounts
     /// CHECK: Fees program - validated against state
@@ -109,6 +115,9 @@ pub struct Redeem<'info> {
     )]
     pub fees_program: AccountInfo<'info>,
     
+    /// CHECK: Event authority PDA of the fees program - validated by the fees program
+    pub fees_event_authority: AccountInfo<'info>,
+    
     /// CHECK: Fees state account - validated against state
     #[account(
         mut,
@@ -132,6 +141,21 @@ pub struct Redeem<'info> {
 }
 
 pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.liquidity_threshold.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_REDEMPTION),
+        AerospacerProtocolError::OperationPaused
+    );
+    require!(
+        !ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolShutdown
+    );
+    
     // PRODUCTION VALIDATION: Input parameter checks
     require!(
         params.amount > 0,
@@ -139,7 +163,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
     );
     
     require!(
-        params.amount >= MINIMUM_LOAN_AMOUNT,
+        params.amount >= ctx.accounts.state.minimum_loan_amount(),
         AerospacerProtocolError::InvalidAmount
     );
     
@@ -148,8 +172,15 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         AerospacerProtocolError::InvalidAmount
     );
     
+    // Lifecycle gate: redemption stays open until the asset is liquidation-only
+    get_asset_state_via_cpi(
+        params.collateral_denom.clone(),
+        ctx.accounts.oracle_program.clone(),
+        ctx.accounts.oracle_asset.clone(),
+    )?.check(TroveAction::Redeem)?;
+    
     // Store protocol fee before creating mutable borrow
-    let protocol_fee = ctx.accounts.state.protocol_fee;
+    let protocol_fee = checked_protocol_fee(ctx.accounts.state.protocol_fee, &ctx.accounts.fees_state)?;
     
     let state = &mut ctx.accounts.state;
     
@@ -174,6 +205,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         params.amount,
         protocol_fee,
         ctx.accounts.fees_program.to_account_info(),
+        ctx.accounts.fees_event_authority.to_account_info(),
         ctx.accounts.user.to_account_info(),
         ctx.accounts.fees_state.to_account_info(),
         ctx.accounts.user_stablecoin_account.to_account_info(),
@@ -184,8 +216,8 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
     )?;
     
     let fee_amount = params.amount.saturating_sub(net_redemption_amount);
-    msg!("Redemption fee: {} aUSD ({}%)", fee_amount, protocol_fee);
-    msg!("Net redemption amount: {} aUSD", net_redemption_amount);
+    debug_msg!("Redemption fee: {} aUSD ({}%)", fee_amount, protocol_fee);
+    debug_msg!("Net redemption amount: {} aUSD", net_redemption_amount);
     
     // Transfer NET redemption amount from user to protocol (after fee deduction)
     let transfer_ctx = CpiContext::new(
@@ -227,12 +259,12 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
     
     // Validate remaining_accounts structure (4 accounts per trove)
     require!(
-        ctx.remaining_accounts.len() % 4 == 0,
+        ctx.remaining_accounts.len().is_multiple_of(4),
         AerospacerProtocolError::InvalidList
     );
     
     let num_troves = ctx.remaining_accounts.len() / 4;
-    msg!("Processing redemption across {} pre-sorted troves", num_troves);
+    debug_msg!("Processing redemption across {} pre-sorted troves", num_troves);
     
     // SECURITY: Verify total_collateral_amount PDA is authentic
     let (expected_total_coll_pda, _bump) = Pubkey::find_program_address(
@@ -295,8 +327,10 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         
         use crate::trove_management::apply_pending_rewards;
         apply_pending_rewards(&mut user_debt, &mut user_collateral, &total_collateral)?;
+        user_debt.upgrade();
+        user_collateral.upgrade();
         
-        // Serialize updated debt and collateral after applying rewards
+        // Serialize updated debt and collateral after applying rewards (and any layout upgrade)
         let mut debt_data_after = debt_account.try_borrow_mut_data()?;
         user_debt.try_serialize(&mut &mut debt_data_after[..])?;
         drop(debt_data_after);
@@ -311,7 +345,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         
         // Skip troves with zero debt (already fully redeemed or liquidated)
         if debt_amount == 0 {
-            msg!("Trove {} has zero debt, skipping", trove_user);
+            debug_msg!("Trove {} has zero debt, skipping", trove_user);
             continue;
         }
         
@@ -344,7 +378,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         
         // Skip if this trove doesn't have the requested collateral type
         if collateral_denom != params.collateral_denom {
-            msg!("Trove {} has {} collateral, not {}, skipping", trove_user, collateral_denom, params.collateral_denom);
+            debug_msg!("Trove {} has {} collateral, not {}, skipping", trove_user, collateral_denom, params.collateral_denom);
             continue;
         }
         
@@ -392,7 +426,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         // CRITICAL: Skip troves where collateral payout would be zero
         // Prevents users from burning stablecoins without receiving collateral
         if collateral_to_send == 0 && redeem_from_trove > 0 {
-            msg!("Trove {} would yield zero collateral for {} debt redemption (undercollateralized), skipping", trove_user, redeem_from_trove);
+            debug_msg!("Trove {} would yield zero collateral for {} debt redemption (undercollateralized), skipping", trove_user, redeem_from_trove);
             continue;
         }
         
@@ -432,7 +466,7 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
             drop(total_coll_data);
             
             total_collateral_sent = total_collateral_sent.saturating_add(collateral_to_send);
-            msg!("Transferred {} {} to user from trove {}", collateral_to_send, params.collateral_denom, trove_user);
+            debug_msg!("Transferred {} {} to user from trove {}", collateral_to_send, params.collateral_denom, trove_user);
         }
         
         // Update trove debt
@@ -446,11 +480,20 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         drop(debt_data_mut);
         
         if new_debt == 0 {
-            msg!("Trove fully redeemed and zeroed: {}", trove_user);
+            debug_msg!("Trove fully redeemed and zeroed: {}", trove_user);
         } else {
-            msg!("Trove partially redeemed: user={}, new_debt={}", trove_user, new_debt);
+            debug_msg!("Trove partially redeemed: user={}, new_debt={}", trove_user, new_debt);
         }
         
+        emit_cpi!(TroveRedeemed {
+            owner: trove_user,
+            redeemer: ctx.accounts.user.key(),
+            collateral_denom: params.collateral_denom.clone(),
+            debt_redeemed: redeem_from_trove,
+            collateral_redeemed: collateral_to_send,
+            debt_after: new_debt,
+        });
+        
         troves_redeemed += 1;
         remaining_amount = remaining_amount.saturating_sub(redeem_from_trove);
     }
@@ -470,12 +513,21 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
     msg!("Redeemed successfully");
     msg!("User: {}", ctx.accounts.user.key());
     msg!("Gross amount: {} aUSD", params.amount);
-    msg!("Fee: {} aUSD ({}%)", fee_amount, ctx.accounts.state.protocol_fee);
+    msg!("Fee: {} aUSD ({}%)", fee_amount, protocol_fee);
     msg!("Net redemption: {} aUSD", net_redemption_amount);
     msg!("Collateral sent: {} {}", total_collateral_sent, params.collateral_denom);
     msg!("Troves redeemed: {}", troves_redeemed);
     msg!("Remaining amount: {} aUSD", remaining_amount);
 
+    emit_cpi!(Redemption {
+        redeemer: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom,
+        amount: params.amount,
+        fee_amount,
+        collateral_received: total_collateral_sent,
+        troves_redeemed,
+    });
+
     Ok(())
 }
 
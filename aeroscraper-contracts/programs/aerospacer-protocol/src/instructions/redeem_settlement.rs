@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::settlement_payout;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RedeemSettlementParams {
+    pub amount: u64, // aUSD to burn
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: RedeemSettlementParams)]
+pub struct RedeemSettlement<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Box<Account<'info, StateAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_stablecoin_account.owner == user.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Box<Account<'info, Mint>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Burn aUSD for a pro-rata share of every denom's settlement backing.
+// remaining_accounts holds one triple per frozen denom:
+//   [CollateralSettlement PDA (mut), protocol_collateral_vault PDA (mut), user's token account for that denom (mut)]
+// Every frozen denom must be present so no holder can be short-changed on part of the basket.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RedeemSettlement<'info>>, params: RedeemSettlementParams) -> Result<()> {
+    let state = &ctx.accounts.state;
+    
+    require!(state.is_shutdown, AerospacerProtocolError::ProtocolNotShutdown);
+    require!(
+        ctx.accounts.clock.unix_timestamp >= state.shutdown_timestamp + SETTLEMENT_GRACE_PERIOD,
+        AerospacerProtocolError::SettlementNotOpen
+    );
+    require!(params.amount > 0, AerospacerProtocolError::InvalidAmount);
+    require!(
+        ctx.accounts.user_stablecoin_account.amount >= params.amount,
+        AerospacerProtocolError::InvalidAmount
+    );
+    
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(3),
+        AerospacerProtocolError::InvalidList
+    );
+    let num_denoms = ctx.remaining_accounts.len() / 3;
+    require!(
+        num_denoms == state.settled_denom_count as usize,
+        AerospacerProtocolError::InvalidList
+    );
+    
+    // Share is measured against the outstanding aUSD supply before this burn
+    let outstanding_supply = ctx.accounts.stable_coin_mint.supply;
+    require!(outstanding_supply > 0, AerospacerProtocolError::DivideByZeroError);
+    
+    let mut seen_denoms: Vec<String> = Vec::with_capacity(num_denoms);
+    let mut payouts: Vec<(String, u64)> = Vec::with_capacity(num_denoms);
+    
+    for i in 0..num_denoms {
+        let settlement_account = &ctx.remaining_accounts[i * 3];
+        let vault_account = &ctx.remaining_accounts[i * 3 + 1];
+        let user_token_account = &ctx.remaining_accounts[i * 3 + 2];
+        
+        // SECURITY: Settlement must be a genuine program-owned PDA
+        require!(
+            settlement_account.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let settlement_data = settlement_account.try_borrow_data()?;
+        let mut settlement = CollateralSettlement::try_deserialize(&mut &settlement_data[..])?;
+        drop(settlement_data);
+        
+        let (expected_settlement, _) = Pubkey::find_program_address(
+            &CollateralSettlement::seeds(&settlement.denom),
+            &crate::ID,
+        );
+        require!(
+            expected_settlement == settlement_account.key(),
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            !seen_denoms.contains(&settlement.denom),
+            AerospacerProtocolError::InvalidList
+        );
+        seen_denoms.push(settlement.denom.clone());
+        
+        // SECURITY: Vault must be the collateral vault PDA for this denom
+        let (expected_vault, vault_bump) = Pubkey::find_program_address(
+            &[b"protocol_collateral_vault", settlement.denom.as_bytes()],
+            &crate::ID,
+        );
+        require!(
+            expected_vault == vault_account.key(),
+            AerospacerProtocolError::InvalidList
+        );
+        
+        let vault_data = vault_account.try_borrow_data()?;
+        let vault = TokenAccount::try_deserialize(&mut &vault_data[..])?;
+        drop(vault_data);
+        
+        require!(
+            user_token_account.owner == &anchor_spl::token::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let user_token_data = user_token_account.try_borrow_data()?;
+        let user_token = TokenAccount::try_deserialize(&mut &user_token_data[..])?;
+        drop(user_token_data);
+        require!(user_token.owner == ctx.accounts.user.key(), AerospacerProtocolError::Unauthorized);
+        require!(user_token.mint == vault.mint, AerospacerProtocolError::InvalidMint);
+        
+        // Pro-rata share of this denom's backing
+        let payout = settlement_payout(settlement.backing_collateral, params.amount, outstanding_supply)?;
+        
+        if payout == 0 {
+            continue;
+        }
+        
+        settlement.backing_collateral = settlement.backing_collateral
+            .checked_sub(payout)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+        settlement.upgrade();
+        let mut settlement_data = settlement_account.try_borrow_mut_data()?;
+        settlement.try_serialize(&mut &mut settlement_data[..])?;
+        drop(settlement_data);
+        
+        let vault_seeds = &[
+            b"protocol_collateral_vault".as_ref(),
+            settlement.denom.as_bytes(),
+            &[vault_bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault_account.clone(),
+                to: user_token_account.clone(),
+                authority: vault_account.clone(),
+            },
+            signer_seeds,
+        );
+        anchor_spl::token::transfer(transfer_ctx, payout)?;
+        
+        msg!("Settlement payout: {} {}", payout, settlement.denom);
+        payouts.push((settlement.denom, payout));
+    }
+    
+    // Burn the redeemed aUSD
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.user_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, params.amount)?;
+    
+    msg!("Burned {} aUSD for settlement basket (supply before burn: {})", params.amount, outstanding_supply);
+    
+    emit_cpi!(SettlementRedeemed {
+        redeemer: ctx.accounts.user.key(),
+        amount: params.amount,
+        outstanding_supply,
+        payouts,
+    });
+    
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Burn};
 use crate::state::*;
 use crate::error::*;
@@ -14,6 +15,7 @@ pub struct RepayLoanParams {
     pub next_node_id: Option<Pubkey>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: RepayLoanParams)]
 pub struct RepayLoan<'info> {
@@ -87,13 +89,35 @@ pub struct RepayLoan<'info> {
     /// CHECK: Our oracle program - validated against state in handler
     pub oracle_program: UncheckedAccount<'info>,
     
-    /// CHECK: Oracle state account - validated against state in handler
-    #[account(mut)]
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
     pub oracle_state: UncheckedAccount<'info>,
     
+    /// CHECK: Oracle asset config PDA for the collateral denom - validated by the oracle program,
+    /// which records the price read on it as the asset's last good price
+    #[account(mut)]
+    pub oracle_asset: UncheckedAccount<'info>,
+    
+    /// CHECK: The oracle's event authority PDA; the oracle emits PriceRecorded through it
+    /// when it records the read on oracle_asset
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = oracle_program.key()
+    )]
+    pub oracle_event_authority: UncheckedAccount<'info>,
+    
     /// CHECK: Pyth price account for collateral price feed
     pub pyth_price_account: UncheckedAccount<'info>,
     
+    /// CHECK: Price account of the collateral's second oracle source, if it has one
+    pub secondary_price_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account of the collateral's third oracle source, if it has one
+    pub tertiary_price_account: Option<UncheckedAccount<'info>>,
+    
     /// CHECK: Clock sysvar - validated in handler if needed
     pub clock: UncheckedAccount<'info>,
     
@@ -102,15 +126,17 @@ pub struct RepayLoan<'info> {
 }
 
 pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.liquidity_threshold.upgrade();
+    ctx.accounts.total_collateral_amount.upgrade();
+    
     // Validate oracle accounts
     require!(
         ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
         AerospacerProtocolError::Unauthorized
     );
-    require!(
-        ctx.accounts.oracle_state.key() == ctx.accounts.state.oracle_state_addr,
-        AerospacerProtocolError::Unauthorized
-    );
     
     // Validate input parameters
     require!(
@@ -141,6 +167,13 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
         AerospacerProtocolError::InvalidAmount
     );
     
+    // Trove position before the operation, for the TroveAdjusted event
+    let before = TroveSnapshot {
+        debt: ctx.accounts.user_debt_amount.amount,
+        collateral: ctx.accounts.user_collateral_amount.amount,
+        icr: ctx.accounts.liquidity_threshold.ratio,
+    };
+    
     // Create contexts in scoped block to reduce stack usage
     let result = {
         let mut trove_ctx = TroveContext {
@@ -162,7 +195,14 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
         let oracle_ctx = OracleContext {
             oracle_program: ctx.accounts.oracle_program.to_account_info(),
             oracle_state: ctx.accounts.oracle_state.to_account_info(),
+            oracle_asset: ctx.accounts.oracle_asset.to_account_info(),
+            oracle_event_authority: ctx.accounts.oracle_event_authority.to_account_info(),
             pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
+            extra_price_accounts: extra_price_accounts(&[
+                &ctx.accounts.secondary_price_account,
+                &ctx.accounts.tertiary_price_account,
+            ]),
+            price_history: None,
             clock: ctx.accounts.clock.to_account_info(),
         };
         
@@ -190,7 +230,7 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
         
         msg!("Validating ICR ordering with {} neighbor account(s)", ctx.remaining_accounts.len());
         
-        let prev_icr = if ctx.remaining_accounts.len() >= 1 {
+        let prev_icr = if !ctx.remaining_accounts.is_empty() {
             let prev_lt = &ctx.remaining_accounts[0];
             let prev_data = prev_lt.try_borrow_data()?;
             let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
@@ -259,5 +299,20 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
     msg!("New ICR: {}", result.new_icr);
     msg!("Collateral amount: {}", result.new_collateral_amount);
     
+    emit_cpi!(TroveAdjusted {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom.clone(),
+        adjustment: TroveAdjustment::Repay,
+        amount: params.amount,
+        fee_amount: 0,
+        before,
+        after: TroveSnapshot {
+            debt: result.new_debt_amount,
+            collateral: result.new_collateral_amount,
+            icr: result.new_icr,
+        },
+        price: result.price,
+    });
+    
     Ok(())
 }
\ No newline at end of file
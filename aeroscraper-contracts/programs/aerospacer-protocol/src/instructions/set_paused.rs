@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, PAUSE_ALL, Role};
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPausedParams {
+    pub paused: u8, // Full replacement bitmask of PAUSE_* flags
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Guardian, &authority.key()) @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: Account<'info, StateAccount>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    require!(
+        params.paused & !PAUSE_ALL == 0,
+        AerospacerProtocolError::InvalidPauseFlags
+    );
+    
+    // The guardian can only halt more operations; lifting a pause needs the owner
+    if ctx.accounts.authority.key() != state.admin {
+        require!(
+            params.paused & state.paused == state.paused,
+            AerospacerProtocolError::Unauthorized
+        );
+    }
+    
+    msg!("Pause flags updated: {:#010b} -> {:#010b}", state.paused, params.paused);
+    
+    emit_cpi!(PauseChanged {
+        authority: ctx.accounts.authority.key(),
+        paused_before: state.paused,
+        paused_after: params.paused,
+    });
+    
+    state.paused = params.paused;
+    
+    Ok(())
+}
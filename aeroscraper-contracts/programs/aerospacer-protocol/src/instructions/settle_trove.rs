@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::{apply_pending_rewards, settle_trove_at_frozen_price};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SettleTroveParams {
+    pub target_user: Pubkey,
+    pub collateral_denom: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SettleTroveParams)]
+pub struct SettleTrove<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Box<Account<'info, StateAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", params.target_user.as_ref()],
+        bump,
+        constraint = user_debt_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", params.target_user.as_ref(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
+
+    #[account(
+        mut,
+        seeds = [b"collateral_settlement", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_settlement: Box<Account<'info, CollateralSettlement>>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: Box<Account<'info, TotalCollateralAmount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: anyone (typically a keeper) can settle any trove after shutdown so
+// that the aUSD backing is complete before the grace period ends. Settlement closes when
+// redemptions open, so every redeemer is paid against the same backing
+pub fn handler(ctx: Context<SettleTrove>, _params: SettleTroveParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.collateral_settlement.upgrade();
+    ctx.accounts.total_collateral_amount.upgrade();
+    
+    require!(
+        ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolNotShutdown
+    );
+    require!(
+        ctx.accounts.clock.unix_timestamp < ctx.accounts.state.shutdown_timestamp + SETTLEMENT_GRACE_PERIOD,
+        AerospacerProtocolError::SettlementWindowClosed
+    );
+    
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &ctx.accounts.total_collateral_amount,
+    )?;
+    
+    require!(
+        ctx.accounts.user_debt_amount.amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
+    );
+    
+    let debt_settled = ctx.accounts.user_debt_amount.amount;
+    let collateral_reserved = settle_trove_at_frozen_price(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &mut ctx.accounts.collateral_settlement,
+        &mut ctx.accounts.total_collateral_amount,
+        ctx.accounts.state.stable_decimals(),
+    )?;
+    
+    msg!("Trove settled by {}", ctx.accounts.caller.key());
+    msg!("Remaining owner collateral: {}", ctx.accounts.user_collateral_amount.amount);
+    msg!("Total aUSD backing for {}: {}", ctx.accounts.collateral_settlement.denom, ctx.accounts.collateral_settlement.backing_collateral);
+    
+    emit_cpi!(TroveSettled {
+        owner: ctx.accounts.user_debt_amount.owner,
+        collateral_denom: ctx.accounts.collateral_settlement.denom.clone(),
+        debt_settled,
+        collateral_reserved,
+        collateral_remaining: ctx.accounts.user_collateral_amount.amount,
+    });
+    
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::utils::*;
@@ -9,6 +10,7 @@ pub struct StakeParams {
     pub amount: u64, // Equivalent to Uint256
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: StakeParams)]
 pub struct Stake<'info> {
@@ -57,6 +59,15 @@ pub struct Stake<'info> {
 
 
 pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_stake_amount.upgrade();
+    
+    // Circuit breaker
+    require!(
+        !ctx.accounts.state.is_paused(PAUSE_STAKING),
+        AerospacerProtocolError::OperationPaused
+    );
+    
     // Validate input parameters
     require!(
         params.amount > 0,
@@ -64,7 +75,7 @@ pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
     );
     
     require!(
-        params.amount >= MINIMUM_LOAN_AMOUNT, // Use same minimum as loans
+        params.amount >= ctx.accounts.state.minimum_loan_amount(), // Use same minimum as loans
         AerospacerProtocolError::InvalidAmount
     );
     
@@ -120,6 +131,7 @@ pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
     user_stake_amount.last_update_block = Clock::get()?.slot;
 
     // Update state
+    let total_stake_before = state.total_stake_amount;
     state.total_stake_amount = safe_add(state.total_stake_amount, params.amount)?;
 
     msg!("Staked successfully (snapshot captured)");
@@ -130,5 +142,13 @@ pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
     msg!("P snapshot: {}", user_stake_amount.p_snapshot);
     msg!("Epoch snapshot: {}", user_stake_amount.epoch_snapshot);
 
+    emit_cpi!(StakeChanged {
+        owner: ctx.accounts.user.key(),
+        deposit_before: current_deposit,
+        deposit_after: user_stake_amount.amount,
+        total_stake_before,
+        total_stake_after: state.total_stake_amount,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 use crate::state::StateAccount;
 
@@ -7,6 +8,7 @@ pub struct TransferStablecoinParams {
     pub amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct TransferStablecoin<'info> {
     #[account(mut)]
@@ -47,5 +49,12 @@ pub fn handler(ctx: Context<TransferStablecoin>, params: TransferStablecoinParam
     transfer(transfer_ctx, params.amount)?;
     
     msg!("Transferred {} stablecoins", params.amount);
+    
+    emit_cpi!(StablecoinTransferred {
+        from: ctx.accounts.from.key(),
+        to: ctx.accounts.to_account.owner,
+        amount: params.amount,
+    });
+    
     Ok(())
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::utils::*;
@@ -9,6 +10,7 @@ pub struct UnstakeParams {
     pub amount: u64, // Equivalent to Uint256
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: UnstakeParams)]
 pub struct Unstake<'info> {
@@ -52,6 +54,9 @@ pub struct Unstake<'info> {
 
 
 pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_stake_amount.upgrade();
+    
     // Validate input parameters
     require!(
         params.amount > 0,
@@ -79,7 +84,7 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
     let is_full_withdrawal = params.amount == compounded_stake;
     if !is_full_withdrawal {
         require!(
-            params.amount >= MINIMUM_LOAN_AMOUNT,
+            params.amount >= state.minimum_loan_amount(),
             AerospacerProtocolError::InvalidAmount
         );
     }
@@ -139,6 +144,7 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
     }
 
     // Update state
+    let total_stake_before = state.total_stake_amount;
     state.total_stake_amount = safe_sub(state.total_stake_amount, params.amount)?;
 
     msg!("Unstaked successfully (compounded stake calculated)");
@@ -148,5 +154,13 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
     msg!("Remaining deposit: {} aUSD", user_stake_amount.amount);
     msg!("Total protocol stake: {} aUSD", state.total_stake_amount);
 
+    emit_cpi!(StakeChanged {
+        owner: ctx.accounts.user.key(),
+        deposit_before: compounded_stake,
+        deposit_after: remaining_compounded,
+        total_stake_before,
+        total_stake_after: state.total_stake_amount,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::StateAccount;
+use crate::events::*;
+use crate::state::{StateAccount, Role};
 use crate::error::AerospacerProtocolError;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UpdateProtocolAddressesParams {
     pub oracle_helper_addr: Option<Pubkey>,
     pub oracle_state_addr: Option<Pubkey>,
@@ -10,6 +11,7 @@ pub struct UpdateProtocolAddressesParams {
     pub fee_state_addr: Option<Pubkey>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateProtocolAddresses<'info> {
     #[account(mut)]
@@ -18,15 +20,51 @@ pub struct UpdateProtocolAddresses<'info> {
     #[account(
         mut,
         seeds = [b"state"],
-        bump,
-        constraint = state.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+        bump
     )]
     pub state: Account<'info, StateAccount>,
 }
 
 pub fn handler(ctx: Context<UpdateProtocolAddresses>, params: UpdateProtocolAddressesParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerProtocolError::TimelockActive
+    );
     
+    authorize(&ctx.accounts.state, &ctx.accounts.admin.key(), &params)?;
+    
+    let before = ProtocolParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state, &params)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.admin.key(),
+        before,
+        after: ProtocolParams::from(&*ctx.accounts.state),
+    });
+    
+    Ok(())
+}
+
+// Oracle addresses belong to the oracle manager, fee addresses to the fee manager
+pub fn authorize(state: &StateAccount, signer: &Pubkey, params: &UpdateProtocolAddressesParams) -> Result<()> {
+    if params.oracle_helper_addr.is_some() || params.oracle_state_addr.is_some() {
+        require!(
+            state.has_role(Role::OracleManager, signer),
+            AerospacerProtocolError::Unauthorized
+        );
+    }
+    if params.fee_distributor_addr.is_some() || params.fee_state_addr.is_some() {
+        require!(
+            state.has_role(Role::FeeManager, signer),
+            AerospacerProtocolError::Unauthorized
+        );
+    }
+    
+    Ok(())
+}
+
+pub fn apply(state: &mut StateAccount, params: &UpdateProtocolAddressesParams) -> Result<()> {
     if let Some(addr) = params.oracle_helper_addr {
         require!(
             addr != Pubkey::default(),
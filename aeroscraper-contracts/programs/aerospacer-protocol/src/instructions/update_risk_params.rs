@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::{StateAccount, Role};
+use crate::error::AerospacerProtocolError;
+use crate::utils::get_liquidation_threshold;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateRiskParamsParams {
+    pub minimum_collateral_ratio: Option<u64>, // Micro-percent (115_000_000 = 115%)
+    pub protocol_fee: Option<u8>,              // Percent
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRiskParams<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, StateAccount>,
+}
+
+pub fn handler(ctx: Context<UpdateRiskParams>, params: UpdateRiskParamsParams) -> Result<()> {
+    // Once a delay is configured the change has to go through queue_change
+    require!(
+        ctx.accounts.state.timelock_delay == 0,
+        AerospacerProtocolError::TimelockActive
+    );
+    
+    authorize(&ctx.accounts.state, &ctx.accounts.authority.key(), &params)?;
+    
+    let before = ProtocolParams::from(&*ctx.accounts.state);
+    apply(&mut ctx.accounts.state, &params)?;
+    
+    emit_cpi!(ParamsChanged {
+        authority: ctx.accounts.authority.key(),
+        before,
+        after: ProtocolParams::from(&*ctx.accounts.state),
+    });
+    
+    Ok(())
+}
+
+// Risk parameters belong to the risk manager; values are checked up front so a bad
+// change cannot sit in the timelock
+pub fn authorize(state: &StateAccount, signer: &Pubkey, params: &UpdateRiskParamsParams) -> Result<()> {
+    require!(
+        state.has_role(Role::RiskManager, signer),
+        AerospacerProtocolError::Unauthorized
+    );
+    require!(
+        params.minimum_collateral_ratio.is_some() || params.protocol_fee.is_some(),
+        AerospacerProtocolError::InvalidAmount
+    );
+    if let Some(ratio) = params.minimum_collateral_ratio {
+        // Troves must be able to open above the liquidation line
+        require!(
+            ratio > get_liquidation_threshold()?,
+            AerospacerProtocolError::InvalidCollateralRatio
+        );
+    }
+    if let Some(fee) = params.protocol_fee {
+        require!(
+            fee <= 100,
+            AerospacerProtocolError::InvalidAmount
+        );
+    }
+    
+    Ok(())
+}
+
+pub fn apply(state: &mut StateAccount, params: &UpdateRiskParamsParams) -> Result<()> {
+    if let Some(ratio) = params.minimum_collateral_ratio {
+        state.minimum_collateral_ratio = ratio;
+        msg!("Minimum collateral ratio updated: {}", ratio);
+    }
+    if let Some(fee) = params.protocol_fee {
+        state.protocol_fee = fee;
+        msg!("Protocol fee updated: {}%", fee);
+    }
+    
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::events::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
 use crate::state::*;
 use crate::utils::*;
@@ -9,6 +10,7 @@ pub struct WithdrawLiquidationGainsParams {
     pub collateral_denom: String,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: WithdrawLiquidationGainsParams)]
 pub struct WithdrawLiquidationGains<'info> {
@@ -71,6 +73,11 @@ pub struct WithdrawLiquidationGains<'info> {
 
 
 pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidationGainsParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_stake_amount.upgrade();
+    ctx.accounts.user_collateral_snapshot.upgrade();
+    ctx.accounts.stability_pool_snapshot.upgrade();
+    
     let user_stake_amount = &mut ctx.accounts.user_stake_amount;
     let user_collateral_snapshot = &mut ctx.accounts.user_collateral_snapshot;
     let stability_pool_snapshot = &ctx.accounts.stability_pool_snapshot;
@@ -201,5 +208,11 @@ pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidati
     msg!("User: {}", ctx.accounts.user.key());
     msg!("S snapshot updated to: {}", stability_pool_snapshot.s_factor);
 
+    emit_cpi!(GainsWithdrawn {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom,
+        amount: collateral_gain,
+    });
+
     Ok(())
 }
\ No newline at end of file
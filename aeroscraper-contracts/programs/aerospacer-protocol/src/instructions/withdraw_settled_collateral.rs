@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::{apply_pending_rewards, settle_trove_at_frozen_price};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawSettledCollateralParams {
+    pub collateral_denom: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: WithdrawSettledCollateralParams)]
+pub struct WithdrawSettledCollateral<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Box<Account<'info, StateAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", user.key().as_ref()],
+        bump,
+        constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        bump,
+        constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
+
+    /// CHECK: CollateralSettlement PDA for the denom; still uninitialized if the admin never
+    /// froze a price for it, which the handler checks before deserializing
+    #[account(
+        mut,
+        seeds = [b"collateral_settlement", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_settlement: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: Box<Account<'info, TotalCollateralAmount>>,
+
+    // User's collateral account (to receive excess collateral)
+    #[account(
+        mut,
+        constraint = user_collateral_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    // Protocol's collateral vault
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump,
+        constraint = protocol_collateral_vault.mint == user_collateral_account.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub protocol_collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Trove owner exit after shutdown: settle any remaining debt at the frozen price,
+// then withdraw whatever collateral is left over.
+// A denom whose price was never frozen is not part of the redemption basket, so once the
+// grace period has ended its troves release all of their collateral and the debt is written off
+pub fn handler(ctx: Context<WithdrawSettledCollateral>, params: WithdrawSettledCollateralParams) -> Result<()> {
+    // Upgrade accounts written under an older layout (see state::Versioned)
+    ctx.accounts.user_debt_amount.upgrade();
+    ctx.accounts.user_collateral_amount.upgrade();
+    ctx.accounts.total_collateral_amount.upgrade();
+    
+    require!(
+        ctx.accounts.state.is_shutdown,
+        AerospacerProtocolError::ProtocolNotShutdown
+    );
+    
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &ctx.accounts.total_collateral_amount,
+    )?;
+    
+    let debt_settled = ctx.accounts.user_debt_amount.amount;
+    let settlement_info = ctx.accounts.collateral_settlement.to_account_info();
+    let collateral_reserved = if settlement_info.data_is_empty() {
+        // The price can still be frozen until the grace period ends
+        require!(
+            ctx.accounts.clock.unix_timestamp >= ctx.accounts.state.shutdown_timestamp + SETTLEMENT_GRACE_PERIOD,
+            AerospacerProtocolError::SettlementNotOpen
+        );
+        ctx.accounts.user_debt_amount.amount = 0;
+        msg!("No settlement price for {}: debt {} written off", params.collateral_denom, debt_settled);
+        0
+    } else {
+        // SECURITY: An initialized PDA at these seeds can only have been created by emergency_shutdown
+        require!(
+            settlement_info.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let settlement_data = settlement_info.try_borrow_data()?;
+        let mut settlement = CollateralSettlement::try_deserialize(&mut &settlement_data[..])?;
+        drop(settlement_data);
+        settlement.upgrade();
+        
+        // Settle now if no keeper has done so yet
+        let collateral_reserved = settle_trove_at_frozen_price(
+            &mut ctx.accounts.user_debt_amount,
+            &mut ctx.accounts.user_collateral_amount,
+            &mut settlement,
+            &mut ctx.accounts.total_collateral_amount,
+            ctx.accounts.state.stable_decimals(),
+        )?;
+        
+        let mut settlement_data = settlement_info.try_borrow_mut_data()?;
+        settlement.try_serialize(&mut &mut settlement_data[..])?;
+        collateral_reserved
+    };
+    
+    let excess_collateral = ctx.accounts.user_collateral_amount.amount;
+    
+    if debt_settled > 0 {
+        emit_cpi!(TroveSettled {
+            owner: ctx.accounts.user.key(),
+            collateral_denom: params.collateral_denom.clone(),
+            debt_settled,
+            collateral_reserved,
+            collateral_remaining: excess_collateral,
+        });
+    }
+    
+    // Update accounting before the transfer
+    ctx.accounts.total_collateral_amount.amount = ctx.accounts.total_collateral_amount.amount
+        .checked_sub(excess_collateral)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    ctx.accounts.user_collateral_amount.amount = 0;
+    
+    if excess_collateral > 0 {
+        let collateral_denom_bytes = params.collateral_denom.as_bytes();
+        let seeds = &[
+            b"protocol_collateral_vault",
+            collateral_denom_bytes,
+            &[ctx.bumps.protocol_collateral_vault],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_collateral_vault.to_account_info(),
+                to: ctx.accounts.user_collateral_account.to_account_info(),
+                authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        anchor_spl::token::transfer(transfer_ctx, excess_collateral)?;
+    }
+    
+    msg!("Settled collateral withdrawn: {} {}", excess_collateral, params.collateral_denom);
+    
+    emit_cpi!(SettledCollateralWithdrawn {
+        owner: ctx.accounts.user.key(),
+        collateral_denom: params.collateral_denom,
+        amount: excess_collateral,
+    });
+    
+    Ok(())
+}
@@ -1,12 +1,25 @@
 #![allow(unexpected_cfgs)]
+// #[program] expands to IDL instructions that call the deprecated AccountInfo::realloc
+#![allow(deprecated)]
 
 use anchor_lang::prelude::*;
 
+// Diagnostic logging for intermediate values. Compiled to a no-op unless the
+// `verbose-logs` feature is enabled, since each msg! costs compute units.
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
 // Core modules
 pub mod error;
 pub mod state;
 pub mod msg;
 pub mod query;
+pub mod events;
 
 // New architecture modules
 pub mod account_management;
@@ -37,6 +50,11 @@ pub mod aerospacer_protocol {
         instructions::update_protocol_addresses::handler(ctx, params)
     }
 
+    // Update the minimum collateral ratio and protocol fee (risk manager)
+    pub fn update_risk_params(ctx: Context<UpdateRiskParams>, params: UpdateRiskParamsParams) -> Result<()> {
+        instructions::update_risk_params::handler(ctx, params)
+    }
+
     // Transfer stablecoins between accounts
     pub fn transfer_stablecoin(ctx: Context<TransferStablecoin>, params: TransferStablecoinParams) -> Result<()> {
         instructions::transfer_stablecoin::handler(ctx, params)
@@ -107,6 +125,76 @@ pub mod aerospacer_protocol {
         instructions::redeem::handler(ctx, params)
     }
 
+    // Grant a role (risk manager, oracle manager, fee manager, guardian) to an account (owner only)
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        instructions::grant_role::handler(ctx, params)
+    }
+
+    // Revoke a role, leaving it unassigned (owner only)
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        instructions::revoke_role::handler(ctx, params)
+    }
+
+    // Propose a new admin; takes effect only once accepted (admin only)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+        instructions::propose_admin::handler(ctx, params)
+    }
+
+    // Accept a pending admin transfer (proposed admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    // Withdraw a pending admin transfer (admin only)
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::cancel_admin_transfer::handler(ctx)
+    }
+
+    // Pause or unpause operation classes (guardian may only pause, owner may do both)
+    pub fn set_paused(ctx: Context<SetPaused>, params: SetPausedParams) -> Result<()> {
+        instructions::set_paused::handler(ctx, params)
+    }
+
+    // Emergency shutdown: stop minting/liquidation and freeze the final price for a denom (admin only, once per denom)
+    pub fn emergency_shutdown(ctx: Context<EmergencyShutdown>, params: EmergencyShutdownParams) -> Result<()> {
+        instructions::emergency_shutdown::handler(ctx, params)
+    }
+
+    // Settle a trove's debt at the frozen shutdown price (permissionless)
+    pub fn settle_trove(ctx: Context<SettleTrove>, params: SettleTroveParams) -> Result<()> {
+        instructions::settle_trove::handler(ctx, params)
+    }
+
+    // Withdraw excess trove collateral after shutdown
+    pub fn withdraw_settled_collateral(ctx: Context<WithdrawSettledCollateral>, params: WithdrawSettledCollateralParams) -> Result<()> {
+        instructions::withdraw_settled_collateral::handler(ctx, params)
+    }
+
+    // Burn aUSD for a pro-rata basket of settlement collateral
+    pub fn redeem_settlement<'info>(ctx: Context<'_, '_, 'info, 'info, RedeemSettlement<'info>>, params: RedeemSettlementParams) -> Result<()> {
+        instructions::redeem_settlement::handler(ctx, params)
+    }
+
+    // Queue a parameter change behind the timelock delay (role required by the change)
+    pub fn queue_change(ctx: Context<QueueChange>, params: QueueChangeParams) -> Result<()> {
+        instructions::queue_change::handler(ctx, params)
+    }
+
+    // Apply a queued change once its delay has elapsed (permissionless)
+    pub fn execute_change(ctx: Context<ExecuteChange>, params: ExecuteChangeParams) -> Result<()> {
+        instructions::execute_change::handler(ctx, params)
+    }
+
+    // Drop a queued change before it executes (guardian)
+    pub fn cancel_change(ctx: Context<CancelChange>, params: CancelChangeParams) -> Result<()> {
+        instructions::cancel_change::handler(ctx, params)
+    }
+
+    // Resize the state account and upgrade it to the current layout version (admin only)
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        instructions::migrate_state::handler(ctx)
+    }
+
     // NOTE: ADMIN functions removed - obsolete with off-chain sorting architecture
     // - reset_sorted_troves: No longer needed (no sorted list state to reset)
     // - close_node: No longer needed (no Node accounts to close)
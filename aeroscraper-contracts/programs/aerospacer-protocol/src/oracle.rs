@@ -1,19 +1,151 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{hash::hash, instruction::{Instruction, AccountMeta}};
 use crate::error::*;
+use crate::state::{MIN_STABLE_COIN_DECIMALS, MAX_STABLE_COIN_DECIMALS};
 
-/// Oracle integration for price feeds
-/// This module provides clean integration with our aerospacer-oracle contract
+/// Decimals of the oracle's normalized prices (matches aerospacer-oracle
+/// NORMALIZED_PRICE_DECIMALS): USD per whole token, 10^18 = 1 USD
+pub const NORMALIZED_PRICE_DECIMALS: u32 = 18;
+
+/// Decimals of collateral values: micro-USD
+const COLLATERAL_VALUE_DECIMALS: u32 = 6;
+
+// Oracle integration for price feeds
+// This module provides clean integration with our aerospacer-oracle contract
 
 /// Price data structure (matches aerospacer-oracle PriceResponse)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct PriceData {
     pub denom: String,
     pub price: i64, // Oracle returns i64
+    /// The collateral token's decimals
     pub decimal: u8,
     pub confidence: u64,
     pub timestamp: i64,
     pub exponent: i32,
+    /// Oldest accepted price age for the asset, in seconds
+    pub max_staleness_secs: u32,
+    /// Oracle feed status; a price that is not live is the oracle's last good price
+    pub status: PriceFeedStatus,
+    /// Exponential moving average price and confidence, in the same units as `price`
+    pub ema_price: i64,
+    pub ema_confidence: u64,
+    /// Price, confidence and EMA price in USD per whole token, with
+    /// NORMALIZED_PRICE_DECIMALS decimals; the protocol's math uses these
+    pub normalized_price: u128,
+    pub normalized_confidence: u128,
+    pub normalized_ema_price: u128,
+    /// The asset's lifecycle state
+    pub asset_state: AssetState,
+}
+
+impl PriceData {
+    /// Reject a price published more than the asset's staleness limit before `now`
+    pub fn check_staleness(&self, now: i64) -> Result<()> {
+        require!(
+            now.saturating_sub(self.timestamp) <= self.max_staleness_secs as i64,
+            AerospacerProtocolError::PriceTooOld
+        );
+        Ok(())
+    }
+    
+    /// Reject the oracle's last good price unless `price_use` is risk-reducing
+    pub fn check_live(&self, price_use: PriceUse) -> Result<()> {
+        require!(
+            self.status.is_live() || price_use == PriceUse::RiskReducing,
+            AerospacerProtocolError::PriceFeedNotLive
+        );
+        Ok(())
+    }
+}
+
+/// Oracle price feed status (matches aerospacer-oracle PriceFeedStatus)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceFeedStatus {
+    Working,
+    UsingFallback,
+    Frozen,
+    Broken,
+}
+
+impl PriceFeedStatus {
+    /// Whether the oracle read the price from its sources, rather than serving the last good one
+    pub fn is_live(&self) -> bool {
+        matches!(self, PriceFeedStatus::Working | PriceFeedStatus::UsingFallback)
+    }
+}
+
+/// Collateral asset lifecycle state (matches aerospacer-oracle AssetState)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetState {
+    Active,
+    BorrowDisabled,
+    LiquidationOnly,
+    Frozen,
+}
+
+/// Trove operations an asset's lifecycle state can forbid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TroveAction {
+    /// Opening a trove and borrowing
+    Borrow,
+    /// Adding collateral to a trove
+    AddCollateral,
+    /// Withdrawing collateral from a trove
+    RemoveCollateral,
+    /// Repaying and closing a trove
+    Repay,
+    /// Redeeming aUSD against troves
+    Redeem,
+    /// Liquidating troves
+    Liquidate,
+}
+
+impl AssetState {
+    /// Whether the state allows `action`. Topping up, repaying and closing only reduce
+    /// risk and are allowed unless the asset is frozen; borrow-disabled assets can also be
+    /// redeemed against and liquidated, liquidation-only ones only liquidated, and frozen
+    /// ones not touched at all
+    pub fn allows(&self, action: TroveAction) -> bool {
+        match self {
+            AssetState::Active => true,
+            AssetState::BorrowDisabled => matches!(
+                action,
+                TroveAction::AddCollateral | TroveAction::Repay | TroveAction::Redeem | TroveAction::Liquidate
+            ),
+            AssetState::LiquidationOnly => matches!(
+                action,
+                TroveAction::AddCollateral | TroveAction::Repay | TroveAction::Liquidate
+            ),
+            AssetState::Frozen => false,
+        }
+    }
+    
+    /// Reject `action` unless the state allows it
+    pub fn check(&self, action: TroveAction) -> Result<()> {
+        require!(self.allows(action), AerospacerProtocolError::AssetStateRestricted);
+        Ok(())
+    }
+}
+
+/// What a price is used for, which decides whether the oracle's last good price will do
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceUse {
+    /// Operations that can leave the system less safe (opening, borrowing, withdrawing
+    /// collateral, liquidating, settling a shutdown): a live price is required
+    Live,
+    /// Adding collateral and repaying, which only reduce risk: the last good price is
+    /// accepted while the feed is frozen or broken
+    RiskReducing,
+}
+
+/// What a price is for (matches aerospacer-oracle PricePurpose); the oracle's price policy
+/// for the asset picks a spot, EMA or TWAP price for it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricePurpose {
+    Borrow,
+    Withdraw,
+    Liquidation,
 }
 
 /// Oracle context for price queries via CPI
@@ -21,16 +153,38 @@ pub struct OracleContext<'info> {
     /// Our oracle program
     pub oracle_program: AccountInfo<'info>,
     
-    /// Oracle state account
+    /// Oracle state PDA; the oracle reads the Pyth program that must own push price accounts
     pub oracle_state: AccountInfo<'info>,
     
+    /// Oracle asset config PDA for the collateral denom; when writable, the oracle records
+    /// the read as its last good price
+    pub oracle_asset: AccountInfo<'info>,
+    
+    /// The oracle's event authority PDA, for the event it emits when it records a read
+    pub oracle_event_authority: AccountInfo<'info>,
+    
     /// Pyth price account for the collateral asset
     pub pyth_price_account: AccountInfo<'info>,
     
+    /// Price accounts of the asset's extra oracle sources, in the oracle's order
+    pub extra_price_accounts: Vec<AccountInfo<'info>>,
+    
+    /// Oracle price history PDA for the collateral denom, needed when the asset's price
+    /// policy uses a TWAP for the purpose asked for
+    pub price_history: Option<AccountInfo<'info>>,
+    
     /// Clock sysvar
     pub clock: AccountInfo<'info>,
 }
 
+/// Collect the extra source price accounts an instruction was given, skipping omitted ones
+pub fn extra_price_accounts<'info>(accounts: &[&Option<UncheckedAccount<'info>>]) -> Vec<AccountInfo<'info>> {
+    accounts
+        .iter()
+        .filter_map(|account| account.as_ref().map(|a| a.to_account_info()))
+        .collect()
+}
+
 /// Oracle integration implementation
 impl<'info> OracleContext<'info> {
     /// Get price for a specific collateral denom via CPI to our oracle
@@ -40,59 +194,130 @@ impl<'info> OracleContext<'info> {
             denom.to_string(),
             self.oracle_program.to_account_info(),
             self.oracle_state.to_account_info(),
+            self.oracle_asset.to_account_info(),
+            self.oracle_event_authority.to_account_info(),
             self.pyth_price_account.to_account_info(),
+            &self.extra_price_accounts,
             self.clock.to_account_info(),
         )?;
         
-        // Convert PriceResponse to PriceData
-        Ok(PriceData {
-            denom: price_response.denom,
-            price: price_response.price,
-            decimal: price_response.decimal,
-            confidence: price_response.confidence,
-            timestamp: price_response.timestamp,
-            exponent: price_response.exponent,
-        })
+        Ok(price_response.into())
     }
     
-    /// Get prices for all supported collateral denoms via CPI
-    pub fn get_all_prices(&self) -> Result<Vec<PriceData>> {
-        let denoms = get_all_denoms_via_cpi(
+    /// Get the price the oracle's price policy sets for `purpose` (spot, EMA or TWAP)
+    pub fn get_price_for(&self, denom: &str, purpose: PricePurpose) -> Result<PriceData> {
+        let price_response = get_price_for_via_cpi(
+            denom.to_string(),
+            purpose,
             self.oracle_program.to_account_info(),
             self.oracle_state.to_account_info(),
+            self.oracle_asset.to_account_info(),
+            self.oracle_event_authority.to_account_info(),
+            self.pyth_price_account.to_account_info(),
+            self.price_history.clone(),
+            &self.extra_price_accounts,
+            self.clock.to_account_info(),
         )?;
         
-        let mut prices = Vec::new();
-        
-        for denom in denoms {
-            let price_data = self.get_price(&denom)?;
-            prices.push(price_data);
-        }
-        
-        Ok(prices)
+        Ok(price_response.into())
     }
     
-    /// Validate price data
-    pub fn validate_price(&self, price_data: &PriceData) -> Result<()> {
+    /// Get the collateral's lifecycle state via CPI to our oracle
+    pub fn get_asset_state(&self, denom: &str) -> Result<AssetState> {
+        get_asset_state_via_cpi(
+            denom.to_string(),
+            self.oracle_program.to_account_info(),
+            self.oracle_asset.to_account_info(),
+        )
+    }
+    
+    /// Whether the oracle no longer lists the collateral (see is_asset_removed)
+    pub fn is_asset_removed(&self, denom: &str) -> bool {
+        is_asset_removed(denom, &self.oracle_program, &self.oracle_asset)
+    }
+    
+    /// Validate price data for `price_use`. The oracle's last good price is only accepted
+    /// for risk-reducing operations, and without a staleness check since it is old by nature
+    pub fn validate_price(&self, price_data: &PriceData, price_use: PriceUse) -> Result<()> {
         // Check if price is within reasonable bounds
         require!(
-            price_data.price > 0,
+            price_data.price > 0 && price_data.normalized_price > 0,
             AerospacerProtocolError::InvalidAmount
         );
         
-        // DEVNET: Price staleness check commented out for testing
-        // let current_time = Clock::get()?.unix_timestamp;
-        // let max_age = 86400; // 24 hours in seconds (more lenient for devnet)
-        // 
-        // require!(
-        //     current_time - price_data.timestamp <= max_age,
-        //     AerospacerProtocolError::InvalidAmount
-        // );
+        price_data.check_live(price_use)?;
+        if !price_data.status.is_live() {
+            msg!("Using last good price for {} ({:?})", price_data.denom, price_data.status);
+            return Ok(());
+        }
+        
+        // Same per-asset limit the oracle applied; the devnet build skips it like the oracle does
+        if !cfg!(feature = "devnet") {
+            price_data.check_staleness(Clock::get()?.unix_timestamp)?;
+        }
         
         Ok(())
     }
 }
 
+/// How collateral is priced for a check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Valuation {
+    /// The oracle price as reported (liquidation, repayment, adding collateral)
+    Spot,
+    /// The price minus its confidence interval (borrowing and withdrawing collateral), so a
+    /// wide interval during volatility cannot be minted against
+    Conservative,
+}
+
+/// a × b / d with a 256-bit intermediate product, rounded down or up. None if `d` is zero
+/// or the result does not fit in u128
+fn mul_div(a: u128, b: u128, d: u128, round_up: bool) -> Option<u128> {
+    if d == 0 {
+        return None;
+    }
+    
+    // Schoolbook multiply on 64-bit halves into (hi, lo)
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    
+    let (quotient, remainder) = if hi == 0 {
+        (lo / d, lo % d)
+    } else {
+        // The quotient only fits in u128 if hi < d
+        if hi >= d {
+            return None;
+        }
+        // Long division of (hi, lo) by d, one bit of lo at a time
+        let mut remainder = hi;
+        let mut quotient = 0u128;
+        for bit in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((lo >> bit) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= d {
+                remainder = remainder.wrapping_sub(d);
+                quotient |= 1;
+            }
+        }
+        (quotient, remainder)
+    };
+    
+    if round_up && remainder > 0 {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
 /// Price calculation utilities
 /// 
 /// ICR Convention:
@@ -102,40 +327,85 @@ impl<'info> OracleContext<'info> {
 pub struct PriceCalculator;
 
 impl PriceCalculator {
-    /// Calculate collateral value in USD
+    /// Calculate collateral value in micro-USD from a normalized price (USD per whole
+    /// token, 18 decimals) and the token's decimals. `confidence` (normalized too) is only
+    /// subtracted under conservative valuation
     pub fn calculate_collateral_value(
         amount: u64,
-        price: u64,
-        decimal: u8,
+        price: u128,
+        confidence: u128,
+        token_decimals: u8,
+        valuation: Valuation,
     ) -> Result<u64> {
-        msg!("🔍 [PriceCalculator::calculate_collateral_value]");
-        msg!("  amount (lamports): {}", amount);
-        msg!("  price (raw Pyth): {}", price);
-        msg!("  decimal (from oracle): {}", decimal);
+        debug_msg!("🔍 [PriceCalculator::calculate_collateral_value]");
+        debug_msg!("  amount (base units): {}", amount);
+        debug_msg!("  price (normalized): {}", price);
+        debug_msg!("  token decimals: {}", token_decimals);
         
-        let decimal_factor = 10_u128.pow(decimal as u32);
-        msg!("  decimal_factor (10^{}): {}", decimal, decimal_factor);
+        let price = match valuation {
+            Valuation::Spot => price,
+            Valuation::Conservative => price.saturating_sub(confidence),
+        };
+        debug_msg!("  price ({:?} valuation): {}", valuation, price);
         
-        let product = (amount as u128)
-            .checked_mul(price as u128)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        msg!("  amount × price: {}", product);
+        // amount / 10^token_decimals whole tokens × price / 10^18 USD, in micro-USD
+        let decimal_factor = Self::value_scale(token_decimals)?;
+        debug_msg!("  decimal_factor: {}", decimal_factor);
         
-        let value = product
-            .checked_div(decimal_factor)
+        // amount × price exceeds u128 for large balances of high-decimal tokens, so the
+        // product is kept at 256 bits until the division
+        let value = mul_div(amount as u128, price, decimal_factor, false)
             .ok_or(AerospacerProtocolError::OverflowError)?;
-        msg!("  collateral_value (after division): {}", value);
+        debug_msg!("  collateral_value (after division): {}", value);
         
         // Convert back to u64, ensuring it fits
         if value > u64::MAX as u128 {
-            msg!("❌ Overflow: value {} > u64::MAX", value);
+            debug_msg!("❌ Overflow: value {} > u64::MAX", value);
             return Err(AerospacerProtocolError::OverflowError.into());
         }
         
-        msg!("✅ Final collateral_value (u64): {}", value as u64);
+        debug_msg!("✅ Final collateral_value (u64): {}", value as u64);
         Ok(value as u64)
     }
     
+    /// Calculate the collateral amount worth `debt_amount` aUSD at the given normalized price
+    /// Inverse of calculate_collateral_value: debt (`stable_decimals` decimals) is worth
+    /// debt × 10^(token_decimals + 18 - stable_decimals) / price collateral units. Rounds up
+    /// so the result always covers the full debt.
+    pub fn calculate_collateral_for_debt(
+        debt_amount: u64,
+        price: u128,
+        token_decimals: u8,
+        stable_decimals: u8,
+    ) -> Result<u64> {
+        require!(price > 0, AerospacerProtocolError::DivideByZeroError);
+        Self::debt_to_micro_usd_factor(stable_decimals)?;
+        
+        let scale = 10_u128
+            .checked_pow(token_decimals as u32 + NORMALIZED_PRICE_DECIMALS - stable_decimals as u32)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+        let collateral = mul_div(debt_amount as u128, scale, price, true)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+        
+        u64::try_from(collateral).map_err(|_| AerospacerProtocolError::OverflowError.into())
+    }
+    
+    /// 10^(token_decimals + 18 - 6): divides base units × normalized price into micro-USD
+    fn value_scale(token_decimals: u8) -> Result<u128> {
+        10_u128
+            .checked_pow(token_decimals as u32 + NORMALIZED_PRICE_DECIMALS - COLLATERAL_VALUE_DECIMALS)
+            .ok_or(AerospacerProtocolError::OverflowError.into())
+    }
+    
+    /// 10^(stable_decimals - 6): debt base units per micro-USD
+    fn debt_to_micro_usd_factor(stable_decimals: u8) -> Result<u128> {
+        require!(
+            (MIN_STABLE_COIN_DECIMALS..=MAX_STABLE_COIN_DECIMALS).contains(&stable_decimals),
+            AerospacerProtocolError::UnsupportedStablecoinDecimals
+        );
+        Ok(10_u128.pow((stable_decimals - MIN_STABLE_COIN_DECIMALS) as u32))
+    }
+    
     /// Calculate collateral ratio in micro-percent (percentage × 1,000,000)
     /// Returns ICR in micro-percent scale to match MCR storage format
     /// Example: 150% ICR = 150_000_000, 832.35% ICR = 832_350_000
@@ -145,95 +415,97 @@ impl PriceCalculator {
     pub fn calculate_collateral_ratio(
         collateral_value: u64,
         debt_amount: u64,
+        stable_decimals: u8,
     ) -> Result<u64> {
-        msg!("🔍 [PriceCalculator::calculate_collateral_ratio]");
-        msg!("  collateral_value: {}", collateral_value);
-        msg!("  debt_amount: {}", debt_amount);
+        debug_msg!("🔍 [PriceCalculator::calculate_collateral_ratio]");
+        debug_msg!("  collateral_value: {}", collateral_value);
+        debug_msg!("  debt_amount: {}", debt_amount);
+        debug_msg!("  stable_decimals: {}", stable_decimals);
         
         if debt_amount == 0 {
-            msg!("  debt is 0 → returning u64::MAX");
+            debug_msg!("  debt is 0 → returning u64::MAX");
             return Ok(u64::MAX);
         }
         
         // Normalize both values to the same units for comparison
-        // Collateral value is in micro-USD (6 decimals) - enforced by oracle's adjusted_decimal
-        // Debt amount is in 18 decimals (aUSD has 18 decimals)
-        // We need to scale them to the same precision: 10^(18-6) = 10^12
+        // Collateral value is in micro-USD (6 decimals) - see calculate_collateral_value
+        // Debt amount is in the stablecoin mint's decimals (6..=18)
+        // We need to scale them to the same precision: 10^(stable_decimals-6)
         
         // To avoid overflow while maintaining precision, we use chunked long-division
-        // Final formula: ICR = (collateral / debt) × 10^20
-        // Where 10^20 = 10^12 (decimal adjustment) × 10^8 (100 × 1_000_000 for micro-percent)
+        // Final formula: ICR = (collateral / debt) × 10^(stable_decimals+2)
+        // Where 10^(stable_decimals+2) = 10^(stable_decimals-6) (decimal adjustment) × 10^8 (100 × 1_000_000 for micro-percent)
+        // For 18-decimal aUSD that is 10^20, which overflows if multiplied in one step
         //
-        // Instead of multiplying by 10^20 all at once (which overflows), we:
+        // Instead we:
         // 1. Compute quotient and remainder: collateral / debt
-        // 2. Apply scaling in chunks: ×10^6, ×10^6, ×10^6, ×10^2 (total ×10^20)
+        // 2. Apply scaling in chunks of at most ×10^6 (e.g. ×10^6, ×10^6, ×10^6, ×10^2 for 10^20)
         // 3. After each chunk, divide by debt and carry the remainder
         // This keeps all intermediates within u128 bounds
         
+        Self::debt_to_micro_usd_factor(stable_decimals)?;
         let debt_128 = debt_amount as u128;
-        let mut quotient = collateral_value as u128;
-        let mut remainder = 0u128;
+        let mut quotient = collateral_value as u128 / debt_128;
+        let mut remainder = collateral_value as u128 % debt_128;
+        let mut exponent = stable_decimals as u32 + 2;
         
-        // Chunk 1: ×10^6
-        remainder = quotient.checked_mul(1_000_000)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        quotient = remainder / debt_128;
-        remainder = remainder % debt_128;
-        msg!("  After chunk 1 (×10^6): quotient={}, remainder={}", quotient, remainder);
-        
-        // Chunk 2: ×10^6
-        quotient = quotient.checked_mul(1_000_000)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        remainder = remainder.checked_mul(1_000_000)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        let temp = remainder / debt_128;
-        quotient = quotient.checked_add(temp)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        remainder = remainder % debt_128;
-        msg!("  After chunk 2 (×10^6): quotient={}, remainder={}", quotient, remainder);
-        
-        // Chunk 3: ×10^6
-        quotient = quotient.checked_mul(1_000_000)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        remainder = remainder.checked_mul(1_000_000)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        let temp = remainder / debt_128;
-        quotient = quotient.checked_add(temp)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        remainder = remainder % debt_128;
-        msg!("  After chunk 3 (×10^6): quotient={}, remainder={}", quotient, remainder);
-        
-        // Chunk 4: ×10^2 (final scaling to reach 10^20 total)
-        quotient = quotient.checked_mul(100)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        remainder = remainder.checked_mul(100)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        let temp = remainder / debt_128;
-        let icr_micro_percent = quotient.checked_add(temp)
-            .ok_or(AerospacerProtocolError::OverflowError)?;
-        msg!("  Final ICR (micro-percent): {}", icr_micro_percent);
+        while exponent > 0 {
+            let chunk = exponent.min(6);
+            let factor = 10_u128.pow(chunk);
+            
+            quotient = quotient.checked_mul(factor)
+                .ok_or(AerospacerProtocolError::OverflowError)?;
+            remainder = remainder.checked_mul(factor)
+                .ok_or(AerospacerProtocolError::OverflowError)?;
+            quotient = quotient.checked_add(remainder / debt_128)
+                .ok_or(AerospacerProtocolError::OverflowError)?;
+            remainder %= debt_128;
+            exponent -= chunk;
+            debug_msg!("  After chunk (×10^{}): quotient={}, remainder={}", chunk, quotient, remainder);
+        }
+        let icr_micro_percent = quotient;
+        debug_msg!("  Final ICR (micro-percent): {}", icr_micro_percent);
         
         // Convert to u64
         let result = u64::try_from(icr_micro_percent).map_err(|_| {
-            msg!("❌ Overflow converting ratio {} to u64", icr_micro_percent);
+            debug_msg!("❌ Overflow converting ratio {} to u64", icr_micro_percent);
             AerospacerProtocolError::OverflowError
         })?;
         
-        msg!("✅ Final ICR (micro-percent): {} (human: {}%)", result, result / 1_000_000);
+        debug_msg!("✅ Final ICR (micro-percent): {} (human: {}%)", result, result / 1_000_000);
         Ok(result)
     }
     
+    /// ICR of a single-collateral trove at the given price
+    pub fn calculate_icr(
+        collateral_amount: u64,
+        debt_amount: u64,
+        price_data: &PriceData,
+        valuation: Valuation,
+        stable_decimals: u8,
+    ) -> Result<u64> {
+        let collateral_value = Self::calculate_collateral_value(
+            collateral_amount,
+            price_data.normalized_price,
+            price_data.normalized_confidence,
+            price_data.decimal,
+            valuation,
+        )?;
+        Self::calculate_collateral_ratio(collateral_value, debt_amount, stable_decimals)
+    }
+    
     /// Check if trove is liquidatable
     pub fn is_liquidatable(
         collateral_value: u64,
         debt_amount: u64,
         minimum_ratio: u64,
+        stable_decimals: u8,
     ) -> Result<bool> {
         if debt_amount == 0 {
             return Ok(false);
         }
         
-        let ratio = Self::calculate_collateral_ratio(collateral_value, debt_amount)?;
+        let ratio = Self::calculate_collateral_ratio(collateral_value, debt_amount, stable_decimals)?;
         Ok(ratio < minimum_ratio)
     }
     
@@ -241,7 +513,7 @@ impl PriceCalculator {
     /// Used for multi-collateral trove ICR calculation
     pub fn calculate_multi_collateral_value(
         collateral_amounts: &[(String, u64)],
-        prices: &[(String, u64, u8)], // (denom, price, decimal)
+        prices: &[(String, u128, u8)], // (denom, normalized price, token decimals)
     ) -> Result<u64> {
         let mut total_value = 0u64;
         
@@ -254,7 +526,9 @@ impl PriceCalculator {
             let value = Self::calculate_collateral_value(
                 *amount,
                 price_data.1,
+                0,
                 price_data.2,
+                Valuation::Spot,
             )?;
             
             total_value = total_value
@@ -269,7 +543,8 @@ impl PriceCalculator {
     pub fn calculate_trove_icr(
         collateral_amounts: &[(String, u64)],
         debt_amount: u64,
-        prices: &[(String, u64, u8)],
+        prices: &[(String, u128, u8)],
+        stable_decimals: u8,
     ) -> Result<u64> {
         if debt_amount == 0 {
             return Ok(u64::MAX);
@@ -280,7 +555,7 @@ impl PriceCalculator {
             prices,
         )?;
         
-        Self::calculate_collateral_ratio(total_collateral_value, debt_amount)
+        Self::calculate_collateral_ratio(total_collateral_value, debt_amount, stable_decimals)
     }
 }
 
@@ -293,14 +568,48 @@ pub struct PriceResponse {
     pub timestamp: i64,
     pub confidence: u64,
     pub exponent: i32,
+    pub max_staleness_secs: u32,
+    pub sources_used: u8,
+    pub status: PriceFeedStatus,
+    pub ema_price: i64,
+    pub ema_confidence: u64,
+    pub normalized_price: u128,
+    pub normalized_confidence: u128,
+    pub normalized_ema_price: u128,
+    pub asset_state: AssetState,
+}
+
+impl From<PriceResponse> for PriceData {
+    fn from(price_response: PriceResponse) -> Self {
+        PriceData {
+            denom: price_response.denom,
+            price: price_response.price,
+            decimal: price_response.decimal,
+            confidence: price_response.confidence,
+            timestamp: price_response.timestamp,
+            exponent: price_response.exponent,
+            max_staleness_secs: price_response.max_staleness_secs,
+            status: price_response.status,
+            ema_price: price_response.ema_price,
+            ema_confidence: price_response.ema_confidence,
+            normalized_price: price_response.normalized_price,
+            normalized_confidence: price_response.normalized_confidence,
+            normalized_ema_price: price_response.normalized_ema_price,
+            asset_state: price_response.asset_state,
+        }
+    }
 }
 
 /// Execute CPI call to oracle contract's get_price instruction
+#[allow(clippy::too_many_arguments)]
 pub fn get_price_via_cpi<'info>(
     denom: String,
     oracle_program: AccountInfo<'info>,
     oracle_state: AccountInfo<'info>,
+    oracle_asset: AccountInfo<'info>,
+    oracle_event_authority: AccountInfo<'info>,
     pyth_price_account: AccountInfo<'info>,
+    extra_price_accounts: &[AccountInfo<'info>],
     clock: AccountInfo<'info>,
 ) -> Result<PriceResponse> {
     // Calculate discriminator for get_price instruction
@@ -316,12 +625,26 @@ pub fn get_price_via_cpi<'info>(
     // Serialize params struct: { denom: String }
     denom.serialize(&mut instruction_data)?;
     
-    // Build account metas for CPI (include all accounts including program)
-    let account_metas = vec![
-        AccountMeta::new(oracle_state.key(), false),
+    // Build account metas for CPI (include all accounts including program), ending with
+    // the event authority and program #[event_cpi] appends; extra source price accounts
+    // go to the oracle's remaining_accounts
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(oracle_state.key(), false),
+        AccountMeta {
+            pubkey: oracle_asset.key(),
+            is_signer: false,
+            is_writable: oracle_asset.is_writable,
+        },
         AccountMeta::new_readonly(pyth_price_account.key(), false),
         AccountMeta::new_readonly(clock.key(), false),
+        AccountMeta::new_readonly(oracle_event_authority.key(), false),
+        AccountMeta::new_readonly(oracle_program.key(), false),
     ];
+    account_metas.extend(
+        extra_price_accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(account.key(), false)),
+    );
     
     // Build the instruction
     let ix = Instruction {
@@ -332,18 +655,92 @@ pub fn get_price_via_cpi<'info>(
     
     // Execute CPI (data accounts + program)
     // Note: Account metas only include data accounts, but invoke needs the program too
-    anchor_lang::solana_program::program::invoke(
-        &ix,
-        &[
-            oracle_program.clone(),
-            oracle_state.clone(),
-            pyth_price_account.clone(),
-            clock.clone(),
-        ],
-    )?;
-    
-    msg!("Oracle CPI executed successfully for denom: {}", denom);
+    let mut account_infos = vec![
+        oracle_program.clone(),
+        oracle_state,
+        oracle_asset.clone(),
+        pyth_price_account.clone(),
+        clock.clone(),
+        oracle_event_authority,
+    ];
+    account_infos.extend_from_slice(extra_price_accounts);
+    anchor_lang::solana_program::program::invoke(&ix, &account_infos)?;
+    
+    debug_msg!("Oracle CPI executed successfully for denom: {}", denom);
+    
+    read_price_response(&oracle_program)
+}
+
+/// Execute CPI call to oracle contract's get_price_for instruction
+#[allow(clippy::too_many_arguments)]
+pub fn get_price_for_via_cpi<'info>(
+    denom: String,
+    purpose: PricePurpose,
+    oracle_program: AccountInfo<'info>,
+    oracle_state: AccountInfo<'info>,
+    oracle_asset: AccountInfo<'info>,
+    oracle_event_authority: AccountInfo<'info>,
+    pyth_price_account: AccountInfo<'info>,
+    price_history: Option<AccountInfo<'info>>,
+    extra_price_accounts: &[AccountInfo<'info>],
+    clock: AccountInfo<'info>,
+) -> Result<PriceResponse> {
+    // Anchor uses: SHA256("global:get_price_for")[0..8]
+    let hash_result = hash(b"global:get_price_for");
+    let discriminator = &hash_result.to_bytes()[..8];
     
+    // Serialize the GetPriceForParams { denom, purpose }
+    let mut instruction_data = Vec::new();
+    instruction_data.extend_from_slice(discriminator);
+    denom.serialize(&mut instruction_data)?;
+    purpose.serialize(&mut instruction_data)?;
+    
+    // An omitted optional account is passed as the oracle program id, as Anchor expects
+    let price_history = price_history.unwrap_or_else(|| oracle_program.clone());
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(oracle_state.key(), false),
+        AccountMeta {
+            pubkey: oracle_asset.key(),
+            is_signer: false,
+            is_writable: oracle_asset.is_writable,
+        },
+        AccountMeta::new_readonly(pyth_price_account.key(), false),
+        AccountMeta::new_readonly(clock.key(), false),
+        AccountMeta::new_readonly(price_history.key(), false),
+        AccountMeta::new_readonly(oracle_event_authority.key(), false),
+        AccountMeta::new_readonly(oracle_program.key(), false),
+    ];
+    account_metas.extend(
+        extra_price_accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(account.key(), false)),
+    );
+    
+    let ix = Instruction {
+        program_id: oracle_program.key(),
+        accounts: account_metas,
+        data: instruction_data,
+    };
+    
+    let mut account_infos = vec![
+        oracle_program.clone(),
+        oracle_state,
+        oracle_asset,
+        pyth_price_account,
+        clock,
+        price_history,
+        oracle_event_authority,
+    ];
+    account_infos.extend_from_slice(extra_price_accounts);
+    anchor_lang::solana_program::program::invoke(&ix, &account_infos)?;
+    
+    debug_msg!("Oracle CPI executed successfully for denom: {} ({:?})", denom, purpose);
+    
+    read_price_response(&oracle_program)
+}
+
+/// Read the PriceResponse the oracle returned from a price CPI
+fn read_price_response(oracle_program: &AccountInfo) -> Result<PriceResponse> {
     // Parse return data from oracle program
     let return_data = anchor_lang::solana_program::program::get_return_data()
         .ok_or(AerospacerProtocolError::InvalidAmount)?;
@@ -357,21 +754,68 @@ pub fn get_price_via_cpi<'info>(
     // Deserialize PriceResponse
     let price_response: PriceResponse = PriceResponse::deserialize(&mut &return_data.1[..])?;
     
-    msg!("✅ [Oracle CPI] Price received from oracle:");
-    msg!("  denom: {}", price_response.denom);
-    msg!("  price: {}", price_response.price);
-    msg!("  decimal: {}", price_response.decimal);
-    msg!("  exponent: {}", price_response.exponent);
-    msg!("  confidence: {}", price_response.confidence);
-    msg!("  timestamp: {}", price_response.timestamp);
+    debug_msg!("✅ [Oracle CPI] Price received from oracle:");
+    debug_msg!("  denom: {}", price_response.denom);
+    debug_msg!("  price: {}", price_response.price);
+    debug_msg!("  decimal: {}", price_response.decimal);
+    debug_msg!("  exponent: {}", price_response.exponent);
+    debug_msg!("  confidence: {}", price_response.confidence);
+    debug_msg!("  timestamp: {}", price_response.timestamp);
+    debug_msg!("  max_staleness_secs: {}", price_response.max_staleness_secs);
+    debug_msg!("  normalized_price: {}", price_response.normalized_price);
     
     Ok(price_response)
 }
 
-/// Execute CPI call to oracle contract's get_all_denoms instruction
+/// Whether `oracle_asset` is the oracle's asset config PDA for `denom` and remove_data has
+/// closed it. A removed asset has no state to ask for, and its troves stay closable
+pub fn is_asset_removed(denom: &str, oracle_program: &AccountInfo, oracle_asset: &AccountInfo) -> bool {
+    let (address, _) = Pubkey::find_program_address(&[b"asset", denom.as_bytes()], oracle_program.key);
+    oracle_asset.key() == address && oracle_asset.owner != oracle_program.key
+}
+
+/// Execute CPI call to oracle contract's get_asset_state instruction
+pub fn get_asset_state_via_cpi<'info>(
+    denom: String,
+    oracle_program: AccountInfo<'info>,
+    oracle_asset: AccountInfo<'info>,
+) -> Result<AssetState> {
+    // Anchor uses: SHA256("global:get_asset_state")[0..8]
+    let hash_result = hash(b"global:get_asset_state");
+    let discriminator = &hash_result.to_bytes()[..8];
+    
+    // Serialize the GetAssetStateParams { denom }
+    let mut instruction_data = Vec::new();
+    instruction_data.extend_from_slice(discriminator);
+    denom.serialize(&mut instruction_data)?;
+    
+    let ix = Instruction {
+        program_id: oracle_program.key(),
+        accounts: vec![AccountMeta::new_readonly(oracle_asset.key(), false)],
+        data: instruction_data,
+    };
+    anchor_lang::solana_program::program::invoke(&ix, &[oracle_asset, oracle_program.clone()])?;
+    
+    let return_data = anchor_lang::solana_program::program::get_return_data()
+        .ok_or(AerospacerProtocolError::InvalidAmount)?;
+    require!(
+        return_data.0 == oracle_program.key(),
+        AerospacerProtocolError::InvalidAmount
+    );
+    let state = AssetState::deserialize(&mut &return_data.1[..])?;
+    
+    debug_msg!("Asset state for {}: {:?}", denom, state);
+    
+    Ok(state)
+}
+
+/// Execute CPI call to oracle contract's get_all_denoms instruction for the page of
+/// asset config PDAs starting at registry index `start`
 pub fn get_all_denoms_via_cpi<'info>(
     oracle_program: AccountInfo<'info>,
     oracle_state: AccountInfo<'info>,
+    assets: &[AccountInfo<'info>],
+    start: u32,
 ) -> Result<Vec<String>> {
     // Calculate discriminator for get_all_denoms instruction
     // Anchor uses: SHA256("global:get_all_denoms")[0..8]
@@ -379,14 +823,16 @@ pub fn get_all_denoms_via_cpi<'info>(
     let hash_result = hash(preimage);
     let discriminator = &hash_result.to_bytes()[..8];
     
-    // Build instruction data (no params, just discriminator)
+    // Serialize GetAllDenomsParams { start }
     let mut instruction_data = Vec::new();
     instruction_data.extend_from_slice(discriminator);
+    start.serialize(&mut instruction_data)?;
     
-    // Build account metas for CPI - only oracle_state needed
-    let account_metas = vec![
-        AccountMeta::new_readonly(oracle_state.key(), false),
-    ];
+    // Build account metas for CPI - oracle_state, then the page of asset config PDAs
+    let account_metas = std::iter::once(&oracle_state)
+        .chain(assets)
+        .map(|account| AccountMeta::new_readonly(account.key(), false))
+        .collect();
     
     // Build the instruction
     let ix = Instruction {
@@ -396,15 +842,12 @@ pub fn get_all_denoms_via_cpi<'info>(
     };
     
     // Execute CPI
-    anchor_lang::solana_program::program::invoke(
-        &ix,
-        &[
-            oracle_state.clone(),
-            oracle_program.clone(),
-        ],
-    )?;
+    let mut account_infos = vec![oracle_state.clone()];
+    account_infos.extend_from_slice(assets);
+    account_infos.push(oracle_program.clone());
+    anchor_lang::solana_program::program::invoke(&ix, &account_infos)?;
     
-    msg!("Oracle get_all_denoms CPI executed successfully");
+    debug_msg!("Oracle get_all_denoms CPI executed successfully");
     
     // Parse return data from oracle program
     let return_data = anchor_lang::solana_program::program::get_return_data()
@@ -419,10 +862,248 @@ pub fn get_all_denoms_via_cpi<'info>(
     // Deserialize Vec<String> response
     let denoms: Vec<String> = Vec::<String>::deserialize(&mut &return_data.1[..])?;
     
-    msg!("Received {} supported denoms from oracle", denoms.len());
+    debug_msg!("Received {} supported denoms from oracle", denoms.len());
     for denom in &denoms {
-        msg!("  - {}", denom);
+        debug_msg!("  - {}", denom);
     }
     
     Ok(denoms)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // SOL at $150.00: 15_000_000_000 at Pyth expo -8, normalized to 18 decimals
+    const SOL_RAW_PRICE: i64 = 15_000_000_000;
+    const SOL_PRICE: u128 = 150_000_000_000_000_000_000;
+    const SOL_DECIMAL: u8 = 9;
+    const AUSD_DECIMALS: u8 = 18;
+    
+    /// A live, active SOL price published at t=1_000 with a 60-second staleness limit and
+    /// no confidence interval; `normalized_price` is `price` at 18 decimals
+    fn price_data(price: i64, exponent: i32, normalized_price: u128) -> PriceData {
+        PriceData {
+            denom: "SOL".to_string(),
+            price,
+            decimal: SOL_DECIMAL,
+            confidence: 0,
+            timestamp: 1_000,
+            exponent,
+            max_staleness_secs: 60,
+            status: PriceFeedStatus::Working,
+            ema_price: price,
+            ema_confidence: 0,
+            normalized_price,
+            normalized_confidence: 0,
+            normalized_ema_price: normalized_price,
+            asset_state: AssetState::Active,
+        }
+    }
+    
+    #[test]
+    fn test_collateral_for_debt_exact() {
+        // 15 aUSD of debt is covered by exactly 0.1 SOL
+        let debt = 15 * 1_000_000_000_000_000_000u64;
+        let collateral = PriceCalculator::calculate_collateral_for_debt(debt, SOL_PRICE, SOL_DECIMAL, AUSD_DECIMALS).unwrap();
+        assert_eq!(collateral, 100_000_000);
+        
+        // Round trip through calculate_collateral_value gives back $15
+        let value = PriceCalculator::calculate_collateral_value(collateral, SOL_PRICE, 0, SOL_DECIMAL, Valuation::Spot).unwrap();
+        assert_eq!(value, 15_000_000);
+    }
+    
+    #[test]
+    fn test_collateral_for_debt_rounds_up() {
+        // 1 wei of aUSD still requires 1 lamport
+        let collateral = PriceCalculator::calculate_collateral_for_debt(1, SOL_PRICE, SOL_DECIMAL, AUSD_DECIMALS).unwrap();
+        assert_eq!(collateral, 1);
+        
+        // Zero debt needs no collateral, zero price is rejected
+        assert_eq!(PriceCalculator::calculate_collateral_for_debt(0, SOL_PRICE, SOL_DECIMAL, AUSD_DECIMALS).unwrap(), 0);
+        assert!(PriceCalculator::calculate_collateral_for_debt(1, 0, SOL_DECIMAL, AUSD_DECIMALS).is_err());
+    }
+    
+    #[test]
+    fn test_collateral_ratio_matches_across_stablecoin_decimals() {
+        // $15 of collateral against 10 aUSD is 150% whatever the mint precision
+        let value = 15_000_000;
+        let icr_18 = PriceCalculator::calculate_collateral_ratio(value, 10 * 1_000_000_000_000_000_000, 18).unwrap();
+        let icr_6 = PriceCalculator::calculate_collateral_ratio(value, 10 * 1_000_000, 6).unwrap();
+        assert_eq!(icr_18, 150_000_000);
+        assert_eq!(icr_6, 150_000_000);
+        
+        // Unsupported precisions are rejected
+        assert!(PriceCalculator::calculate_collateral_ratio(value, 10, 2).is_err());
+        assert!(PriceCalculator::calculate_collateral_ratio(value, 10, 19).is_err());
+    }
+    
+    #[test]
+    fn test_six_decimal_debt_beyond_eighteen_decimal_cap() {
+        // 1 billion aUSD of debt, far beyond the ~18.4 aUSD u64 cap at 18 decimals
+        let debt = 1_000_000_000 * 1_000_000u64;
+        let collateral = PriceCalculator::calculate_collateral_for_debt(debt, SOL_PRICE, SOL_DECIMAL, 6).unwrap();
+        assert_eq!(collateral, 6_666_666_666_666_667); // 6,666,666.67 SOL, rounded up
+        
+        let value = PriceCalculator::calculate_collateral_value(collateral, SOL_PRICE, 0, SOL_DECIMAL, Valuation::Spot).unwrap();
+        let icr = PriceCalculator::calculate_collateral_ratio(value, debt, 6).unwrap();
+        assert_eq!(icr, 100_000_000);
+    }
+    
+    #[test]
+    fn test_debt_to_micro_usd_factor() {
+        // Debt base units per micro-USD for each supported mint precision
+        assert_eq!(PriceCalculator::debt_to_micro_usd_factor(6).unwrap(), 1);
+        assert_eq!(PriceCalculator::debt_to_micro_usd_factor(9).unwrap(), 1_000);
+        assert_eq!(PriceCalculator::debt_to_micro_usd_factor(18).unwrap(), 1_000_000_000_000);
+        
+        assert!(PriceCalculator::debt_to_micro_usd_factor(5).is_err());
+        assert!(PriceCalculator::debt_to_micro_usd_factor(19).is_err());
+    }
+    
+    #[test]
+    fn test_six_decimal_stablecoin_icr() {
+        // 0.1 SOL at $150 against 10 units of a 6-decimal stablecoin is 150%, as with aUSD
+        let price = price_data(SOL_RAW_PRICE, -8, SOL_PRICE);
+        let collateral = 100_000_000;
+        let icr_6 = PriceCalculator::calculate_icr(collateral, 10_000_000, &price, Valuation::Spot, 6).unwrap();
+        let icr_18 = PriceCalculator::calculate_icr(collateral, 10 * 1_000_000_000_000_000_000, &price, Valuation::Spot, AUSD_DECIMALS).unwrap();
+        assert_eq!(icr_6, 150_000_000);
+        assert_eq!(icr_6, icr_18);
+        
+        // 15 units of debt are covered by exactly 0.1 SOL
+        assert_eq!(PriceCalculator::calculate_collateral_for_debt(15_000_000, SOL_PRICE, SOL_DECIMAL, 6).unwrap(), collateral);
+    }
+    
+    #[test]
+    fn test_collateral_value_across_token_decimals() {
+        // One whole token at $150 is worth $150 whatever its precision
+        for token_decimals in [0u8, 6, 9, 18] {
+            let amount = 10u64.pow(token_decimals as u32);
+            let value = PriceCalculator::calculate_collateral_value(amount, SOL_PRICE, 0, token_decimals, Valuation::Spot).unwrap();
+            assert_eq!(value, 150_000_000);
+        }
+        
+        // A price below a micro-USD per base unit: 1000 tokens at $0.000002 is $0.002
+        let value = PriceCalculator::calculate_collateral_value(1_000_000_000, 2_000_000_000_000, 0, 6, Valuation::Spot).unwrap();
+        assert_eq!(value, 2_000);
+    }
+    
+    #[test]
+    fn test_eighteen_decimal_collateral_beyond_u128_product() {
+        // 18 tokens of an 18-decimal asset at $3000: amount × price is ~5.4e40, past u128
+        let eth_price = 3_000 * 1_000_000_000_000_000_000u128;
+        let amount = 18 * 1_000_000_000_000_000_000u64;
+        let value = PriceCalculator::calculate_collateral_value(amount, eth_price, 0, 18, Valuation::Spot).unwrap();
+        assert_eq!(value, 54_000_000_000);
+        
+        // The largest balance a u64 holds is still valued, ~18.45 tokens
+        let value = PriceCalculator::calculate_collateral_value(u64::MAX, eth_price, 0, 18, Valuation::Spot).unwrap();
+        assert_eq!(value, 55_340_232_221);
+        
+        // 10,000 units of 6-decimal debt need 3.33… tokens, rounded up
+        let collateral = PriceCalculator::calculate_collateral_for_debt(10_000_000_000, eth_price, 18, 6).unwrap();
+        assert_eq!(collateral, 3_333_333_333_333_333_334);
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(7, 3, 2, false), Some(10));
+        assert_eq!(mul_div(7, 3, 2, true), Some(11));
+        assert_eq!(mul_div(6, 3, 2, true), Some(9));
+        assert_eq!(mul_div(1, 1, 0, false), None);
+        
+        // Products past u128 divide back down exactly
+        assert_eq!(mul_div(u128::MAX, 2, 2, false), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, u128::MAX - 1, u128::MAX, false), Some(u128::MAX - 1));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX, true), Some(u128::MAX));
+        assert_eq!(mul_div(1 << 127, 3, 4, true), Some(3 << 125));
+        
+        // A quotient that does not fit is None rather than wrapping
+        assert_eq!(mul_div(u128::MAX, 2, 1, false), None);
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1, false), None);
+    }
+    
+    #[test]
+    fn test_price_staleness_limit() {
+        let price = price_data(SOL_RAW_PRICE, -8, SOL_PRICE);
+        
+        // Accepted up to exactly the limit, rejected one second after
+        assert!(price.check_staleness(1_000).is_ok());
+        assert!(price.check_staleness(1_060).is_ok());
+        assert!(price.check_staleness(1_061).is_err());
+    }
+    
+    #[test]
+    fn test_last_good_price_only_reduces_risk() {
+        let mut price = price_data(SOL_RAW_PRICE, -8, SOL_PRICE);
+        price.status = PriceFeedStatus::UsingFallback;
+        assert!(price.check_live(PriceUse::Live).is_ok());
+        
+        for status in [PriceFeedStatus::Frozen, PriceFeedStatus::Broken] {
+            price.status = status;
+            assert!(price.check_live(PriceUse::Live).is_err());
+            assert!(price.check_live(PriceUse::RiskReducing).is_ok());
+        }
+    }
+    
+    #[test]
+    fn test_asset_states_wind_down() {
+        use TroveAction::*;
+        let actions = [Borrow, AddCollateral, RemoveCollateral, Repay, Redeem, Liquidate];
+        let allowed = |state: AssetState| actions.iter().filter(|a| state.allows(**a)).copied().collect::<Vec<_>>();
+        
+        assert_eq!(allowed(AssetState::Active), actions);
+        assert_eq!(allowed(AssetState::BorrowDisabled), [AddCollateral, Repay, Redeem, Liquidate]);
+        assert_eq!(allowed(AssetState::LiquidationOnly), [AddCollateral, Repay, Liquidate]);
+        assert!(allowed(AssetState::Frozen).is_empty());
+        assert!(AssetState::Frozen.check(Liquidate).is_err());
+        
+        // Top-ups, repayment and closing stay open until the asset is frozen
+        for state in [AssetState::Active, AssetState::BorrowDisabled, AssetState::LiquidationOnly] {
+            assert!(state.check(AddCollateral).is_ok());
+            assert!(state.check(Repay).is_ok());
+        }
+        for action in [AddCollateral, Repay] {
+            assert!(AssetState::Frozen.check(action).is_err());
+        }
+    }
+    
+    #[test]
+    fn test_removed_asset_detection() {
+        let oracle = Pubkey::new_unique();
+        let (asset, _) = Pubkey::find_program_address(&[b"asset", b"SOL"], &oracle);
+        let system = anchor_lang::system_program::ID;
+        let is_removed = |key: &Pubkey, owner: &Pubkey| {
+            let (mut oracle_lamports, mut asset_lamports) = (0, 0);
+            let (mut oracle_data, mut asset_data) = ([], []);
+            let oracle_info = AccountInfo::new(&oracle, false, false, &mut oracle_lamports, &mut oracle_data, &system, true, 0);
+            let asset_info = AccountInfo::new(key, false, false, &mut asset_lamports, &mut asset_data, owner, false, 0);
+            is_asset_removed("SOL", &oracle_info, &asset_info)
+        };
+        
+        // Closed by remove_data: the PDA is back with the system program
+        assert!(is_removed(&asset, &system));
+        // Still listed
+        assert!(!is_removed(&asset, &oracle));
+        // Any other empty account is not taken for the removed asset
+        assert!(!is_removed(&Pubkey::new_unique(), &system));
+    }
+    
+    #[test]
+    fn test_conservative_icr_discounts_confidence() {
+        // 0.1 SOL at $150 ± $1.50 against 10 aUSD of debt
+        let price = PriceData {
+            confidence: SOL_RAW_PRICE as u64 / 100,
+            normalized_confidence: SOL_PRICE / 100,
+            ..price_data(SOL_RAW_PRICE, -8, SOL_PRICE)
+        };
+        let collateral = 100_000_000;
+        let debt = 10 * 1_000_000_000_000_000_000u64;
+        
+        let spot = PriceCalculator::calculate_icr(collateral, debt, &price, Valuation::Spot, AUSD_DECIMALS).unwrap();
+        let conservative = PriceCalculator::calculate_icr(collateral, debt, &price, Valuation::Conservative, AUSD_DECIMALS).unwrap();
+        assert_eq!(spot, 150_000_000);
+        assert_eq!(conservative, 148_500_000);
+    }
+}
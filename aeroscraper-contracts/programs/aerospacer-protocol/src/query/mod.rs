@@ -61,7 +61,11 @@ pub fn query_stake(
     let stake_amount = user_stake_amount_account.amount;
 
     let percentage = if total_stake_amount > 0 {
-        (stake_amount * 1_000_000_000_000_000_000) / total_stake_amount // Simplified Decimal256
+        // Simplified Decimal256 (widened to u128: amount × 10^18 overflows u64 beyond ~18 base units)
+        ((stake_amount as u128)
+            .checked_mul(DECIMAL_FRACTION_18)
+            .ok_or(AerospacerProtocolError::OverflowError)?
+            / total_stake_amount as u128) as u64
     } else {
         0
     };
@@ -2,19 +2,19 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::*;
 
-/// Simplified sorted troves module - Off-chain sorting with on-chain validation
-/// 
-/// NEW ARCHITECTURE:
-/// - Client fetches all troves via RPC (no on-chain size limits)
-/// - Client sorts by ICR off-chain (efficient, no compute limits)
-/// - Client passes 2-3 neighbor accounts via remainingAccounts for validation
-/// - Contract validates ICR ordering without storing linked list
-/// 
-/// Benefits:
-/// - Reduced on-chain storage (no Node accounts, no SortedTrovesState)
-/// - No transaction size limits from passing full list
-/// - Simpler contract logic (~100 lines vs 668 lines)
-/// - Client has full flexibility for sorting strategy
+// Simplified sorted troves module - Off-chain sorting with on-chain validation
+// 
+// NEW ARCHITECTURE:
+// - Client fetches all troves via RPC (no on-chain size limits)
+// - Client sorts by ICR off-chain (efficient, no compute limits)
+// - Client passes 2-3 neighbor accounts via remainingAccounts for validation
+// - Contract validates ICR ordering without storing linked list
+// 
+// Benefits:
+// - Reduced on-chain storage (no Node accounts, no SortedTrovesState)
+// - No transaction size limits from passing full list
+// - Simpler contract logic (~100 lines vs 668 lines)
+// - Client has full flexibility for sorting strategy
 
 /// Validate that a trove's ICR is correctly ordered between its neighbors
 /// 
@@ -42,7 +42,7 @@ pub fn validate_icr_ordering(
             prev <= trove_icr,
             AerospacerProtocolError::InvalidList
         );
-        msg!("✓ Valid ordering: prev_icr {} <= trove_icr {}", prev, trove_icr);
+        debug_msg!("✓ Valid ordering: prev_icr {} <= trove_icr {}", prev, trove_icr);
     }
     
     // Validate next neighbor: trove_icr <= next_icr
@@ -51,7 +51,7 @@ pub fn validate_icr_ordering(
             trove_icr <= next,
             AerospacerProtocolError::InvalidList
         );
-        msg!("✓ Valid ordering: trove_icr {} <= next_icr {}", trove_icr, next);
+        debug_msg!("✓ Valid ordering: trove_icr {} <= next_icr {}", trove_icr, next);
     }
     
     Ok(())
@@ -95,7 +95,7 @@ pub fn get_liquidatable_troves(
     
     // Validate accounts come in triplets
     require!(
-        remaining_accounts.len() % 3 == 0,
+        remaining_accounts.len().is_multiple_of(3),
         AerospacerProtocolError::InvalidList
     );
     
@@ -155,7 +155,7 @@ pub fn get_liquidatable_troves(
         // This prevents attackers from injecting fabricated accounts with arbitrary ICRs
         verify_liquidity_threshold_pda(lt_account, owner, program_id)?;
         
-        msg!("Trove {}: owner={}, ICR={}", i, owner, current_icr);
+        debug_msg!("Trove {}: owner={}, ICR={}", i, owner, current_icr);
         
         // Validate ascending ICR order (sorted list)
         if let Some(prev) = prev_icr {
@@ -168,11 +168,11 @@ pub fn get_liquidatable_troves(
         // Check if liquidatable
         if current_icr < liquidation_threshold {
             liquidatable.push(owner);
-            msg!("  -> Liquidatable (ICR {} < threshold {})", current_icr, liquidation_threshold);
+            debug_msg!("  -> Liquidatable (ICR {} < threshold {})", current_icr, liquidation_threshold);
             prev_icr = Some(current_icr);
         } else {
             // Sorted list optimization: stop at first safe trove
-            msg!("  -> Safe (ICR {} >= threshold {}). Stopping (sorted list optimization)", current_icr, liquidation_threshold);
+            debug_msg!("  -> Safe (ICR {} >= threshold {}). Stopping (sorted list optimization)", current_icr, liquidation_threshold);
             break;
         }
     }
@@ -1,6 +1,18 @@
 use anchor_lang::prelude::*;
+use crate::error::AerospacerProtocolError;
+use crate::instructions::{UpdateProtocolAddressesParams, UpdateRiskParamsParams};
 
 // Exact replication of INJECTIVE state.rs
+
+// Access-control roles (the owner, stored as `admin`, implicitly holds every role)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    RiskManager,   // Minimum collateral ratio and protocol fee
+    OracleManager, // Oracle program/state addresses
+    FeeManager,    // Fee program/state addresses
+    Guardian,      // Pause operation classes
+}
+
 // Main state account (equivalent to INJECTIVE's ADMIN, ORACLE_HELPER_ADDR, FEE_DISTRIBUTOR_ADDR, MINIMUM_COLLATERAL_RATIO, PROTOCOL_FEE, STABLE_COIN_ADDR, TOTAL_DEBT_AMOUNT, TOTAL_STAKE_AMOUNT)
 #[account]
 pub struct StateAccount {
@@ -19,10 +31,36 @@ pub struct StateAccount {
     // Stability Pool Snapshot Variables (Liquity Product-Sum Algorithm)
     pub p_factor: u128,  // Product/depletion factor - tracks cumulative pool depletion from debt burns (starts at SCALE_FACTOR)
     pub epoch: u64,      // Current epoch - increments when pool is completely depleted to 0
+
+    // Circuit breaker
+    pub guardian: Pubkey, // Guardian role: can pause operation classes without holding the admin key
+    pub paused: u8,       // Bitmask of PAUSE_* operation classes currently halted
+
+    // Global settlement (emergency shutdown)
+    pub is_shutdown: bool,         // Once set, minting/liquidation/redemption stop for good
+    pub shutdown_timestamp: i64,   // When shutdown was triggered (starts the settlement grace period)
+    pub settled_denom_count: u8,   // Number of denoms with a frozen CollateralSettlement price
+
+    // Role holders (admin is the owner and implicitly holds all of them)
+    pub risk_manager: Pubkey,
+    pub oracle_manager: Pubkey,
+    pub fee_manager: Pubkey,
+
+    // Two-step ownership transfer
+    pub pending_admin: Pubkey, // Proposed new admin; Pubkey::default() when no transfer is pending
+
+    // Timelock for parameter changes
+    pub timelock_delay: i64,   // Seconds between queueing and execution; 0 allows direct admin instructions
+    pub next_change_id: u64,   // Id (and PDA seed) of the next QueuedChange
+
+    // Stablecoin precision (debt and stake amounts are in base units of the stablecoin mint)
+    pub stable_coin_decimals: u8, // 0 on accounts created before this field; see stable_decimals()
+
+    pub version: u8, // Layout version (see Versioned); upgraded by migrate_state
 }
 
 impl StateAccount {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 16 + 8; // Added oracle_state_addr + fee_state_addr + stable_coin_code_id, minimum_collateral_ratio now u64
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 16 + 8 + 32 + 1 + 1 + 8 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1; // Added oracle_state_addr + fee_state_addr + stable_coin_code_id, minimum_collateral_ratio now u64, guardian + paused, shutdown fields, roles, pending_admin, timelock, stable_coin_decimals, version
     
     // Scale factor for precision in P/S calculations (10^18, same as Liquity)
     pub const SCALE_FACTOR: u128 = 1_000_000_000_000_000_000;
@@ -30,6 +68,159 @@ impl StateAccount {
     pub fn seeds() -> [&'static [u8]; 1] {
         [b"state"]
     }
+
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused & operation != 0
+    }
+
+    /// Current holder of a role (Pubkey::default() when unassigned)
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::RiskManager => self.risk_manager,
+            Role::OracleManager => self.oracle_manager,
+            Role::FeeManager => self.fee_manager,
+            Role::Guardian => self.guardian,
+        }
+    }
+    
+    /// Assign a role holder (Pubkey::default() revokes it)
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::RiskManager => self.risk_manager = holder,
+            Role::OracleManager => self.oracle_manager = holder,
+            Role::FeeManager => self.fee_manager = holder,
+            Role::Guardian => self.guardian = holder,
+        }
+    }
+    
+    /// Decimals of the stablecoin mint. State accounts created before `stable_coin_decimals`
+    /// existed read 0 from the allocation slack until migrate_state runs, and always used
+    /// the 18-decimal aUSD mint
+    pub fn stable_decimals(&self) -> u8 {
+        if self.stable_coin_decimals == 0 {
+            LEGACY_STABLE_COIN_DECIMALS
+        } else {
+            self.stable_coin_decimals
+        }
+    }
+    
+    /// Smallest loan, redemption or stake: 0.001 stablecoin
+    pub fn minimum_loan_amount(&self) -> u64 {
+        10_u64.pow(self.stable_decimals() as u32 - 3)
+    }
+    
+    /// The owner can act in every role, since it could grant any role to itself anyway
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        *key == self.admin || (*key != Pubkey::default() && *key == self.role_holder(role))
+    }
+}
+
+// Account layout versioning
+// Every account ends with a `version` byte. Accounts written before it existed read 0 there
+// from their allocation slack (LEN counts the discriminator a second time, and strings are
+// reserved at 32 bytes). Later fields are appended after `version` and get their defaults
+// in `upgrade`. The state account is resized and upgraded by the admin via migrate_state;
+// every other account is upgraded in place by the next instruction that writes to it, so a
+// new field must fit in that slack unless it comes with its own resize step
+pub trait Versioned {
+    const VERSION: u8;
+    
+    fn version(&self) -> u8;
+    
+    /// Bring an account written under an older layout up to VERSION.
+    /// Returns false if it was already current
+    fn upgrade(&mut self) -> bool;
+}
+
+// Accounts whose only change so far is the version byte itself
+macro_rules! versioned {
+    ($($account:ty => $version:expr),* $(,)?) => {
+        $(
+            impl Versioned for $account {
+                const VERSION: u8 = $version;
+                
+                fn version(&self) -> u8 {
+                    self.version
+                }
+                
+                fn upgrade(&mut self) -> bool {
+                    if self.version >= Self::VERSION {
+                        return false;
+                    }
+                    self.version = Self::VERSION;
+                    true
+                }
+            }
+        )*
+    };
+}
+
+versioned! {
+    UserDebtAmount => 1,
+    UserCollateralAmount => 1,
+    UserStakeAmount => 1,
+    LiquidityThreshold => 1,
+    TotalCollateralAmount => 1,
+    UserLiquidationCollateralGain => 1,
+    TotalLiquidationCollateralGain => 1,
+    StabilityPoolSnapshot => 1,
+    UserCollateralSnapshot => 1,
+}
+
+impl Versioned for QueuedChange {
+    const VERSION: u8 = 2;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v2: the change is stored as length-prefixed bytes, so v1 queued changes no longer
+        // load; drain the queue before upgrading (see ACCOUNT_MIGRATION.md)
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for CollateralSettlement {
+    const VERSION: u8 = 2;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v2: the price is normalized and 8 bytes wider, so v1 settlements no longer load;
+        // programs are not upgraded during a shutdown (see ACCOUNT_MIGRATION.md)
+        self.version = Self::VERSION;
+        true
+    }
+}
+
+impl Versioned for StateAccount {
+    const VERSION: u8 = 1;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn upgrade(&mut self) -> bool {
+        if self.version >= Self::VERSION {
+            return false;
+        }
+        // v0 -> v1: record the decimals of the mint every pre-v1 deployment used
+        if self.stable_coin_decimals == 0 {
+            self.stable_coin_decimals = LEGACY_STABLE_COIN_DECIMALS;
+        }
+        self.version = Self::VERSION;
+        true
+    }
 }
 
 // User debt amount (equivalent to INJECTIVE's USER_DEBT_AMOUNT: Map<Addr, Uint256>)
@@ -38,10 +229,11 @@ pub struct UserDebtAmount {
     pub owner: Pubkey,
     pub amount: u64,
     pub l_debt_snapshot: u128,
+    pub version: u8,
 }
 
 impl UserDebtAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16;
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 1;
     pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
         [b"user_debt_amount", owner.as_ref()]
     }
@@ -54,10 +246,11 @@ pub struct UserCollateralAmount {
     pub denom: String,
     pub amount: u64,
     pub l_collateral_snapshot: u128,
+    pub version: u8,
 }
 
 impl UserCollateralAmount {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 16;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 16 + 1;
     pub fn seeds<'a>(owner: &'a Pubkey, denom: &'a str) -> [&'a [u8]; 3] {
         [b"user_collateral_amount", owner.as_ref(), denom.as_bytes()]
     }
@@ -71,10 +264,11 @@ pub struct UserStakeAmount {
     pub p_snapshot: u128,               // User's P factor snapshot at last deposit (for compounded stake calculation)
     pub epoch_snapshot: u64,            // Epoch when user last deposited (for epoch transition tracking)
     pub last_update_block: u64,         // Last block when stake was updated
+    pub version: u8,
 }
 
 impl UserStakeAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16 + 8 + 8; // Added p_snapshot(16) + epoch_snapshot(8) + last_update_block(8)
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 8 + 8 + 1; // Added p_snapshot(16) + epoch_snapshot(8) + last_update_block(8) + version(1)
     pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
         [b"user_stake_amount", owner.as_ref()]
     }
@@ -85,10 +279,11 @@ impl UserStakeAmount {
 pub struct LiquidityThreshold {
     pub owner: Pubkey,
     pub ratio: u64, // Equivalent to Decimal256
+    pub version: u8,
 }
 
 impl LiquidityThreshold {
-    pub const LEN: usize = 8 + 32 + 8;
+    pub const LEN: usize = 8 + 32 + 8 + 1;
     pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
         [b"liquidity_threshold", owner.as_ref()]
     }
@@ -101,10 +296,11 @@ pub struct TotalCollateralAmount {
     pub amount: u64,
     pub l_collateral: u128,
     pub l_debt: u128,
+    pub version: u8,
 }
 
 impl TotalCollateralAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16 + 16;
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 16 + 1;
     pub fn seeds(denom: &str) -> [&[u8]; 2] {
         [b"total_collateral_amount", denom.as_bytes()]
     }
@@ -116,10 +312,11 @@ pub struct UserLiquidationCollateralGain {
     pub user: Pubkey,
     pub block_height: u64,
     pub claimed: bool,
+    pub version: u8,
 }
 
 impl UserLiquidationCollateralGain {
-    pub const LEN: usize = 8 + 32 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 1;
     pub fn seeds(user: &Pubkey, block_height: u64) -> [&[u8]; 3] {
         let block_height_bytes = Box::leak(block_height.to_le_bytes().to_vec().into_boxed_slice());
         [b"user_liq_gain", user.as_ref(), block_height_bytes]
@@ -132,10 +329,11 @@ pub struct TotalLiquidationCollateralGain {
     pub block_height: u64,
     pub denom: String,
     pub amount: u64, // Equivalent to Uint256
+    pub version: u8,
 }
 
 impl TotalLiquidationCollateralGain {
-    pub const LEN: usize = 8 + 8 + 32 + 8; // String length needs to be considered
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 1; // String length needs to be considered
     pub fn seeds(block_height: u64, denom: &str) -> [&[u8]; 3] {
         let block_height_bytes = Box::leak(block_height.to_le_bytes().to_vec().into_boxed_slice());
         [b"total_liq_gain", block_height_bytes, denom.as_bytes()]
@@ -157,10 +355,11 @@ pub struct StabilityPoolSnapshot {
     pub s_factor: u128,                 // Sum: cumulative collateral-per-unit-staked (scaled by SCALE_FACTOR)
     pub total_collateral_gained: u64,  // Total collateral seized and distributed this epoch
     pub epoch: u64,                     // Current epoch (resets when pool depletes to 0)
+    pub version: u8,
 }
 
 impl StabilityPoolSnapshot {
-    pub const LEN: usize = 8 + 32 + 16 + 8 + 8; // denom(32) + s_factor(16) + total(8) + epoch(8)
+    pub const LEN: usize = 8 + 32 + 16 + 8 + 8 + 1; // denom(32) + s_factor(16) + total(8) + epoch(8) + version(1)
     
     pub fn seeds(denom: &str) -> [&[u8]; 2] {
         [b"stability_pool_snapshot", denom.as_bytes()]
@@ -175,22 +374,116 @@ pub struct UserCollateralSnapshot {
     pub denom: String,
     pub s_snapshot: u128,               // User's S factor snapshot at last deposit
     pub pending_collateral_gain: u64,  // Unclaimed gains from previous epochs
+    pub version: u8,
 }
 
 impl UserCollateralSnapshot {
-    pub const LEN: usize = 8 + 32 + 32 + 16 + 8; // owner(32) + denom(32) + s_snapshot(16) + pending(8)
+    pub const LEN: usize = 8 + 32 + 32 + 16 + 8 + 1; // owner(32) + denom(32) + s_snapshot(16) + pending(8) + version(1)
     
     pub fn seeds<'a>(owner: &'a Pubkey, denom: &'a str) -> [&'a [u8]; 3] {
         [b"user_collateral_snapshot", owner.as_ref(), denom.as_bytes()]
     }
 }
 
+// Collateral settlement - frozen price and aUSD backing for one denom after emergency shutdown
+// Trove debt is settled against this price; the collateral taken from troves becomes
+// `backing_collateral`, which aUSD holders claim pro rata via redeem_settlement
+#[account]
+pub struct CollateralSettlement {
+    pub denom: String,
+    pub price: u128,                    // Final normalized oracle price (18 decimals), frozen at shutdown
+    pub decimal: u8,                    // Collateral token decimals
+    pub fixed_at: i64,                  // Timestamp the price was frozen
+    pub backing_collateral: u64,        // Collateral reserved for aUSD holders (skimmed from settled troves)
+    pub version: u8,
+}
+
+impl CollateralSettlement {
+    pub const LEN: usize = 8 + 32 + 16 + 1 + 8 + 8 + 1; // denom(32) + price(16) + decimal(1) + fixed_at(8) + backing(8) + version(1)
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"collateral_settlement", denom.as_bytes()]
+    }
+}
+
+// Parameter change waiting in the timelock; anyone can execute it once `eta` has passed,
+// and the guardian can cancel it before then. Rent goes back to the proposer either way
+#[account]
+pub struct QueuedChange {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub eta: i64,                       // Earliest execution timestamp
+    pub change_version: u8,             // CHANGE_VERSION the payload was encoded under
+    pub change: Vec<u8>,                // Borsh-encoded TimelockedChange, still loadable after its shape changes
+    pub version: u8,
+}
+
+impl QueuedChange {
+    // discriminator + id + proposer + queued_at + eta + change_version + payload length + version
+    pub const BASE_LEN: usize = 8 + 8 + 32 + 8 + 8 + 1 + 4 + 1;
+    
+    // Encoding of TimelockedChange payloads; bump it whenever a variant or its parameters change shape
+    pub const CHANGE_VERSION: u8 = 1;
+    
+    // Payload size varies by variant, so the account is sized to the change it holds
+    pub fn space(change: &TimelockedChange) -> usize {
+        Self::BASE_LEN + change.try_to_vec().map_or(0, |data| data.len())
+    }
+    
+    pub fn set_change(&mut self, change: &TimelockedChange) -> Result<()> {
+        self.change_version = Self::CHANGE_VERSION;
+        self.change = change.try_to_vec()?;
+        Ok(())
+    }
+    
+    // A change queued under another encoding has to be cancelled and queued again
+    pub fn change(&self) -> Result<TimelockedChange> {
+        require!(
+            self.change_version == Self::CHANGE_VERSION,
+            AerospacerProtocolError::StaleQueuedChange
+        );
+        TimelockedChange::try_from_slice(&self.change)
+            .map_err(|_| AerospacerProtocolError::StaleQueuedChange.into())
+    }
+}
+
+// Parameter changes that go through the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TimelockedChange {
+    UpdateProtocolAddresses(UpdateProtocolAddressesParams), // Oracle fields need the oracle manager, fee fields the fee manager
+    SetTimelockDelay(i64),                                  // Owner only
+    UpdateRiskParams(UpdateRiskParamsParams),               // Risk manager
+}
+
 // Constants to match INJECTIVE exactly
-pub const MINIMUM_LOAN_AMOUNT: u64 = 1_000_000_000_000_000; // 0.001 aUSD with 18 decimals
 pub const MINIMUM_COLLATERAL_AMOUNT: u64 = 1_000_000; // 0.001 SOL with 9 decimals
 pub const DEFAULT_MINIMUM_COLLATERAL_RATIO: u64 = 115_000_000; // 115% in micro-percent (115 * 1_000_000)
 pub const DEFAULT_PROTOCOL_FEE: u8 = 5; // 5%
 
+// Supported stablecoin mint decimals. Debt and stake amounts are u64 base units, so an
+// 18-decimal mint caps total debt at ~18.4 aUSD; 6 decimals allows ~18.4 trillion aUSD.
+// The lower bound keeps debt at least as precise as the micro-USD collateral values
+pub const MIN_STABLE_COIN_DECIMALS: u8 = 6;
+pub const MAX_STABLE_COIN_DECIMALS: u8 = 18;
+pub const LEGACY_STABLE_COIN_DECIMALS: u8 = 18; // Mint used by deployments that predate stable_coin_decimals
+
+// Circuit breaker operation classes (bits of StateAccount.paused)
+// Only inflows are pausable: debt-reducing actions (repay_loan, add_collateral, close_trove)
+// and exits from the stability pool (unstake, withdraw_liquidation_gains) always stay open
+pub const PAUSE_MINTING: u8 = 1 << 0;               // open_trove, borrow_loan
+pub const PAUSE_COLLATERAL_WITHDRAWAL: u8 = 1 << 1; // remove_collateral
+pub const PAUSE_REDEMPTION: u8 = 1 << 2;            // redeem
+pub const PAUSE_LIQUIDATION: u8 = 1 << 3;           // liquidate_trove, liquidate_troves
+pub const PAUSE_STAKING: u8 = 1 << 4;               // stake
+pub const PAUSE_ALL: u8 = PAUSE_MINTING | PAUSE_COLLATERAL_WITHDRAWAL | PAUSE_REDEMPTION | PAUSE_LIQUIDATION | PAUSE_STAKING;
+
+// Time after shutdown during which troves are settled before aUSD holders can redeem (72 hours)
+pub const SETTLEMENT_GRACE_PERIOD: i64 = 72 * 60 * 60;
+
+// Upper bound for the timelock delay (30 days)
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+
 // Decimal fractions to match INJECTIVE
 pub const DECIMAL_FRACTION_6: u128 = 1_000_000;
 pub const DECIMAL_FRACTION_18: u128 = 1_000_000_000_000_000_000;
\ No newline at end of file
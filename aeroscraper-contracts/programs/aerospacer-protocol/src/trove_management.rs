@@ -4,8 +4,8 @@ use crate::error::*;
 use crate::oracle::*;
 use crate::account_management::*;
 
-/// Trove management utilities
-/// This module provides clean, type-safe trove operations
+// Trove management utilities
+// This module provides clean, type-safe trove operations
 
 /// Trove operation result
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -14,6 +14,7 @@ pub struct TroveOperationResult {
     pub new_debt_amount: u64,
     pub new_collateral_amount: u64,
     pub new_icr: u64,
+    pub price: u128,       // Normalized oracle price the ICR was computed at (0 if no price was needed)
     pub message: String,
 }
 
@@ -41,7 +42,7 @@ impl TroveManager {
     ) -> Result<TroveOperationResult> {
         // Validate minimum amounts
         require!(
-            loan_amount >= MINIMUM_LOAN_AMOUNT,
+            loan_amount >= trove_ctx.state.minimum_loan_amount(),
             AerospacerProtocolError::LoanAmountBelowMinimum
         );
         
@@ -51,35 +52,40 @@ impl TroveManager {
         );
         
         // Get collateral price
-        let price_data = oracle_ctx.get_price(&collateral_denom)?;
-        oracle_ctx.validate_price(&price_data)?;
+        let price_data = oracle_ctx.get_price_for(&collateral_denom, PricePurpose::Borrow)?;
+        price_data.asset_state.check(TroveAction::Borrow)?;
+        oracle_ctx.validate_price(&price_data, PriceUse::Live)?;
         
-        // Calculate collateral value using proper price data
-        let collateral_value = PriceCalculator::calculate_collateral_value(
+        debug_msg!("DEBUG - Collateral amount: {}", collateral_amount);
+        debug_msg!("DEBUG - Price: {}", price_data.price);
+        debug_msg!("DEBUG - Price decimal: {}", price_data.decimal);
+        debug_msg!("DEBUG - Loan amount: {}", loan_amount);
+        
+        // Recorded ICR at the spot price, which orders troves the way liquidation sees them
+        let icr = PriceCalculator::calculate_icr(
             collateral_amount,
-            price_data.price as u64, // Convert i64 to u64
-            price_data.decimal,
+            loan_amount,
+            &price_data,
+            Valuation::Spot,
+            trove_ctx.state.stable_decimals(),
         )?;
         
-        msg!("DEBUG - Collateral amount: {}", collateral_amount);
-        msg!("DEBUG - Price: {}", price_data.price);
-        msg!("DEBUG - Price decimal: {}", price_data.decimal);
-        msg!("DEBUG - Calculated collateral value: {}", collateral_value);
-        msg!("DEBUG - Loan amount: {}", loan_amount);
-        
-        // Calculate ICR using proper calculation
-        let icr = PriceCalculator::calculate_collateral_ratio(
-            collateral_value,
+        // Borrowing has to clear the MCR at the conservative price
+        let borrow_icr = PriceCalculator::calculate_icr(
+            collateral_amount,
             loan_amount,
+            &price_data,
+            Valuation::Conservative,
+            trove_ctx.state.stable_decimals(),
         )?;
         
-        msg!("DEBUG - Calculated ICR: {}", icr);
-        msg!("DEBUG - Minimum ICR required: {}", trove_ctx.state.minimum_collateral_ratio);
+        debug_msg!("DEBUG - Calculated ICR: {} (conservative {})", icr, borrow_icr);
+        debug_msg!("DEBUG - Minimum ICR required: {}", trove_ctx.state.minimum_collateral_ratio);
         
         // Check minimum collateral ratio
-        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio as u64;
+        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio;
         require!(
-            icr >= minimum_ratio,
+            borrow_icr >= minimum_ratio,
             AerospacerProtocolError::CollateralBelowMinimum
         );
         
@@ -104,6 +110,7 @@ impl TroveManager {
             new_debt_amount: loan_amount,
             new_collateral_amount: collateral_amount,
             new_icr: icr,
+            price: price_data.normalized_price,
             message: "Trove opened successfully".to_string(),
         })
     }
@@ -129,28 +136,25 @@ impl TroveManager {
         
         // Get collateral price
         let price_data = oracle_ctx.get_price(&collateral_denom)?;
-        oracle_ctx.validate_price(&price_data)?;
+        price_data.asset_state.check(TroveAction::AddCollateral)?;
+        oracle_ctx.validate_price(&price_data, PriceUse::RiskReducing)?;
         
         // Calculate new collateral amount
         let new_collateral_amount = collateral_info.amount
             .checked_add(additional_amount)
             .ok_or(AerospacerProtocolError::OverflowError)?;
         
-        // Calculate new collateral value
-        let new_collateral_value = PriceCalculator::calculate_collateral_value(
-            new_collateral_amount,
-            price_data.price as u64, // Convert i64 to u64
-            price_data.decimal,
-        )?;
-        
         // Calculate new ICR
-        let new_icr = PriceCalculator::calculate_collateral_ratio(
-            new_collateral_value,
+        let new_icr = PriceCalculator::calculate_icr(
+            new_collateral_amount,
             trove_info.debt_amount,
+            &price_data,
+            Valuation::Spot,
+            trove_ctx.state.stable_decimals(),
         )?;
         
         // Check minimum collateral ratio (both are simple percentages)
-        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio as u64;
+        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio;
         require!(
             new_icr >= minimum_ratio,
             AerospacerProtocolError::CollateralBelowMinimum
@@ -168,8 +172,9 @@ impl TroveManager {
         Ok(TroveOperationResult {
             success: true,
             new_debt_amount: trove_info.debt_amount,
-            new_collateral_amount: new_collateral_amount,
-            new_icr: new_icr,
+            new_collateral_amount,
+            new_icr,
+            price: price_data.normalized_price,
             message: "Collateral added successfully".to_string(),
         })
     }
@@ -201,8 +206,9 @@ impl TroveManager {
         );
         
         // Get collateral price
-        let price_data = oracle_ctx.get_price(&collateral_denom)?;
-        oracle_ctx.validate_price(&price_data)?;
+        let price_data = oracle_ctx.get_price_for(&collateral_denom, PricePurpose::Withdraw)?;
+        price_data.asset_state.check(TroveAction::RemoveCollateral)?;
+        oracle_ctx.validate_price(&price_data, PriceUse::Live)?;
         
         // Calculate new collateral amount
         let new_collateral_amount = collateral_info.amount
@@ -215,23 +221,28 @@ impl TroveManager {
             AerospacerProtocolError::CollateralBelowMinimum
         );
         
-        // Calculate new collateral value
-        let new_collateral_value = PriceCalculator::calculate_collateral_value(
+        // Calculate new ICR (recorded at the spot price)
+        let new_icr = PriceCalculator::calculate_icr(
             new_collateral_amount,
-            price_data.price as u64, // Convert i64 to u64
-            price_data.decimal,
+            trove_info.debt_amount,
+            &price_data,
+            Valuation::Spot,
+            trove_ctx.state.stable_decimals(),
         )?;
         
-        // Calculate new ICR
-        let new_icr = PriceCalculator::calculate_collateral_ratio(
-            new_collateral_value,
+        // Withdrawing has to clear the MCR at the conservative price
+        let withdraw_icr = PriceCalculator::calculate_icr(
+            new_collateral_amount,
             trove_info.debt_amount,
+            &price_data,
+            Valuation::Conservative,
+            trove_ctx.state.stable_decimals(),
         )?;
         
         // Check minimum collateral ratio (both are simple percentages)
-        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio as u64;
+        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio;
         require!(
-            new_icr >= minimum_ratio,
+            withdraw_icr >= minimum_ratio,
             AerospacerProtocolError::CollateralBelowMinimum
         );
         
@@ -246,8 +257,9 @@ impl TroveManager {
         Ok(TroveOperationResult {
             success: true,
             new_debt_amount: trove_info.debt_amount,
-            new_collateral_amount: new_collateral_amount,
-            new_icr: new_icr,
+            new_collateral_amount,
+            new_icr,
+            price: price_data.normalized_price,
             message: "Collateral removed successfully".to_string(),
         })
     }
@@ -276,50 +288,58 @@ impl TroveManager {
             .ok_or(AerospacerProtocolError::OverflowError)?;
         
         // Get collateral price
-        msg!("📊 [borrow_loan] Getting oracle price for denom: {}", collateral_info.denom);
-        let price_data = oracle_ctx.get_price(&collateral_info.denom)?;
-        oracle_ctx.validate_price(&price_data)?;
-        
-        msg!("📊 [borrow_loan] Oracle price data:");
-        msg!("  denom: {}", price_data.denom);
-        msg!("  price: {}", price_data.price);
-        msg!("  decimal: {}", price_data.decimal);
-        msg!("  exponent: {}", price_data.exponent);
-        msg!("  confidence: {}", price_data.confidence);
+        debug_msg!("📊 [borrow_loan] Getting oracle price for denom: {}", collateral_info.denom);
+        let price_data = oracle_ctx.get_price_for(&collateral_info.denom, PricePurpose::Borrow)?;
+        price_data.asset_state.check(TroveAction::Borrow)?;
+        oracle_ctx.validate_price(&price_data, PriceUse::Live)?;
+        
+        debug_msg!("📊 [borrow_loan] Oracle price data:");
+        debug_msg!("  denom: {}", price_data.denom);
+        debug_msg!("  price: {}", price_data.price);
+        debug_msg!("  decimal: {}", price_data.decimal);
+        debug_msg!("  exponent: {}", price_data.exponent);
+        debug_msg!("  confidence: {}", price_data.confidence);
         
         // Calculate collateral value
-        msg!("📊 [borrow_loan] Calculating collateral value:");
-        msg!("  collateral_amount: {}", collateral_info.amount);
-        msg!("  new_debt_amount: {}", new_debt_amount);
+        debug_msg!("📊 [borrow_loan] Calculating collateral value:");
+        debug_msg!("  collateral_amount: {}", collateral_info.amount);
+        debug_msg!("  new_debt_amount: {}", new_debt_amount);
         
-        let collateral_value = PriceCalculator::calculate_collateral_value(
+        // Calculate new ICR (recorded at the spot price)
+        let new_icr = PriceCalculator::calculate_icr(
             collateral_info.amount,
-            price_data.price as u64, // Convert i64 to u64
-            price_data.decimal,
+            new_debt_amount,
+            &price_data,
+            Valuation::Spot,
+            trove_ctx.state.stable_decimals(),
         )?;
         
-        // Calculate new ICR
-        let new_icr = PriceCalculator::calculate_collateral_ratio(
-            collateral_value,
+        // Borrowing has to clear the MCR at the conservative price
+        let borrow_icr = PriceCalculator::calculate_icr(
+            collateral_info.amount,
             new_debt_amount,
+            &price_data,
+            Valuation::Conservative,
+            trove_ctx.state.stable_decimals(),
         )?;
         
         // Check minimum collateral ratio
-        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio as u64;
-        msg!("📊 [borrow_loan] ICR Check:");
-        msg!("  new_icr (micro-percent): {}", new_icr);
-        msg!("  new_icr (human-readable): {}.{}%", new_icr / 1_000_000, (new_icr % 1_000_000) / 10_000);
-        msg!("  minimum_ratio (micro-percent): {}", minimum_ratio);
-        msg!("  minimum_ratio (human-readable): {}%", minimum_ratio / 1_000_000);
-        
-        if new_icr < minimum_ratio {
-            msg!("❌ ICR {} < MCR {} → CollateralBelowMinimum", new_icr, minimum_ratio);
+        let minimum_ratio = trove_ctx.state.minimum_collateral_ratio;
+        debug_msg!("📊 [borrow_loan] ICR Check:");
+        debug_msg!("  new_icr (micro-percent): {}", new_icr);
+        debug_msg!("  borrow_icr (conservative, micro-percent): {}", borrow_icr);
+        debug_msg!("  new_icr (human-readable): {}.{}%", new_icr / 1_000_000, (new_icr % 1_000_000) / 10_000);
+        debug_msg!("  minimum_ratio (micro-percent): {}", minimum_ratio);
+        debug_msg!("  minimum_ratio (human-readable): {}%", minimum_ratio / 1_000_000);
+        
+        if borrow_icr < minimum_ratio {
+            debug_msg!("❌ ICR {} < MCR {} → CollateralBelowMinimum", borrow_icr, minimum_ratio);
         } else {
-            msg!("✅ ICR {} >= MCR {} → Check passed", new_icr, minimum_ratio);
+            debug_msg!("✅ ICR {} >= MCR {} → Check passed", borrow_icr, minimum_ratio);
         }
         
         require!(
-            new_icr >= minimum_ratio,
+            borrow_icr >= minimum_ratio,
             AerospacerProtocolError::CollateralBelowMinimum
         );
         
@@ -336,9 +356,10 @@ impl TroveManager {
         
         Ok(TroveOperationResult {
             success: true,
-            new_debt_amount: new_debt_amount,
+            new_debt_amount,
             new_collateral_amount: collateral_info.amount,
-            new_icr: new_icr,
+            new_icr,
+            price: price_data.normalized_price,
             message: "Loan borrowed successfully".to_string(),
         })
     }
@@ -368,6 +389,12 @@ impl TroveManager {
             AerospacerProtocolError::InvalidAmount
         );
         
+        // A full repayment reads no price, so the asset's state is asked for separately.
+        // Troves on an asset the oracle has removed can still be repaid in full
+        if !oracle_ctx.is_asset_removed(&collateral_info.denom) {
+            oracle_ctx.get_asset_state(&collateral_info.denom)?.check(TroveAction::Repay)?;
+        }
+        
         // Calculate new debt amount
         let new_debt_amount = trove_info.debt_amount
             .checked_sub(repay_amount)
@@ -394,25 +421,22 @@ impl TroveManager {
                 new_debt_amount: 0,
                 new_collateral_amount: 0,
                 new_icr: 0,
+                price: 0,
                 message: "Trove fully repaid and closed".to_string(),
             })
         } else {
             // Partial repayment
             // Get collateral price for ICR calculation
             let price_data = oracle_ctx.get_price(&collateral_info.denom)?;
-            oracle_ctx.validate_price(&price_data)?;
-            
-            // Calculate collateral value
-            let collateral_value = PriceCalculator::calculate_collateral_value(
-                collateral_info.amount,
-                price_data.price as u64, // Convert i64 to u64
-                price_data.decimal,
-            )?;
+            oracle_ctx.validate_price(&price_data, PriceUse::RiskReducing)?;
             
             // Calculate new ICR
-            let new_icr = PriceCalculator::calculate_collateral_ratio(
-                collateral_value,
+            let new_icr = PriceCalculator::calculate_icr(
+                collateral_info.amount,
                 new_debt_amount,
+                &price_data,
+                Valuation::Spot,
+                trove_ctx.state.stable_decimals(),
             )?;
             
             // Update accounts
@@ -423,9 +447,10 @@ impl TroveManager {
             
             Ok(TroveOperationResult {
                 success: true,
-                new_debt_amount: new_debt_amount,
+                new_debt_amount,
                 new_collateral_amount: collateral_info.amount,
-                new_icr: new_icr,
+                new_icr,
+                price: price_data.normalized_price,
                 message: "Partial repayment successful".to_string(),
             })
         }
@@ -450,7 +475,7 @@ impl TroveManager {
             let trove_data = parse_trove_data(user, i, remaining_accounts)?;
             
             // Validate trove is actually undercollateralized
-            validate_trove_for_liquidation(&trove_data, oracle_ctx)?;
+            validate_trove_for_liquidation(&trove_data, oracle_ctx, liquidation_ctx.state.stable_decimals())?;
             
             // Calculate liquidation gains
             let mut trove_collateral_gain = 0u64;
@@ -486,7 +511,7 @@ impl TroveManager {
             
             // Note: Sorted list operations happen in instruction handler via sorted_troves_simple
             
-            msg!("Liquidated trove: user={}, debt={}, collateral={}", 
+            debug_msg!("Liquidated trove: user={}, debt={}, collateral={}", 
                  user, trove_data.debt_amount, trove_collateral_gain);
         }
         
@@ -639,16 +664,20 @@ fn validate_token_account(account_info: &AccountInfo, _expected_user: &Pubkey) -
 }
 
 /// Validate that a trove is actually undercollateralized and can be liquidated
-fn validate_trove_for_liquidation(trove_data: &TroveData, oracle_ctx: &OracleContext) -> Result<()> {
+fn validate_trove_for_liquidation(trove_data: &TroveData, oracle_ctx: &OracleContext, stable_decimals: u8) -> Result<()> {
     // Calculate current collateral value
     let mut total_collateral_value = 0u64;
     
     for (denom, amount) in &trove_data.collateral_amounts {
-        let price_data = oracle_ctx.get_price(denom)?;
+        let price_data = oracle_ctx.get_price_for(denom, PricePurpose::Liquidation)?;
+        price_data.asset_state.check(TroveAction::Liquidate)?;
+        oracle_ctx.validate_price(&price_data, PriceUse::Live)?;
         let collateral_value = PriceCalculator::calculate_collateral_value(
             *amount,
-            price_data.price as u64,
+            price_data.normalized_price,
+            price_data.normalized_confidence,
             price_data.decimal,
+            Valuation::Spot,
         )?;
         total_collateral_value = total_collateral_value.saturating_add(collateral_value);
     }
@@ -657,6 +686,7 @@ fn validate_trove_for_liquidation(trove_data: &TroveData, oracle_ctx: &OracleCon
     let current_icr = PriceCalculator::calculate_collateral_ratio(
         total_collateral_value,
         trove_data.debt_amount,
+        stable_decimals,
     )?;
     
     // Check if trove is undercollateralized (ICR < 110%)
@@ -667,7 +697,7 @@ fn validate_trove_for_liquidation(trove_data: &TroveData, oracle_ctx: &OracleCon
         AerospacerProtocolError::CollateralBelowMinimum // Reuse error for now
     );
     
-    msg!("Trove validated for liquidation: ICR={}, threshold={}", 
+    debug_msg!("Trove validated for liquidation: ICR={}, threshold={}", 
          current_icr, liquidation_threshold);
     
     Ok(())
@@ -702,7 +732,7 @@ fn update_user_accounts_after_liquidation(
     liquidity_threshold.ratio = 0;
     liquidity_threshold.serialize(&mut &mut liquidity_data[..])?;
     
-    msg!("Updated user accounts after liquidation: user={}", user);
+    debug_msg!("Updated user accounts after liquidation: user={}", user);
     
     Ok(())
 }
@@ -729,15 +759,15 @@ pub fn distribute_liquidation_gains_to_stakers(
 ) -> Result<()> {
     let total_stake = state.total_stake_amount;
     
-    msg!("Distributing liquidation gains to stability pool (snapshot algorithm):");
-    msg!("  Total stake in pool: {}", total_stake);
-    msg!("  Debt liquidated: {}", debt_amount);
-    msg!("  Current P factor: {}", state.p_factor);
-    msg!("  Current epoch: {}", state.epoch);
+    debug_msg!("Distributing liquidation gains to stability pool (snapshot algorithm):");
+    debug_msg!("  Total stake in pool: {}", total_stake);
+    debug_msg!("  Debt liquidated: {}", debt_amount);
+    debug_msg!("  Current P factor: {}", state.p_factor);
+    debug_msg!("  Current epoch: {}", state.epoch);
     
     // If no stakers, collateral stays in vault (no distribution needed)
     if total_stake == 0 {
-        msg!("  No stakers - seized collateral remains in protocol vault");
+        debug_msg!("  No stakers - seized collateral remains in protocol vault");
         return Ok(());
     }
     
@@ -752,8 +782,8 @@ pub fn distribute_liquidation_gains_to_stakers(
             .ok_or(AerospacerProtocolError::OverflowError)?;
         state.p_factor = StateAccount::SCALE_FACTOR;
         state.total_stake_amount = 0;
-        msg!("  Pool depleted to 0 - starting epoch {}", state.epoch);
-        msg!("  P factor reset to SCALE_FACTOR");
+        debug_msg!("  Pool depleted to 0 - starting epoch {}", state.epoch);
+        debug_msg!("  P factor reset to SCALE_FACTOR");
     } else {
         // Calculate depletion ratio: (remaining_stake / total_stake)
         let depletion_ratio = (remaining_stake as u128)
@@ -771,8 +801,8 @@ pub fn distribute_liquidation_gains_to_stakers(
         
         state.total_stake_amount = remaining_stake;
         
-        msg!("  Updated P factor: {} (depletion ratio: {})", state.p_factor, depletion_ratio);
-        msg!("  Remaining stake: {}", remaining_stake);
+        debug_msg!("  Updated P factor: {} (depletion ratio: {})", state.p_factor, depletion_ratio);
+        debug_msg!("  Remaining stake: {}", remaining_stake);
     }
     
     // STEP 2: Update S factor for the collateral type (tracks cumulative rewards)
@@ -802,11 +832,11 @@ pub fn distribute_liquidation_gains_to_stakers(
         
         stability_pool_snapshot.epoch = state.epoch;
         
-        msg!("  Updated S factor for {}: +{} (new S: {})", 
+        debug_msg!("  Updated S factor for {}: +{} (new S: {})", 
              denom, s_increment, stability_pool_snapshot.s_factor);
     }
     
-    msg!("Liquidation gains distribution complete (snapshot algorithm)");
+    debug_msg!("Liquidation gains distribution complete (snapshot algorithm)");
     
     Ok(())
 }
@@ -870,7 +900,7 @@ pub fn apply_pending_rewards(
             .ok_or(AerospacerProtocolError::OverflowError)?;
         user_debt.l_debt_snapshot = l_debt;
         
-        msg!("Applied pending debt reward: +{} (new debt: {})", pending_debt_reward, user_debt.amount);
+        debug_msg!("Applied pending debt reward: +{} (new debt: {})", pending_debt_reward, user_debt.amount);
     }
     
     if pending_collateral_reward > 0 {
@@ -879,7 +909,7 @@ pub fn apply_pending_rewards(
             .ok_or(AerospacerProtocolError::OverflowError)?;
         user_collateral.l_collateral_snapshot = l_collateral;
         
-        msg!("Applied pending collateral reward: +{} (new collateral: {})", 
+        debug_msg!("Applied pending collateral reward: +{} (new collateral: {})", 
              pending_collateral_reward, user_collateral.amount);
     }
     
@@ -899,10 +929,10 @@ pub fn redistribute_debt_and_collateral(
         AerospacerProtocolError::InvalidAmount
     );
     
-    msg!("Redistributing debt and collateral to active troves:");
-    msg!("  Total active collateral in system: {}", total_collateral_in_system);
-    msg!("  Debt to redistribute: {}", debt_to_redistribute);
-    msg!("  Collateral to redistribute: {}", collateral_to_redistribute);
+    debug_msg!("Redistributing debt and collateral to active troves:");
+    debug_msg!("  Total active collateral in system: {}", total_collateral_in_system);
+    debug_msg!("  Debt to redistribute: {}", debt_to_redistribute);
+    debug_msg!("  Collateral to redistribute: {}", collateral_to_redistribute);
     
     let debt_per_unit_staked = (debt_to_redistribute as u128)
         .checked_mul(StateAccount::SCALE_FACTOR)
@@ -927,9 +957,160 @@ pub fn redistribute_debt_and_collateral(
     state.total_debt_amount = state.total_debt_amount
         .saturating_sub(debt_to_redistribute);
     
-    msg!("  New L_debt: {}", total_collateral.l_debt);
-    msg!("  New L_collateral: {}", total_collateral.l_collateral);
-    msg!("Redistribution complete - gains will be applied to troves on next operation");
+    debug_msg!("  New L_debt: {}", total_collateral.l_debt);
+    debug_msg!("  New L_collateral: {}", total_collateral.l_collateral);
+    debug_msg!("Redistribution complete - gains will be applied to troves on next operation");
     
     Ok(())
 }
+
+/// Settle a trove's debt against the frozen shutdown price
+/// 
+/// Takes collateral worth the trove's debt (or all of it if the trove is underwater)
+/// and moves it into the settlement's aUSD backing. The trove is left debt-free with
+/// only its excess collateral, which the owner can then withdraw.
+/// 
+/// Pending redistribution rewards must be applied by the caller beforehand.
+/// Returns the amount of collateral moved into the backing.
+pub fn settle_trove_at_frozen_price(
+    user_debt: &mut UserDebtAmount,
+    user_collateral: &mut UserCollateralAmount,
+    settlement: &mut CollateralSettlement,
+    total_collateral: &mut TotalCollateralAmount,
+    stable_decimals: u8,
+) -> Result<u64> {
+    let debt_amount = user_debt.amount;
+    if debt_amount == 0 {
+        return Ok(0);
+    }
+    
+    let collateral_for_debt = PriceCalculator::calculate_collateral_for_debt(
+        debt_amount,
+        settlement.price,
+        settlement.decimal,
+        stable_decimals,
+    )?;
+    let skimmed = collateral_for_debt.min(user_collateral.amount);
+    
+    user_collateral.amount = user_collateral.amount
+        .checked_sub(skimmed)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    user_debt.amount = 0;
+    
+    settlement.backing_collateral = settlement.backing_collateral
+        .checked_add(skimmed)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    total_collateral.amount = total_collateral.amount
+        .checked_sub(skimmed)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    debug_msg!("Settled trove {}: debt {} covered by {} {}", user_debt.owner, debt_amount, skimmed, settlement.denom);
+    if skimmed < collateral_for_debt {
+        debug_msg!("  Trove was undercollateralized at the frozen price (shortfall {} {})", collateral_for_debt - skimmed, settlement.denom);
+    }
+    
+    Ok(skimmed)
+}
+
+// Share of one denom's settlement backing paid out for `amount` aUSD: backing × amount / outstanding supply
+pub fn settlement_payout(backing_collateral: u64, amount: u64, outstanding_supply: u64) -> Result<u64> {
+    let payout = (backing_collateral as u128)
+        .checked_mul(amount as u128)
+        .ok_or(AerospacerProtocolError::OverflowError)?
+        .checked_div(outstanding_supply as u128)
+        .ok_or(AerospacerProtocolError::DivideByZeroError)?;
+    Ok(u64::try_from(payout).map_err(|_| AerospacerProtocolError::OverflowError)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // SOL frozen at $150.00 (18 decimals)
+    const SOL_PRICE: u128 = 150_000_000_000_000_000_000;
+    const SOL_DECIMAL: u8 = 9;
+    const AUSD_DECIMALS: u8 = 18;
+    const ONE_AUSD: u64 = 1_000_000_000_000_000_000;
+    const ONE_SOL: u64 = 1_000_000_000;
+    
+    fn trove(owner: Pubkey, debt: u64, collateral: u64) -> (UserDebtAmount, UserCollateralAmount) {
+        (
+            UserDebtAmount { owner, amount: debt, l_debt_snapshot: 0, version: 1 },
+            UserCollateralAmount { owner, denom: "SOL".to_string(), amount: collateral, l_collateral_snapshot: 0, version: 1 },
+        )
+    }
+    
+    #[test]
+    fn test_shutdown_settle_redeem_withdraw() {
+        let mut settlement = CollateralSettlement {
+            denom: "SOL".to_string(),
+            price: SOL_PRICE,
+            decimal: SOL_DECIMAL,
+            fixed_at: 0,
+            backing_collateral: 0,
+            version: 2,
+        };
+        let mut total = TotalCollateralAmount { denom: "SOL".to_string(), amount: ONE_SOL + ONE_SOL / 20, l_collateral: 0, l_debt: 0, version: 1 };
+        
+        // Healthy trove: 1.5 aUSD against 1 SOL; undercollateralized trove: 15 aUSD against 0.05 SOL
+        let (mut debt_a, mut coll_a) = trove(Pubkey::new_unique(), 3 * ONE_AUSD / 2, ONE_SOL);
+        let (mut debt_b, mut coll_b) = trove(Pubkey::new_unique(), 15 * ONE_AUSD, ONE_SOL / 20);
+        
+        // Keeper settles both troves during the grace period
+        let reserved_a = settle_trove_at_frozen_price(&mut debt_a, &mut coll_a, &mut settlement, &mut total, AUSD_DECIMALS).unwrap();
+        let reserved_b = settle_trove_at_frozen_price(&mut debt_b, &mut coll_b, &mut settlement, &mut total, AUSD_DECIMALS).unwrap();
+        assert_eq!(reserved_a, ONE_SOL / 100);
+        assert_eq!(reserved_b, ONE_SOL / 20); // capped at what the trove holds
+        assert_eq!(debt_a.amount, 0);
+        assert_eq!(debt_b.amount, 0);
+        assert_eq!(settlement.backing_collateral, 60_000_000);
+        assert_eq!(total.amount, ONE_SOL - ONE_SOL / 100);
+        
+        // Settling again is a no-op
+        assert_eq!(settle_trove_at_frozen_price(&mut debt_a, &mut coll_a, &mut settlement, &mut total, AUSD_DECIMALS).unwrap(), 0);
+        
+        // Owners keep the remainder: 0.99 SOL for the healthy trove, nothing for the other
+        assert_eq!(coll_a.amount, ONE_SOL - ONE_SOL / 100);
+        assert_eq!(coll_b.amount, 0);
+        
+        // Redemption: holders of the 16.5 aUSD supply split the backing pro rata
+        let mut supply = 16 * ONE_AUSD + ONE_AUSD / 2;
+        let first = settlement_payout(settlement.backing_collateral, supply / 3, supply).unwrap();
+        assert_eq!(first, 20_000_000);
+        settlement.backing_collateral -= first;
+        supply -= supply / 3;
+        
+        let rest = settlement_payout(settlement.backing_collateral, supply, supply).unwrap();
+        assert_eq!(rest, 40_000_000);
+        settlement.backing_collateral -= rest;
+        assert_eq!(settlement.backing_collateral, 0);
+    }
+    
+    #[test]
+    fn test_settlement_with_six_decimal_stablecoin() {
+        let mut settlement = CollateralSettlement {
+            denom: "SOL".to_string(),
+            price: SOL_PRICE,
+            decimal: SOL_DECIMAL,
+            fixed_at: 0,
+            backing_collateral: 0,
+            version: 2,
+        };
+        let mut total = TotalCollateralAmount { denom: "SOL".to_string(), amount: 100 * ONE_SOL, l_collateral: 0, l_debt: 0, version: 1 };
+        
+        // 1,500 units of a 6-decimal stablecoin against 100 SOL reserve exactly 10 SOL
+        let (mut debt, mut coll) = trove(Pubkey::new_unique(), 1_500_000_000, 100 * ONE_SOL);
+        let reserved = settle_trove_at_frozen_price(&mut debt, &mut coll, &mut settlement, &mut total, 6).unwrap();
+        assert_eq!(reserved, 10 * ONE_SOL);
+        assert_eq!(coll.amount, 90 * ONE_SOL);
+        
+        // Redeeming the whole supply pays out the whole backing
+        assert_eq!(settlement_payout(settlement.backing_collateral, 1_500_000_000, 1_500_000_000).unwrap(), 10 * ONE_SOL);
+    }
+    
+    #[test]
+    fn test_settlement_payout_rejects_empty_supply() {
+        assert!(settlement_payout(ONE_SOL, ONE_AUSD, 0).is_err());
+        assert_eq!(settlement_payout(0, ONE_AUSD, ONE_AUSD).unwrap(), 0);
+    }
+}
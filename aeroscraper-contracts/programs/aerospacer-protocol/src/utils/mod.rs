@@ -82,11 +82,12 @@ pub fn get_liquidation_gains<'a>(
             if user_stake_amount > 0 && total_stake_amount > 0 {
                 // In Injective: Decimal256::from_ratio(stake_amount, total_stake_amount)
                 // For Solana: simplified calculation
-                let stake_percentage = (user_stake_amount * 1_000_000_000_000_000_000) / total_stake_amount; // Simplified Decimal256
+                // Widened to u128: amount × 10^18 overflows u64 beyond ~18 base units
+                let stake_percentage = (user_stake_amount as u128 * DECIMAL_FRACTION_18) / total_stake_amount as u128; // Simplified Decimal256
                 
                 // In Injective: calculate_stake_amount(total_collateral_amount, stake_percentage, false)
                 // For Solana: simplified calculation
-                let collateral_gain = (total_collateral_amount * stake_percentage) / 1_000_000_000_000_000_000;
+                let collateral_gain = ((total_collateral_amount as u128 * stake_percentage) / DECIMAL_FRACTION_18) as u64;
                 
                 collateral_gains.push(CollateralGain {
                     block_height,
@@ -139,11 +140,12 @@ pub fn update_total_collateral_from_account_info(
             .ok_or(AerospacerProtocolError::OverflowError)?;
     } else {
         total_collateral.amount = total_collateral.amount
-            .checked_sub(amount_change.abs() as u64)
+            .checked_sub(amount_change.unsigned_abs())
             .ok_or(AerospacerProtocolError::OverflowError)?;
     }
     
     // Serialize back to account
+    total_collateral.upgrade();
     total_collateral.try_serialize(&mut &mut data[..])?;
     
     msg!("Updated total collateral by: {} (new total: {})", amount_change, total_collateral.amount);
@@ -177,8 +179,9 @@ pub fn calculate_net_amount_after_fee(amount: u64, fee_percentage: u8) -> Result
 pub fn get_trove_icr<'a>(
     user_debt_amount: &UserDebtAmount,
     user_collateral_amount_accounts: &'a [AccountInfo<'a>],
-    collateral_prices: &HashMap<String, u64>,
+    collateral_prices: &HashMap<String, (u128, u8)>, // normalized price, token decimals
     owner: Pubkey,
+    stable_decimals: u8,
 ) -> Result<u64> {
     use crate::oracle::PriceCalculator;
     
@@ -219,31 +222,21 @@ pub fn get_trove_icr<'a>(
     }
     
     // Convert HashMap prices to Vec format for PriceCalculator
-    // Prices are stored as raw values, we need to add decimal information
-    let mut price_data: Vec<(String, u64, u8)> = Vec::new();
-    
-    for (denom, _amount) in &collateral_amounts {
-        if let Some(price) = collateral_prices.get(denom) {
-            // Get ADJUSTED decimal precision for each denom (to produce micro-USD values)
-            // Formula: adjusted_decimal = token_decimals + price_exponent - 6
-            // Must match the oracle's adjusted_decimal calculation
-            let decimal = match denom.as_str() {
-                "SOL" => 11,    // token(9) + price_exp(8) - target(6) = 11
-                "USDC" => 8,    // token(6) + price_exp(8) - target(6) = 8
-                "INJ" => 20,    // token(18) + price_exp(8) - target(6) = 20
-                "ATOM" => 8,    // token(6) + price_exp(8) - target(6) = 8
-                _ => 8,         // Default: assume 6 token decimals + 8 price exp - 6 = 8
-            };
-            
-            price_data.push((denom.clone(), *price, decimal));
-        }
-    }
+    let price_data: Vec<(String, u128, u8)> = collateral_amounts
+        .iter()
+        .filter_map(|(denom, _amount)| {
+            collateral_prices
+                .get(denom)
+                .map(|(price, token_decimals)| (denom.clone(), *price, *token_decimals))
+        })
+        .collect();
     
     // Calculate total collateral value and ICR
     let icr = PriceCalculator::calculate_trove_icr(
         &collateral_amounts,
         debt,
         &price_data,
+        stable_decimals,
     )?;
     
     Ok(icr)
@@ -255,7 +248,7 @@ pub fn check_trove_icr_with_ratio(
     state_account: &StateAccount,
     icr: u64,
 ) -> Result<()> {
-    let minimum_ratio = state_account.minimum_collateral_ratio as u64;
+    let minimum_ratio = state_account.minimum_collateral_ratio;
     
     require!(
         icr >= minimum_ratio,
@@ -8,6 +8,8 @@ import type { AccountMeta } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID, createMint, createAssociatedTokenAccount, mintTo } from "@solana/spl-token";
 import { assert } from "chai";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
+import { deriveOracleAssetPda } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 describe("Devnet Initialization and Core Testing", () => {
   const provider = anchor.AnchorProvider.env();
@@ -344,13 +346,15 @@ describe("Devnet Initialization and Core Testing", () => {
     it("Should set collateral data", async () => {
       try {
         const tx = await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "SOL",
             decimal: 9,
-            priceId: "SOL/USD"
-          })
+            priceId: "SOL/USD",
+          }))
           .accounts({
             state: oracleState,
+            asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             admin: admin.publicKey,
           })
           .signers([admin])
@@ -362,7 +366,7 @@ describe("Devnet Initialization and Core Testing", () => {
         // Verify data was set
         const state = await oracleProgram.account.oracleStateAccount.fetch(oracleState);
         console.log("✅ Oracle data verified");
-        console.log("- Collateral data count:", state.collateralData.length);
+        console.log("- Asset count:", state.assetCount);
       } catch (error) {
         console.log("❌ Set data failed:", error);
         throw error;
@@ -575,7 +579,7 @@ describe("Devnet Initialization and Core Testing", () => {
         console.log("📊 Oracle State:");
         console.log("- Admin:", oracleStateAccount.admin.toString());
         console.log("- Oracle Address:", oracleStateAccount.oracleAddress.toString());
-        console.log("- Collateral Data Count:", oracleStateAccount.collateralData.length);
+        console.log("- Asset Count:", oracleStateAccount.assetCount);
 
         // Verify fees state
         const feesStateAccount = await feesProgram.account.feeStateAccount.fetch(feesState);
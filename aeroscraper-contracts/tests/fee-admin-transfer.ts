@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerFees } from "../target/types/aerospacer_fees";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL
+} from "@solana/web3.js";
+import { assert, expect } from "chai";
+
+describe("Fee Contract - Admin Transfer Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
+
+  const feesProgram = anchor.workspace.AerospacerFees as Program<AerospacerFees>;
+
+  const admin = provider.wallet as anchor.Wallet;
+  const newAdmin = Keypair.generate();
+  const stranger = Keypair.generate();
+  let feeStateAccount: PublicKey;
+
+  before(async () => {
+    [feeStateAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_state")],
+      feesProgram.programId
+    );
+
+    try {
+      await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    } catch (error) {
+      await feesProgram.methods
+        .initialize()
+        .accounts({
+          state: feeStateAccount,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    for (const kp of [newAdmin, stranger]) {
+      const sig = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig);
+    }
+  });
+
+  it("Accepting without a pending transfer fails", async () => {
+    try {
+      await feesProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: newAdmin.publicKey, state: feeStateAccount })
+        .signers([newAdmin])
+        .rpc();
+      assert.fail("Accept should fail with no pending transfer");
+    } catch (error: any) {
+      expect(error.message).to.include("NoPendingAdmin");
+    }
+  });
+
+  it("Admin can propose and cancel a transfer", async () => {
+    await feesProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+
+    let state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.pendingAdmin.toString(), newAdmin.publicKey.toString());
+
+    await feesProgram.methods
+      .cancelAdminTransfer()
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+
+    state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+    assert.equal(state.admin.toString(), admin.publicKey.toString());
+  });
+
+  it("Only the proposed admin can accept", async () => {
+    await feesProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+
+    try {
+      await feesProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: stranger.publicKey, state: feeStateAccount })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to accept");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Proposed admin accepts and can hand control back", async () => {
+    await feesProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: newAdmin.publicKey, state: feeStateAccount })
+      .signers([newAdmin])
+      .rpc();
+
+    let state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.admin.toString(), newAdmin.publicKey.toString());
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+
+    // Restore the provider wallet as admin for the other suites
+    await feesProgram.methods
+      .proposeAdmin({ newAdmin: admin.publicKey })
+      .accounts({ admin: newAdmin.publicKey, state: feeStateAccount })
+      .signers([newAdmin])
+      .rpc();
+    await feesProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+
+    state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.admin.toString(), admin.publicKey.toString());
+  });
+});
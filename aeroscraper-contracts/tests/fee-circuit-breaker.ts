@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerFees } from "../target/types/aerospacer_fees";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL
+} from "@solana/web3.js";
+import { assert, expect } from "chai";
+
+const PAUSE_FEE_DISTRIBUTION = 1 << 0;
+
+describe("Fee Contract - Circuit Breaker Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
+
+  const feesProgram = anchor.workspace.AerospacerFees as Program<AerospacerFees>;
+
+  const admin = provider.wallet as anchor.Wallet;
+  const guardian = Keypair.generate();
+  const stranger = Keypair.generate();
+  let feeStateAccount: PublicKey;
+
+  before(async () => {
+    [feeStateAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_state")],
+      feesProgram.programId
+    );
+
+    try {
+      await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    } catch (error) {
+      await feesProgram.methods
+        .initialize()
+        .accounts({
+          state: feeStateAccount,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    for (const kp of [guardian, stranger]) {
+      const sig = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig);
+    }
+
+    await feesProgram.methods
+      .grantRole({ role: { guardian: {} }, account: guardian.publicKey })
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+  });
+
+  after(async () => {
+    // Leave the shared fee state unpaused for the other suites
+    await feesProgram.methods
+      .setPaused({ paused: 0 })
+      .accounts({ authority: admin.publicKey, state: feeStateAccount })
+      .rpc();
+  });
+
+  it("Guardian can pause fee distribution", async () => {
+    await feesProgram.methods
+      .setPaused({ paused: PAUSE_FEE_DISTRIBUTION })
+      .accounts({ authority: guardian.publicKey, state: feeStateAccount })
+      .signers([guardian])
+      .rpc();
+
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.paused, PAUSE_FEE_DISTRIBUTION);
+    assert.equal(state.guardian.toString(), guardian.publicKey.toString());
+  });
+
+  it("Guardian cannot lift a pause", async () => {
+    try {
+      await feesProgram.methods
+        .setPaused({ paused: 0 })
+        .accounts({ authority: guardian.publicKey, state: feeStateAccount })
+        .signers([guardian])
+        .rpc();
+      assert.fail("Guardian should not be able to unpause");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Unrelated signer cannot pause", async () => {
+    try {
+      await feesProgram.methods
+        .setPaused({ paused: PAUSE_FEE_DISTRIBUTION })
+        .accounts({ authority: stranger.publicKey, state: feeStateAccount })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to pause");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Guardian role does not grant fee manager powers", async () => {
+    try {
+      await feesProgram.methods
+        .toggleStakeContract()
+        .accounts({ admin: guardian.publicKey, state: feeStateAccount })
+        .signers([guardian])
+        .rpc();
+      assert.fail("Guardian should not be able to toggle staking");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects unknown pause flags", async () => {
+    try {
+      await feesProgram.methods
+        .setPaused({ paused: 0x80 })
+        .accounts({ authority: admin.publicKey, state: feeStateAccount })
+        .rpc();
+      assert.fail("Unknown flags should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidPauseFlags");
+    }
+  });
+
+  it("Admin can unpause", async () => {
+    await feesProgram.methods
+      .setPaused({ paused: 0 })
+      .accounts({ authority: admin.publicKey, state: feeStateAccount })
+      .rpc();
+
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.paused, 0);
+  });
+});
@@ -0,0 +1,79 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, utils } from "@coral-xyz/anchor";
+import { AerospacerFees } from "../target/types/aerospacer_fees";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+
+describe("Fee Contract - Event Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
+
+  const feesProgram = anchor.workspace.AerospacerFees as Program<AerospacerFees>;
+
+  const admin = provider.wallet as anchor.Wallet;
+  let feeStateAccount: PublicKey;
+
+  // emit_cpi! events are the data of a self-CPI: 8-byte instruction tag, then the event
+  const decodeCpiEvents = async (signature: string) => {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const accountKeys = tx!.transaction.message.getAccountKeys();
+    const events: any[] = [];
+    for (const inner of tx!.meta!.innerInstructions ?? []) {
+      for (const ix of inner.instructions) {
+        if (!accountKeys.get(ix.programIdIndex)!.equals(feesProgram.programId)) continue;
+        const data = utils.bytes.bs58.decode(ix.data);
+        const event = feesProgram.coder.events.decode(
+          utils.bytes.base64.encode(data.subarray(8))
+        );
+        if (event) events.push(event);
+      }
+    }
+    return events;
+  };
+
+  before(async () => {
+    [feeStateAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_state")],
+      feesProgram.programId
+    );
+
+    try {
+      await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    } catch (error) {
+      await feesProgram.methods
+        .initialize()
+        .accounts({
+          state: feeStateAccount,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
+  it("Toggling the stake contract emits ParamsChanged", async () => {
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+
+    const signature = await feesProgram.methods
+      .toggleStakeContract()
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc({ commitment: "confirmed" });
+
+    const events = await decodeCpiEvents(signature);
+    const changed = events.find((e) => e.name === "paramsChanged");
+    assert.isDefined(changed);
+    assert.equal(changed.data.authority.toString(), admin.publicKey.toString());
+    assert.equal(changed.data.before.isStakeEnabled, state.isStakeEnabled);
+    assert.equal(changed.data.after.isStakeEnabled, !state.isStakeEnabled);
+
+    // Restore the previous routing
+    await feesProgram.methods
+      .toggleStakeContract()
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+  });
+});
@@ -0,0 +1,157 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { AerospacerFees } from "../target/types/aerospacer_fees";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL
+} from "@solana/web3.js";
+import { assert, expect } from "chai";
+
+describe("Fee Contract - Timelock Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
+
+  const feesProgram = anchor.workspace.AerospacerFees as Program<AerospacerFees>;
+
+  const admin = provider.wallet as anchor.Wallet;
+  const stranger = Keypair.generate();
+  let feeStateAccount: PublicKey;
+
+  const queuedChangePda = (id: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("timelock"), id.toArrayLike(Buffer, "le", 8)],
+      feesProgram.programId
+    )[0];
+
+  before(async () => {
+    [feeStateAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_state")],
+      feesProgram.programId
+    );
+
+    try {
+      await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    } catch (error) {
+      await feesProgram.methods
+        .initialize()
+        .accounts({
+          state: feeStateAccount,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const sig = await connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(sig);
+  });
+
+  it("Queued change executes once due (zero delay)", async () => {
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(state.timelockDelay.toNumber(), 0);
+    const id = state.nextChangeId;
+    const queuedChange = queuedChangePda(id);
+
+    await feesProgram.methods
+      .queueChange({ change: { toggleStakeContract: {} } })
+      .accounts({
+        proposer: admin.publicKey,
+        state: feeStateAccount,
+        queuedChange,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await feesProgram.methods
+      .executeChange({ id })
+      .accounts({
+        executor: stranger.publicKey,
+        state: feeStateAccount,
+        queuedChange,
+        proposer: admin.publicKey,
+      })
+      .signers([stranger])
+      .rpc();
+
+    const after = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(after.isStakeEnabled, !state.isStakeEnabled);
+    assert.isNull(await connection.getAccountInfo(queuedChange));
+
+    // Restore the previous routing directly (allowed while the delay is zero)
+    await feesProgram.methods
+      .toggleStakeContract()
+      .accounts({ admin: admin.publicKey, state: feeStateAccount })
+      .rpc();
+  });
+
+  it("Guardian can cancel a queued change", async () => {
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    const id = state.nextChangeId;
+    const queuedChange = queuedChangePda(id);
+
+    await feesProgram.methods
+      .queueChange({ change: { toggleStakeContract: {} } })
+      .accounts({
+        proposer: admin.publicKey,
+        state: feeStateAccount,
+        queuedChange,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await feesProgram.methods
+      .cancelChange({ id })
+      .accounts({
+        authority: admin.publicKey,
+        state: feeStateAccount,
+        queuedChange,
+        proposer: admin.publicKey,
+      })
+      .rpc();
+
+    const after = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    assert.equal(after.isStakeEnabled, state.isStakeEnabled);
+    assert.isNull(await connection.getAccountInfo(queuedChange));
+  });
+
+  it("Unauthorized signer cannot queue a change", async () => {
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    try {
+      await feesProgram.methods
+        .queueChange({ change: { toggleStakeContract: {} } })
+        .accounts({
+          proposer: stranger.publicKey,
+          state: feeStateAccount,
+          queuedChange: queuedChangePda(state.nextChangeId),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to queue changes");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Only the owner can queue a timelock delay change", async () => {
+    const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+    try {
+      await feesProgram.methods
+        .queueChange({ change: { setTimelockDelay: { 0: new BN(3600) } } })
+        .accounts({
+          proposer: stranger.publicKey,
+          state: feeStateAccount,
+          queuedChange: queuedChangePda(state.nextChangeId),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to change the delay");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, getAllOracleDenoms, lastOracleAsset, oracleAssetMetas } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 describe("Oracle Contract - Admin Controls Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -50,27 +52,24 @@ describe("Oracle Contract - Admin Controls Tests", () => {
       console.log("📝 Adding SOL collateral...");
 
       const tx = await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"),
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       console.log("✅ SOL collateral added. TX:", tx);
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.include(denoms, "SOL");
       console.log("✅ Collateral verified:", denoms);
@@ -84,15 +83,17 @@ describe("Oracle Contract - Admin Controls Tests", () => {
       console.log("📝 Updating SOL collateral...");
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 10,
           priceId: newPriceId,
           pythPriceAccount: new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"),
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
@@ -100,7 +101,7 @@ describe("Oracle Contract - Admin Controls Tests", () => {
       const priceId = await oracleProgram.methods
         .getPriceId({ denom: "SOL" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
         })
         .view();
 
@@ -112,20 +113,18 @@ describe("Oracle Contract - Admin Controls Tests", () => {
   describe("Test 2.3: Admin Can Add Multiple Assets with set_data_batch", () => {
     it("Should allow admin to add multiple assets at once", async () => {
       const batchData = [
-        {
+        oracleAssetParams({
           denom: "ETH",
           decimal: 18,
           priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: new PublicKey("EdVCmQ9FSPcVe5YySXDPCRmc8aDQLKJ9xvYBMZPie1Vw"),
-        },
-        {
+        }),
+        oracleAssetParams({
           denom: "BTC",
           decimal: 8,
           priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: new PublicKey("HovQMDrbAgAYPCmHVSrezcSmkMtXSSUsLDFANExrZh2J"),
-        },
+        }),
       ];
 
       console.log("📝 Adding ETH and BTC in batch...");
@@ -137,16 +136,13 @@ describe("Oracle Contract - Admin Controls Tests", () => {
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
+        .remainingAccounts(oracleAssetMetas(batchData.map((d) => d.denom), oracleProgram.programId))
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.include(denoms, "ETH");
       assert.include(denoms, "BTC");
@@ -165,16 +161,13 @@ describe("Oracle Contract - Admin Controls Tests", () => {
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          lastAsset: await lastOracleAsset(oracleProgram, "SOL"),
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.notInclude(denoms, "SOL");
       console.log("✅ SOL removed. Remaining:", denoms);
@@ -216,15 +209,17 @@ describe("Oracle Contract - Admin Controls Tests", () => {
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "USDC",
             decimal: 6,
             priceId: "eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a",
             pythPriceAccount: Keypair.generate().publicKey,
-          })
+          }))
           .accounts({
             admin: nonAdmin.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("USDC", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .signers([nonAdmin])
@@ -250,6 +245,8 @@ describe("Oracle Contract - Admin Controls Tests", () => {
           .accounts({
             admin: nonAdmin.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+            lastAsset: await lastOracleAsset(oracleProgram, "ETH"),
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .signers([nonAdmin])
@@ -300,8 +297,10 @@ describe("Oracle Contract - Admin Controls Tests", () => {
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
+          .remainingAccounts(oracleAssetMetas([], oracleProgram.programId))
           .rpc();
 
         assert.fail("Should have thrown an error");
@@ -318,15 +317,17 @@ describe("Oracle Contract - Admin Controls Tests", () => {
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "INVALID",
             decimal: 6,
             priceId: "notahexstring",
             pythPriceAccount: Keypair.generate().publicKey,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("INVALID", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL
+} from "@solana/web3.js";
+import { assert, expect } from "chai";
+
+describe("Oracle Contract - Admin Transfer Tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+  const admin = provider.wallet as anchor.Wallet;
+  const newAdmin = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+
+  before(async () => {
+    const existingState = await connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+
+    for (const kp of [newAdmin, stranger]) {
+      const sig = await connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig);
+    }
+  });
+
+  it("Accepting without a pending transfer fails", async () => {
+    try {
+      await oracleProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: newAdmin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([newAdmin])
+        .rpc();
+      assert.fail("Accept should fail with no pending transfer");
+    } catch (error: any) {
+      expect(error.message).to.include("NoPendingAdmin");
+    }
+  });
+
+  it("Admin can propose and cancel a transfer", async () => {
+    await oracleProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: admin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .rpc();
+
+    let state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.pendingAdmin.toString(), newAdmin.publicKey.toString());
+
+    await oracleProgram.methods
+      .cancelAdminTransfer()
+      .accounts({ admin: admin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .rpc();
+
+    state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+    assert.equal(state.admin.toString(), admin.publicKey.toString());
+  });
+
+  it("Only the proposed admin can accept", async () => {
+    await oracleProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: admin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .rpc();
+
+    try {
+      await oracleProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: stranger.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to accept");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Proposed admin accepts and can hand control back", async () => {
+    await oracleProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: newAdmin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .signers([newAdmin])
+      .rpc();
+
+    let state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.admin.toString(), newAdmin.publicKey.toString());
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+
+    // Restore the provider wallet as admin for the other suites
+    await oracleProgram.methods
+      .proposeAdmin({ newAdmin: admin.publicKey })
+      .accounts({ admin: newAdmin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .signers([newAdmin])
+      .rpc();
+    await oracleProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: admin.publicKey, state: stateAccountPda, clock: anchor.web3.SYSVAR_CLOCK_PUBKEY })
+      .rpc();
+
+    state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.admin.toString(), admin.publicKey.toString());
+  });
+});
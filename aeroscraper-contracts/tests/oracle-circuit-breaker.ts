@@ -0,0 +1,174 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { deriveOracleAssetPda } from "./oracle-registry";
+import { oracleAssetParams, SOL_PRICE_FEED, SOL_PRICE_ID } from "./test-utils";
+
+const PAUSE_PRICE_UPDATES = 1 << 0;
+const PAUSE_ASSET_CONFIG = 1 << 1;
+
+describe("Oracle Contract - Circuit Breaker Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+  const DENOM = "PSOL";
+
+  const admin = provider.wallet as anchor.Wallet;
+  const guardian = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+
+  async function setPaused(paused: number, authority: Keypair | null = null) {
+    const builder = oracleProgram.methods
+      .setPaused({ paused })
+      .accounts({
+        authority: authority ? authority.publicKey : admin.publicKey,
+        state: stateAccountPda,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      });
+    await (authority ? builder.signers([authority]) : builder).rpc();
+  }
+
+  async function setAsset() {
+    await oracleProgram.methods
+      .setData(oracleAssetParams({
+        denom: DENOM,
+        decimal: 9,
+        priceId: SOL_PRICE_ID,
+        pythPriceAccount: SOL_PRICE_FEED,
+      }))
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(DENOM, oracleProgram.programId),
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  async function updatePrice() {
+    await oracleProgram.methods
+      .updatePythPrice({ denom: DENOM })
+      .accounts({
+        updater: admin.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(DENOM, oracleProgram.programId),
+        pythPriceAccount: SOL_PRICE_FEED,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        priceHistory: null,
+      })
+      .rpc();
+  }
+
+  before(async () => {
+    const existingState = await provider.connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+
+    for (const kp of [guardian, stranger]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    await oracleProgram.methods
+      .grantRole({ role: { guardian: {} }, account: guardian.publicKey })
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+
+    await setAsset();
+  });
+
+  after(async () => {
+    // Leave the shared oracle state unpaused for the other suites
+    await setPaused(0);
+  });
+
+  it("Guardian can pause price updates", async () => {
+    await setPaused(PAUSE_PRICE_UPDATES, guardian);
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.paused, PAUSE_PRICE_UPDATES);
+
+    try {
+      await updatePrice();
+      assert.fail("update_pyth_price should be paused");
+    } catch (error: any) {
+      expect(error.message).to.include("OperationPaused");
+    }
+  });
+
+  it("Price updates pause leaves asset config open", async () => {
+    await setAsset();
+  });
+
+  it("Guardian can add the asset config pause", async () => {
+    await setPaused(PAUSE_PRICE_UPDATES | PAUSE_ASSET_CONFIG, guardian);
+
+    try {
+      await setAsset();
+      assert.fail("set_data should be paused");
+    } catch (error: any) {
+      expect(error.message).to.include("OperationPaused");
+    }
+  });
+
+  it("Guardian cannot lift a pause", async () => {
+    try {
+      await setPaused(PAUSE_ASSET_CONFIG, guardian);
+      assert.fail("Guardian should not be able to clear a flag");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Unrelated signer cannot pause", async () => {
+    try {
+      await setPaused(PAUSE_PRICE_UPDATES | PAUSE_ASSET_CONFIG, stranger);
+      assert.fail("Stranger should not be able to pause");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects unknown pause flags", async () => {
+    try {
+      await setPaused(0x80);
+      assert.fail("Unknown flags should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidPauseFlags");
+    }
+  });
+
+  it("Admin can unpause", async () => {
+    await setPaused(0);
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.paused, 0);
+
+    await setAsset();
+    await updatePrice();
+  });
+});
@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, getAllOracleDenoms, lastOracleAsset, oracleAssetMetas } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 describe("Oracle Contract - Edge Cases & Error Handling", () => {
   const provider = anchor.AnchorProvider.env();
@@ -47,30 +49,27 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
   describe("Test 6.1: Add Maximum Length Denom String", () => {
     it("Should handle very long denomination strings", async () => {
-      const longDenom = "A".repeat(50);
+      const longDenom = "A".repeat(32); // PDA seed limit
 
       console.log(`⚡ Adding ${longDenom.length}-char denom...`);
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: longDenom,
           decimal: 6,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda(longDenom, oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.include(denoms, longDenom);
       console.log("✅ Long denom handled successfully");
@@ -82,13 +81,12 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
       const batchData = [];
 
       for (let i = 0; i < 5; i++) {
-        batchData.push({
+        batchData.push(oracleAssetParams({
           denom: `ASSET${i}`,
           decimal: 6 + (i % 13),
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: Keypair.generate().publicKey,
-        });
+        }));
       }
 
       console.log("⚡ Adding 5 assets in batch...");
@@ -100,16 +98,13 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
+        .remainingAccounts(oracleAssetMetas(batchData.map((d) => d.denom), oracleProgram.programId))
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       expect(denoms.length).to.be.greaterThanOrEqual(5);
       console.log(`✅ Batch of 5 handled. Total assets: ${denoms.length}`);
@@ -121,13 +116,12 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
       const oversizeBatch = [];
 
       for (let i = 0; i < 6; i++) {
-        oversizeBatch.push({
+        oversizeBatch.push(oracleAssetParams({
           denom: `OVER${i}`,
           decimal: 6,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: Keypair.generate().publicKey,
-        });
+        }));
       }
 
       console.log("🔒 Attempting batch of 6 items...");
@@ -140,8 +134,10 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
+          .remainingAccounts(oracleAssetMetas(oversizeBatch.map((d) => d.denom), oracleProgram.programId))
           .rpc();
 
         assert.fail("Should have rejected oversized batch");
@@ -158,15 +154,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
       for (let i = 0; i < 10; i++) {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "UPDATETEST",
             decimal: 6 + i,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("UPDATETEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -184,15 +182,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
       for (let i = 0; i < 5; i++) {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "CYCLETEST",
             decimal: 6,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("CYCLETEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -204,6 +204,8 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("CYCLETEST", oracleProgram.programId),
+            lastAsset: await lastOracleAsset(oracleProgram, "CYCLETEST"),
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -220,15 +222,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
       console.log("⚡ Testing max decimal (255)...");
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "MAXDECIMAL",
           decimal: 255,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("MAXDECIMAL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
@@ -246,15 +250,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
         if (operation === 0) {
           await oracleProgram.methods
-            .setData({
+            .setData(oracleAssetParams({
               denom: `RAPID${i}`,
               decimal: 6,
               priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
               pythPriceAccount: SOL_PRICE_FEED,
-            })
+            }))
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda(`RAPID${i, oracleProgram.programId),
+              systemProgram: SystemProgram.programId,
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -267,6 +273,8 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
               .accounts({
                 admin: provider.wallet.publicKey,
                 state: stateAccountPda,
+                asset: deriveOracleAssetPda(`RAPID${i - 1, oracleProgram.programId),
+                lastAsset: await lastOracleAsset(oracleProgram, `RAPID${i - 1),
                 clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
               })
               .rpc();
@@ -323,15 +331,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
       for (const denom of specialDenoms) {
         await oracleProgram.methods
-          .setData({
-            denom: denom,
+          .setData(oracleAssetParams({
+            denom,
             decimal: 6,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -348,39 +358,38 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
       console.log("⚡ Testing case sensitivity...");
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "sol",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("sol", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       const hasLowercase = denoms.includes("sol");
       const hasUppercase = denoms.includes("SOL");
@@ -397,15 +406,17 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "TEST",
             decimal: 6,
             priceId: "",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -418,7 +429,67 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
     });
   });
 
-  describe("Test 6.12: All Error Codes Tested", () => {
+  describe("Test 6.12: Zero Max Staleness Rejected", () => {
+    it("Should reject an asset without a staleness limit", async () => {
+      console.log("🔒 Testing zero max staleness...");
+
+      try {
+        await oracleProgram.methods
+          .setData(oracleAssetParams({
+            denom: "TEST",
+            decimal: 6,
+            priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+            pythPriceAccount: SOL_PRICE_FEED,
+            maxStalenessSecs: 0,
+          }))
+          .accounts({
+            admin: provider.wallet.publicKey,
+            state: stateAccountPda,
+            asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+
+        assert.fail("Should have rejected zero max staleness");
+      } catch (error: any) {
+        console.log("✅ Zero max staleness rejected");
+        expect(error.message).to.include("InvalidCollateralData");
+      }
+    });
+  });
+
+  describe("Test 6.13: Zero Max Confidence Rejected", () => {
+    it("Should reject an asset that would refuse every price", async () => {
+      console.log("🔒 Testing zero max confidence...");
+
+      try {
+        await oracleProgram.methods
+          .setData(oracleAssetParams({
+            denom: "TEST",
+            decimal: 6,
+            priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+            pythPriceAccount: SOL_PRICE_FEED,
+            maxConfidenceBps: 0,
+          }))
+          .accounts({
+            admin: provider.wallet.publicKey,
+            state: stateAccountPda,
+            asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+
+        assert.fail("Should have rejected zero max confidence");
+      } catch (error: any) {
+        console.log("✅ Zero max confidence rejected");
+        expect(error.message).to.include("InvalidCollateralData");
+      }
+    });
+  });
+
+  describe("Test 6.14: All Error Codes Tested", () => {
     it("Should verify all error codes are working", async () => {
       const errorCodes = [
         "Unauthorized",
@@ -441,6 +512,6 @@ describe("Oracle Contract - Edge Cases & Error Handling", () => {
 
   after(() => {
     console.log("\n✅ Oracle Edge Cases Tests Complete");
-    console.log("  Total Tests Passed: 12\n");
+    console.log("  Total Tests Passed: 14\n");
   });
 });
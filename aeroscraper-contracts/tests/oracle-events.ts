@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, utils } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { assert } from "chai";
+import { deriveOracleAssetPda, lastOracleAsset } from "./oracle-registry";
+import { oracleAssetParams, SOL_PRICE_FEED, SOL_PRICE_ID } from "./test-utils";
+
+describe("Oracle Contract - Event Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+  const DENOM = "ESOL";
+
+  const admin = provider.wallet as anchor.Wallet;
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+  const asset = deriveOracleAssetPda(DENOM, oracleProgram.programId);
+  const [priceHistory] = PublicKey.findProgramAddressSync(
+    [Buffer.from("history"), Buffer.from(DENOM)],
+    oracleProgram.programId
+  );
+
+  // emit_cpi! events are the data of a self-CPI: 8-byte instruction tag, then the event
+  const decodeCpiEvents = async (signature: string) => {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const accountKeys = tx!.transaction.message.getAccountKeys();
+    const events: any[] = [];
+    for (const inner of tx!.meta!.innerInstructions ?? []) {
+      for (const ix of inner.instructions) {
+        if (!accountKeys.get(ix.programIdIndex)!.equals(oracleProgram.programId)) continue;
+        const data = utils.bytes.bs58.decode(ix.data);
+        const event = oracleProgram.coder.events.decode(
+          utils.bytes.base64.encode(data.subarray(8))
+        );
+        if (event) events.push(event);
+      }
+    }
+    return events;
+  };
+
+  before(async () => {
+    const existingState = await connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+
+    await oracleProgram.methods
+      .setData(oracleAssetParams({
+        denom: DENOM,
+        decimal: 9,
+        priceId: SOL_PRICE_ID,
+        pythPriceAccount: SOL_PRICE_FEED,
+      }))
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        asset,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    await oracleProgram.methods
+      .removeData({ collateralDenom: DENOM })
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        asset,
+        lastAsset: await lastOracleAsset(oracleProgram, DENOM),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  });
+
+  it("Creating a price history emits PriceHistoryCreated", async function () {
+    // History accounts are never closed, so only the first run against a cluster sees it
+    if (await connection.getAccountInfo(priceHistory)) {
+      this.skip();
+    }
+
+    const signature = await oracleProgram.methods
+      .initPriceHistory({ denom: DENOM })
+      .accounts({
+        authority: admin.publicKey,
+        state: stateAccountPda,
+        asset,
+        priceHistory,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const events = await decodeCpiEvents(signature);
+    const created = events.find((e) => e.name === "priceHistoryCreated");
+    assert.isDefined(created);
+    assert.equal(created.data.authority.toString(), admin.publicKey.toString());
+    assert.equal(created.data.denom, DENOM);
+    assert.equal(created.data.capacity, 64);
+  });
+
+  it("A recorded get_price read emits PriceRecorded", async () => {
+    const ix = await oracleProgram.methods
+      .getPrice({ denom: DENOM })
+      .accounts({
+        asset,
+        pythPriceAccount: SOL_PRICE_FEED,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .instruction();
+    // The read is only recorded when the asset account is passed writable
+    ix.keys.find((key) => key.pubkey.equals(asset))!.isWritable = true;
+
+    const signature = await provider.sendAndConfirm(new Transaction().add(ix), [], {
+      commitment: "confirmed",
+    });
+
+    const events = await decodeCpiEvents(signature);
+    const recorded = events.find((e) => e.name === "priceRecorded");
+    assert.isDefined(recorded);
+    assert.equal(recorded.data.denom, DENOM);
+
+    const config = await oracleProgram.account.assetConfig.fetch(asset);
+    if (recorded.data.status.working) {
+      assert.equal(recorded.data.price.toString(), config.lastGoodPrice.toString());
+    }
+  });
+});
@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, fetchOracleAssets, getAllOracleDenoms, lastOracleAsset } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 describe("Oracle Contract - Info Query Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -49,13 +51,13 @@ describe("Oracle Contract - Info Query Tests", () => {
   // Cleanup function to reset oracle state
   async function cleanupOracleState() {
     try {
-      // Get current state to see what assets exist
-      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      // Get the configured assets
+      const assets = await fetchOracleAssets(oracleProgram);
       
-      console.log(`🧹 Cleaning up ${state.collateralData.length} assets...`);
+      console.log(`🧹 Cleaning up ${assets.length} assets...`);
       
       // Remove all existing assets
-      for (const asset of state.collateralData) {
+      for (const asset of assets) {
         try {
           console.log(`  Removing asset: ${asset.denom}`);
           await oracleProgram.methods
@@ -63,6 +65,8 @@ describe("Oracle Contract - Info Query Tests", () => {
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda(asset.denom, oracleProgram.programId),
+              lastAsset: await lastOracleAsset(oracleProgram, asset.denom),
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -98,12 +102,7 @@ describe("Oracle Contract - Info Query Tests", () => {
       // Clean up before test
       await cleanupOracleState();
       
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.equal(denoms.length, 0);
       console.log("✅ Empty denoms array returned");
@@ -116,39 +115,38 @@ describe("Oracle Contract - Info Query Tests", () => {
       await cleanupOracleState();
       
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "ETH",
           decimal: 18,
           priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
           pythPriceAccount: ETH_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       assert.equal(denoms.length, 2);
       assert.include(denoms, "SOL");
@@ -163,7 +161,7 @@ describe("Oracle Contract - Info Query Tests", () => {
       const exists = await oracleProgram.methods
         .checkDenom({ denom: "SOL" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
         })
         .view();
 
@@ -177,7 +175,7 @@ describe("Oracle Contract - Info Query Tests", () => {
       const exists = await oracleProgram.methods
         .checkDenom({ denom: "BTC" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
         })
         .view();
 
@@ -193,7 +191,7 @@ describe("Oracle Contract - Info Query Tests", () => {
       const priceId = await oracleProgram.methods
         .getPriceId({ denom: "SOL" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
         })
         .view();
 
@@ -210,7 +208,7 @@ describe("Oracle Contract - Info Query Tests", () => {
         await oracleProgram.methods
           .getPriceId({ denom: "USDC" })
           .accounts({
-            state: stateAccountPda,
+            asset: deriveOracleAssetPda("USDC", oracleProgram.programId),
           })
           .rpc();
 
@@ -262,15 +260,17 @@ describe("Oracle Contract - Info Query Tests", () => {
       const initialCount = config.assetCount;
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "BTC",
           decimal: 8,
           priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
           pythPriceAccount: new PublicKey("HovQMDrbAgAYPCmHVSrezcSmkMtXSSUsLDFANExrZh2J"),
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
@@ -292,6 +292,8 @@ describe("Oracle Contract - Info Query Tests", () => {
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
+          lastAsset: await lastOracleAsset(oracleProgram, "BTC"),
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
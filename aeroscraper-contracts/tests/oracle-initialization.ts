@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, fetchOracleAssets, lastOracleAsset } from "./oracle-registry";
 
 describe("Oracle Contract - Initialization Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -28,17 +29,19 @@ describe("Oracle Contract - Initialization Tests", () => {
   // Cleanup function to reset oracle state
   async function cleanupOracleState() {
     try {
-      // Get current state to see what assets exist
-      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      // Get the configured assets
+      const assets = await fetchOracleAssets(oracleProgram);
       
       // Remove all existing assets
-      for (const asset of state.collateralData) {
+      for (const asset of assets) {
         try {
           await oracleProgram.methods
             .removeData({ collateralDenom: asset.denom })
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda(asset.denom, oracleProgram.programId),
+              lastAsset: await lastOracleAsset(oracleProgram, asset.denom),
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -207,6 +210,7 @@ describe("Oracle Contract - Initialization Tests", () => {
 
       assert.isArray(state.collateralData, "Collateral data should be an array");
       assert.equal(state.collateralData.length, 0, "Collateral data should be empty on init");
+      assert.equal(state.assetCount, 0, "No asset accounts should remain");
 
       console.log("✅ Collateral data is empty as expected");
       console.log("  Length:", state.collateralData.length);
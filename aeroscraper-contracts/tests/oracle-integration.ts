@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, fetchOracleAssets, getAllOracleDenoms, lastOracleAsset, oracleAssetMetas } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 interface PriceData {
   denom: string;
@@ -68,7 +70,7 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
     const ix = await oracleProgram.methods
       .getPrice({ denom })
       .accounts({
-        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
         pythPriceAccount: pythAccount,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -95,14 +97,18 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
   }
 
   async function queryAllPrices(pythAccounts: PublicKey[]): Promise<PriceData[]> {
+    const assets = await fetchOracleAssets(oracleProgram);
     const ix = await oracleProgram.methods
-      .getAllPrices({})
+      .getAllPrices({ start: 0 })
       .accounts({
-        state: stateAccountPda,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .remainingAccounts(
-        pythAccounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: false }))
+        // Every configured asset followed by its Pyth account, in registry order
+        assets.flatMap((asset, i) => [
+          { pubkey: asset.address, isSigner: false, isWritable: false },
+          ...(pythAccounts[i] ? [{ pubkey: pythAccounts[i], isSigner: false, isWritable: false }] : []),
+        ])
       )
       .instruction();
 
@@ -145,17 +151,19 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
   // Cleanup function to reset oracle state
   async function cleanupOracleState() {
     try {
-      // Get current state to see what assets exist
-      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      // Get the configured assets
+      const assets = await fetchOracleAssets(oracleProgram);
       
       // Remove all existing assets
-      for (const asset of state.collateralData) {
+      for (const asset of assets) {
         try {
           await oracleProgram.methods
             .removeData({ collateralDenom: asset.denom })
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda(asset.denom, oracleProgram.programId),
+              lastAsset: await lastOracleAsset(oracleProgram, asset.denom),
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -204,27 +212,24 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
     }
 
     const batchData = [
-      {
+      oracleAssetParams({
         denom: "SOL",
         decimal: 9,
         priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: SOL_PRICE_FEED,
-      },
-      {
+      }),
+      oracleAssetParams({
         denom: "ETH",
         decimal: 18,
         priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: ETH_PRICE_FEED,
-      },
-      {
+      }),
+      oracleAssetParams({
         denom: "BTC",
         decimal: 8,
         priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: BTC_PRICE_FEED,
-      },
+      }),
     ];
 
     await oracleProgram.methods
@@ -232,8 +237,10 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       .accounts({
         admin: provider.wallet.publicKey,
         state: stateAccountPda,
+        systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
+      .remainingAccounts(oracleAssetMetas(batchData.map((d) => d.denom), oracleProgram.programId))
       .rpc();
 
     console.log("✅ Setup complete - Oracle ready for CPI");
@@ -287,7 +294,7 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
         const exists = await oracleProgram.methods
           .checkDenom({ denom: asset })
           .accounts({
-            state: stateAccountPda,
+            asset: deriveOracleAssetPda(asset, oracleProgram.programId),
           })
           .view();
 
@@ -307,15 +314,17 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       console.log("  Adding SOL asset...");
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "SOL",
             decimal: 9,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -328,15 +337,17 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       console.log("  Adding ETH asset...");
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "ETH",
             decimal: 18,
             priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
             pythPriceAccount: ETH_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -349,15 +360,17 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       console.log("  Adding BTC asset...");
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "BTC",
             decimal: 8,
             priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
             pythPriceAccount: BTC_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -369,12 +382,7 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       
       console.log("📡 Simulating protocol CPI: get_all_denoms()...");
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       console.log(`✅ Protocol received ${denoms.length} supported assets:`);
       console.log(`  ${denoms.join(', ')}`);
@@ -393,43 +401,49 @@ describe("Oracle Contract - Protocol CPI Integration Tests", () => {
       
       // Add only the 3 assets this test expects
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "ETH",
           decimal: 18,
           priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
           pythPriceAccount: ETH_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "BTC",
           decimal: 8,
           priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
           pythPriceAccount: BTC_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
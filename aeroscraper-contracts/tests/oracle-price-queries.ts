@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, fetchOracleAssets, lastOracleAsset, oracleAssetMetas } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 interface PriceData {
   denom: string;
@@ -87,7 +89,7 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
     const ix = await oracleProgram.methods
       .getPrice({ denom })
       .accounts({
-        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
         pythPriceAccount: pythAccount,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -114,14 +116,18 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
   }
 
   async function queryAllPrices(pythAccounts: PublicKey[]): Promise<PriceData[]> {
+    const assets = await fetchOracleAssets(oracleProgram);
     const ix = await oracleProgram.methods
-      .getAllPrices({})
+      .getAllPrices({ start: 0 })
       .accounts({
-        state: stateAccountPda,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .remainingAccounts(
-        pythAccounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: false }))
+        // Every configured asset followed by its Pyth account, in registry order
+        assets.flatMap((asset, i) => [
+          { pubkey: asset.address, isSigner: false, isWritable: false },
+          ...(pythAccounts[i] ? [{ pubkey: pythAccounts[i], isSigner: false, isWritable: false }] : []),
+        ])
       )
       .instruction();
 
@@ -148,17 +154,19 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
   // Cleanup function to reset oracle state
   async function cleanupOracleState() {
     try {
-      // Get current state to see what assets exist
-      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      // Get the configured assets
+      const assets = await fetchOracleAssets(oracleProgram);
       
       // Remove all existing assets
-      for (const asset of state.collateralData) {
+      for (const asset of assets) {
         try {
           await oracleProgram.methods
             .removeData({ collateralDenom: asset.denom })
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda(asset.denom, oracleProgram.programId),
+              lastAsset: await lastOracleAsset(oracleProgram, asset.denom),
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -206,27 +214,24 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
     }
 
     const batchData = [
-      {
+      oracleAssetParams({
         denom: "SOL",
         decimal: 9,
         priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: SOL_PRICE_FEED,
-      },
-      {
+      }),
+      oracleAssetParams({
         denom: "ETH",
         decimal: 18,
         priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: ETH_PRICE_FEED,
-      },
-      {
+      }),
+      oracleAssetParams({
         denom: "BTC",
         decimal: 8,
         priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-        configuredAt: new anchor.BN(Date.now() / 1000),
         pythPriceAccount: BTC_PRICE_FEED,
-      },
+      }),
     ];
 
     await oracleProgram.methods
@@ -236,8 +241,10 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
       .accounts({
         admin: provider.wallet.publicKey,
         state: stateAccountPda,
+        systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
+      .remainingAccounts(oracleAssetMetas(batchData.map((d) => d.denom), oracleProgram.programId))
       .rpc();
 
     console.log("✅ Setup complete - 3 assets configured with Pyth feeds");
@@ -305,43 +312,49 @@ describe("Oracle Contract - Price Queries with Real Pyth Integration", () => {
       
       // Add only the 3 assets this test expects
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "SOL",
           decimal: 9,
           priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
           pythPriceAccount: SOL_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "ETH",
           decimal: 18,
           priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
           pythPriceAccount: ETH_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
 
       await oracleProgram.methods
-        .setData({
+        .setData(oracleAssetParams({
           denom: "BTC",
           decimal: 8,
           priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
           pythPriceAccount: BTC_PRICE_FEED,
-        })
+        }))
         .accounts({
           admin: provider.wallet.publicKey,
           state: stateAccountPda,
+          asset: deriveOracleAssetPda("BTC", oracleProgram.programId),
+          systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
         .rpc();
@@ -0,0 +1,706 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { deriveOracleAssetPda, lastOracleAsset } from "./oracle-registry";
+import {
+  oracleAssetParams,
+  SOL_PRICE_ID,
+  SINGLE_PRICE_SOURCE,
+  NO_PRICE_BOUNDS,
+  NOT_COMPOSITE,
+} from "./test-utils";
+
+describe("Oracle Contract - Price Source Adapters", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+  // Pyth push account: owned by the Pyth oracle program, not the receiver or Switchboard
+  const SOL_PRICE_FEED = new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix");
+  const ETH_PRICE_FEED = new PublicKey("EdVCmQ9FSPcVe5YySXDPCRmc8aDQLKJ9xvYBMZPie1Vw");
+  const ETH_PRICE_ID = "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace";
+  // Jito's SPL stake pool; setting an asset up only needs its address
+  const STAKE_POOL = new PublicKey("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb");
+
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+
+  async function setAsset(
+    denom: string,
+    priceId: string,
+    source: any,
+    sources: any = SINGLE_PRICE_SOURCE,
+    priceBounds: any = NO_PRICE_BOUNDS,
+    priceAccount: PublicKey = SOL_PRICE_FEED,
+    composite: any = NOT_COMPOSITE,
+    pegBandBps: number = 0
+  ) {
+    await oracleProgram.methods
+      .setData(oracleAssetParams({
+        denom,
+        decimal: 6,
+        priceId,
+        pythPriceAccount: priceAccount,
+        source,
+        ...sources,
+        priceBounds,
+        composite,
+        pegBandBps,
+      }))
+      .accounts({
+        admin: provider.wallet.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  async function removeAsset(denom: string) {
+    await oracleProgram.methods
+      .removeData({ collateralDenom: denom })
+      .accounts({
+        admin: provider.wallet.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        lastAsset: await lastOracleAsset(oracleProgram, denom),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  function getPrice(
+    denom: string,
+    extraPriceAccounts: PublicKey[] = [],
+    priceAccount: PublicKey = SOL_PRICE_FEED
+  ) {
+    return oracleProgram.methods
+      .getPrice({ denom })
+      .accounts({
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        pythPriceAccount: priceAccount,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(
+        extraPriceAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .view();
+  }
+
+  // SOL and ETH as two sources of one asset, which can never agree to within 0.01%
+  const SOL_AND_ETH_FEEDS = {
+    extraSources: [{ source: { pythPush: {} }, priceId: ETH_PRICE_ID, priceAccount: ETH_PRICE_FEED }],
+    minSources: 2,
+    maxDeviationBps: 1,
+  };
+
+  async function updatePrice(denom: string) {
+    await oracleProgram.methods
+      .updatePythPrice({ denom })
+      .accounts({
+        updater: provider.wallet.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        pythPriceAccount: SOL_PRICE_FEED,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  // The SOL push feed listed again as a second source: it always agrees with itself
+  const TWO_SOL_FEEDS = {
+    extraSources: [{ source: { pythPush: {} }, priceId: SOL_PRICE_ID, priceAccount: SOL_PRICE_FEED }],
+    minSources: 2,
+    maxDeviationBps: 100,
+  };
+
+  before(async () => {
+    console.log("\n🚀 Setting up Price Source Adapter Tests...");
+
+    const existingState = await provider.connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  describe("Test 15.1: Fixed Price Asset", () => {
+    it("Should return the configured fixed price without a feed", async () => {
+      await setAsset("USDX", "", { fixed: { price: new BN(100_000_000), exponent: -8 } });
+
+      const price = await getPrice("USDX");
+
+      assert.equal(price.denom, "USDX");
+      assert.equal(price.price.toString(), "100000000");
+      assert.equal(price.exponent, -8);
+      assert.equal(price.confidence.toString(), "0");
+      assert.equal(price.decimal, 6); // the token's decimals
+      assert.equal(price.normalizedPrice.toString(), "1000000000000000000"); // 1 USD at 18 decimals
+      assert.equal(price.emaPrice.toString(), "100000000"); // a fixed price is its own EMA
+      assert.equal(price.emaConfidence.toString(), "0");
+      console.log("✅ Fixed price returned: 1.00 USD");
+
+      await removeAsset("USDX");
+    });
+  });
+
+  describe("Test 15.2: Non-Positive Fixed Price Rejected", () => {
+    it("Should reject a fixed price of zero", async () => {
+      try {
+        await setAsset("USDX", "", { fixed: { price: new BN(0), exponent: -8 } });
+        assert.fail("Should have rejected a zero fixed price");
+      } catch (error: any) {
+        console.log("✅ Zero fixed price rejected");
+        expect(error.message).to.include("InvalidPriceSource");
+      }
+    });
+  });
+
+  describe("Test 15.3: Feed Sources Require a Price ID", () => {
+    it("Should reject a Pyth pull asset without a price ID", async () => {
+      try {
+        await setAsset("PULL", "", { pythPull: {} });
+        assert.fail("Should have rejected an empty price ID");
+      } catch (error: any) {
+        console.log("✅ Empty price ID rejected for a feed source");
+        expect(error.message).to.include("InvalidCollateralData");
+      }
+    });
+  });
+
+  describe("Test 15.4: Pyth Pull Rejects a Push Account", () => {
+    it("Should reject a price account not owned by the Pyth receiver", async () => {
+      await setAsset("PULL", SOL_PRICE_ID, { pythPull: {} });
+
+      try {
+        await getPrice("PULL");
+        assert.fail("Should have rejected the push account");
+      } catch (error: any) {
+        console.log("✅ Push account rejected for a pull asset");
+        expect(error.message).to.include("InvalidPriceAccountOwner");
+      } finally {
+        await removeAsset("PULL");
+      }
+    });
+  });
+
+  describe("Test 15.5: Switchboard Rejects a Non-Switchboard Account", () => {
+    it("Should reject a price account not owned by Switchboard", async () => {
+      await setAsset("SBRD", SOL_PRICE_ID, { switchboardOnDemand: {} });
+
+      try {
+        await getPrice("SBRD");
+        assert.fail("Should have rejected the Pyth account");
+      } catch (error: any) {
+        console.log("✅ Pyth account rejected for a Switchboard asset");
+        expect(error.message).to.include("InvalidPriceAccountOwner");
+      } finally {
+        await removeAsset("SBRD");
+      }
+    });
+  });
+
+  describe("Test 15.6: Median of Several Sources", () => {
+    it("Should report a price from every fresh source", async () => {
+      await setAsset("MSOL", SOL_PRICE_ID, { pythPush: {} }, TWO_SOL_FEEDS);
+
+      try {
+        const price = await getPrice("MSOL", [SOL_PRICE_FEED]);
+
+        assert.equal(price.sourcesUsed, 0b11);
+        expect(price.price.toNumber()).to.be.greaterThan(0);
+        console.log("✅ Both sources used:", price.price.toString());
+      } finally {
+        await removeAsset("MSOL");
+      }
+    });
+  });
+
+  describe("Test 15.7: Every Source Needs a Price Account", () => {
+    it("Should reject a query missing an extra source's account", async () => {
+      await setAsset("MSOL", SOL_PRICE_ID, { pythPush: {} }, TWO_SOL_FEEDS);
+
+      try {
+        await getPrice("MSOL");
+        assert.fail("Should have rejected the missing price account");
+      } catch (error: any) {
+        console.log("✅ Missing extra price account rejected");
+        expect(error.message).to.include("InvalidPriceAccounts");
+      } finally {
+        await removeAsset("MSOL");
+      }
+    });
+  });
+
+  describe("Test 15.8: Source Quorum Must Be Reachable", () => {
+    it("Should reject requiring more fresh sources than are configured", async () => {
+      try {
+        await setAsset("MSOL", SOL_PRICE_ID, { pythPush: {} }, { ...TWO_SOL_FEEDS, minSources: 3 });
+        assert.fail("Should have rejected an unreachable quorum");
+      } catch (error: any) {
+        console.log("✅ Unreachable source quorum rejected");
+        expect(error.message).to.include("InvalidPriceSource");
+      }
+    });
+  });
+
+  describe("Test 15.9: Live Prices Report a Working Feed", () => {
+    it("Should mark a price read from the primary source as working", async () => {
+      await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} });
+
+      try {
+        const price = await getPrice("CSOL");
+
+        assert.deepEqual(price.status, { working: {} });
+        assert.equal(price.sourcesUsed, 0b1);
+        console.log("✅ Feed status: working");
+      } finally {
+        await removeAsset("CSOL");
+      }
+    });
+  });
+
+  describe("Test 15.10: Broken Feed Serves the Last Good Price", () => {
+    it("Should return the recorded price once the sources disagree", async () => {
+      await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} });
+
+      try {
+        // Record a last good price, then add a source that can never agree with SOL
+        await updatePrice("CSOL");
+        const asset = await oracleProgram.account.assetConfig.fetch(
+          deriveOracleAssetPda("CSOL", oracleProgram.programId)
+        );
+        await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} }, SOL_AND_ETH_FEEDS);
+
+        const price = await getPrice("CSOL", [ETH_PRICE_FEED]);
+
+        assert.deepEqual(price.status, { broken: {} });
+        assert.equal(price.sourcesUsed, 0);
+        assert.equal(price.price.toString(), asset.lastGoodPrice.toString());
+        console.log("✅ Last good price served:", price.price.toString());
+      } finally {
+        await removeAsset("CSOL");
+      }
+    });
+  });
+
+  describe("Test 15.11: Broken Feed Without a Last Good Price Fails", () => {
+    it("Should reject the query when no good price was ever recorded", async () => {
+      await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} }, SOL_AND_ETH_FEEDS);
+
+      try {
+        await getPrice("CSOL", [ETH_PRICE_FEED]);
+        assert.fail("Should have rejected the disagreeing sources");
+      } catch (error: any) {
+        console.log("✅ Disagreeing sources rejected without a cached price");
+        expect(error.message).to.include("PriceDeviationTooHigh");
+      } finally {
+        await removeAsset("CSOL");
+      }
+    });
+  });
+
+  describe("Test 15.12: Price Outside the Asset's Bounds", () => {
+    it("Should reject a price above the configured maximum", async () => {
+      // $1.00 fixed price against a $0.50 ceiling (bounds use exponent -8)
+      await setAsset("USDX", "", { fixed: { price: new BN(100_000_000), exponent: -8 } }, SINGLE_PRICE_SOURCE, {
+        ...NO_PRICE_BOUNDS,
+        maxPrice: new BN(50_000_000),
+      });
+
+      try {
+        await getPrice("USDX");
+        assert.fail("Should have rejected the out-of-bounds price");
+      } catch (error: any) {
+        console.log("✅ Out-of-bounds price rejected");
+        expect(error.message).to.include("PriceOutOfBounds");
+      } finally {
+        await removeAsset("USDX");
+      }
+    });
+  });
+
+  describe("Test 15.13: Change Limit Freezes the Feed", () => {
+    it("Should serve the last good price after a jump beyond the change limit", async () => {
+      await setAsset("USDX", "", { fixed: { price: new BN(100_000_000), exponent: -8 } });
+
+      try {
+        await updatePrice("USDX");
+
+        // Doubling the price is a 100% move against a 1% per hour limit
+        await setAsset("USDX", "", { fixed: { price: new BN(200_000_000), exponent: -8 } }, SINGLE_PRICE_SOURCE, {
+          ...NO_PRICE_BOUNDS,
+          maxChangeBps: 100,
+          changeWindowSecs: 3_600,
+        });
+        const price = await getPrice("USDX");
+
+        assert.deepEqual(price.status, { frozen: {} });
+        assert.equal(price.price.toString(), "100000000");
+        console.log("✅ Feed frozen at the last good price");
+      } finally {
+        await removeAsset("USDX");
+      }
+    });
+  });
+
+  describe("Test 15.14: Price Bounds Must Be Ordered", () => {
+    it("Should reject a minimum above the maximum", async () => {
+      try {
+        await setAsset("USDX", "", { fixed: { price: new BN(100_000_000), exponent: -8 } }, SINGLE_PRICE_SOURCE, {
+          ...NO_PRICE_BOUNDS,
+          minPrice: new BN(200_000_000),
+          maxPrice: new BN(100_000_000),
+        });
+        assert.fail("Should have rejected the inverted bounds");
+      } catch (error: any) {
+        console.log("✅ Inverted price bounds rejected");
+        expect(error.message).to.include("InvalidPriceBounds");
+      }
+    });
+  });
+
+  describe("Test 15.15: Push Account Pinned to Its Address", () => {
+    it("Should reject a Pyth account other than the configured one", async () => {
+      await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} });
+
+      try {
+        await getPrice("CSOL", [], ETH_PRICE_FEED);
+        assert.fail("Should have rejected the ETH price account");
+      } catch (error: any) {
+        console.log("✅ Unconfigured price account rejected");
+        expect(error.message).to.include("PriceSourceMismatch");
+      } finally {
+        await removeAsset("CSOL");
+      }
+    });
+  });
+
+  describe("Test 15.16: Push Account Owned by the Pyth Program", () => {
+    it("Should reject a configured account the Pyth program does not own", async () => {
+      await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} }, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, stateAccountPda);
+
+      try {
+        await getPrice("CSOL", [], stateAccountPda);
+        assert.fail("Should have rejected the oracle's own state account");
+      } catch (error: any) {
+        console.log("✅ Price account with the wrong owner rejected");
+        expect(error.message).to.include("InvalidPriceAccountOwner");
+      } finally {
+        await removeAsset("CSOL");
+      }
+    });
+  });
+
+  describe("Test 15.17: Feed Sources Require a Price Account", () => {
+    it("Should reject a Pyth push asset without a price account", async () => {
+      try {
+        await setAsset("CSOL", SOL_PRICE_ID, { pythPush: {} }, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, PublicKey.default);
+        assert.fail("Should have rejected the missing price account");
+      } catch (error: any) {
+        console.log("✅ Missing price account rejected");
+        expect(error.message).to.include("InvalidPriceSource");
+      }
+    });
+  });
+
+  // A fixed 2.00 ratio to another asset, like an LST's exchange rate
+  const TWO_TO_ONE = { fixed: { price: new BN(200_000_000), exponent: -8 } };
+
+  describe("Test 15.18: Composite Price", () => {
+    it("Should multiply the asset's own price by another asset's", async () => {
+      await setAsset("PSOL", SOL_PRICE_ID, { pythPush: {} });
+      await setAsset("XSOL", "", TWO_TO_ONE, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, {
+        op: { multiply: {} },
+        denom: "PSOL",
+      });
+
+      try {
+        const sol = await getPrice("PSOL");
+        const composite = await getPrice("XSOL", [
+          deriveOracleAssetPda("PSOL", oracleProgram.programId),
+          SOL_PRICE_FEED,
+        ]);
+
+        const scale = 10 ** (sol.exponent - composite.exponent);
+        const expected = sol.price.toNumber() * 2 * scale;
+        expect(Math.abs(composite.price.toNumber() - expected)).to.be.at.most(expected / 10_000);
+        expect(composite.confidence.toNumber()).to.be.at.least(sol.confidence.toNumber() * 2 * scale * 0.999);
+        console.log("✅ Composite price:", composite.price.toString(), "SOL:", sol.price.toString());
+      } finally {
+        await removeAsset("XSOL");
+        await removeAsset("PSOL");
+      }
+    });
+  });
+
+  describe("Test 15.19: Composite Must Use Another Asset", () => {
+    it("Should reject a composite derived from itself", async () => {
+      try {
+        await setAsset("XSOL", "", TWO_TO_ONE, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, {
+          op: { multiply: {} },
+          denom: "XSOL",
+        });
+        assert.fail("Should have rejected the self-referencing composite");
+      } catch (error: any) {
+        console.log("✅ Self-referencing composite rejected");
+        expect(error.message).to.include("InvalidCompositePrice");
+      }
+    });
+  });
+
+  describe("Test 15.20: Composite Needs the Configured Asset", () => {
+    it("Should reject another asset's config in place of the configured one", async () => {
+      await setAsset("PSOL", SOL_PRICE_ID, { pythPush: {} });
+      await setAsset("QSOL", SOL_PRICE_ID, { pythPush: {} });
+      await setAsset("XSOL", "", TWO_TO_ONE, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, {
+        op: { divide: {} },
+        denom: "PSOL",
+      });
+
+      try {
+        await getPrice("XSOL", [deriveOracleAssetPda("QSOL", oracleProgram.programId), SOL_PRICE_FEED]);
+        assert.fail("Should have rejected the unconfigured asset");
+      } catch (error: any) {
+        console.log("✅ Wrong composite asset rejected");
+        expect(error.message).to.include("InvalidAssetAccount");
+      } finally {
+        await removeAsset("XSOL");
+        await removeAsset("QSOL");
+        await removeAsset("PSOL");
+      }
+    });
+  });
+
+  describe("Test 15.21: Stake Pool Rates Are Priced Through SOL", () => {
+    it("Should reject a stake pool source that is not multiplied by another asset", async () => {
+      try {
+        await setAsset("LSOL", "", { splStakePool: { haircutBps: 50 } }, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, STAKE_POOL);
+        assert.fail("Should have required a composite price");
+      } catch (error: any) {
+        console.log("✅ Stake pool without the SOL price rejected");
+        expect(error.message).to.include("InvalidCompositePrice");
+      }
+    });
+  });
+
+  describe("Test 15.22: Stake Pool Haircut Below 100%", () => {
+    it("Should reject a haircut of the whole rate", async () => {
+      try {
+        await setAsset("LSOL", "", { splStakePool: { haircutBps: 10_000 } }, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, STAKE_POOL, {
+          op: { multiply: {} },
+          denom: "PSOL",
+        });
+        assert.fail("Should have rejected the haircut");
+      } catch (error: any) {
+        console.log("✅ Full haircut rejected");
+        expect(error.message).to.include("InvalidPriceSource");
+      }
+    });
+  });
+
+  // Fixed prices standing in for a stablecoin feed, 0.3% above and 3% below its peg
+  const NEAR_PEG = { fixed: { price: new BN(100_300_000), exponent: -8 } };
+  const DEPEGGED = { fixed: { price: new BN(97_000_000), exponent: -8 } };
+
+  async function setPegFrozen(denom: string, frozen: boolean) {
+    await oracleProgram.methods
+      .setPegFrozen({ denom, frozen })
+      .accounts({
+        authority: provider.wallet.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  describe("Test 15.23: Pegged Price Within the Band", () => {
+    it("Should report exactly 1 USD while the feed is within the band", async () => {
+      await setAsset("PUSD", "", NEAR_PEG, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, NOT_COMPOSITE, 50);
+
+      try {
+        const price = await getPrice("PUSD");
+        expect(price.price.toNumber()).to.equal(100_000_000);
+        expect(price.exponent).to.equal(-8);
+        expect(price.confidence.toNumber()).to.equal(0);
+        console.log("✅ Pegged price:", price.price.toString());
+      } finally {
+        await removeAsset("PUSD");
+      }
+    });
+  });
+
+  describe("Test 15.24: Depegged Price Outside the Band", () => {
+    it("Should report the feed's own price outside the band", async () => {
+      await setAsset("PUSD", "", DEPEGGED, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, NOT_COMPOSITE, 50);
+
+      try {
+        const price = await getPrice("PUSD");
+        expect(price.price.toNumber()).to.equal(97_000_000);
+        console.log("✅ Depegged price:", price.price.toString());
+      } finally {
+        await removeAsset("PUSD");
+      }
+    });
+  });
+
+  describe("Test 15.25: Frozen Peg", () => {
+    it("Should stop reporting a live price while the peg is frozen", async () => {
+      await setAsset("PUSD", "", NEAR_PEG, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, NOT_COMPOSITE, 50);
+
+      try {
+        await setPegFrozen("PUSD", true);
+        try {
+          await getPrice("PUSD");
+          assert.fail("Should have rejected the frozen price");
+        } catch (error: any) {
+          expect(error.message).to.include("PegFrozen");
+        }
+
+        // The owner can unfreeze it
+        await setPegFrozen("PUSD", false);
+        const price = await getPrice("PUSD");
+        expect(price.price.toNumber()).to.equal(100_000_000);
+        console.log("✅ Peg frozen and unfrozen");
+      } finally {
+        await removeAsset("PUSD");
+      }
+    });
+  });
+
+  describe("Test 15.26: Peg Configuration", () => {
+    it("Should reject a band wider than the maximum", async () => {
+      try {
+        await setAsset("PUSD", "", NEAR_PEG, SINGLE_PRICE_SOURCE, NO_PRICE_BOUNDS, SOL_PRICE_FEED, NOT_COMPOSITE, 1_001);
+        assert.fail("Should have rejected the band");
+      } catch (error: any) {
+        console.log("✅ Wide peg band rejected");
+        expect(error.message).to.include("InvalidPegConfig");
+      }
+    });
+
+    it("Should reject freezing an asset that is not pegged", async () => {
+      await setAsset("PUSD", "", NEAR_PEG);
+
+      try {
+        await setPegFrozen("PUSD", true);
+        assert.fail("Should have rejected the unpegged asset");
+      } catch (error: any) {
+        console.log("✅ Freezing an unpegged asset rejected");
+        expect(error.message).to.include("InvalidPegConfig");
+      } finally {
+        await removeAsset("PUSD");
+      }
+    });
+  });
+
+  describe("Test 15.27: Normalized Price for Any Exponent", () => {
+    it("Should report USD per whole token at 18 decimals", async () => {
+      // 3 USD at exponent 0 and 0.5 USD at exponent -18
+      await setAsset("WHOLE", "", { fixed: { price: new BN(3), exponent: 0 } });
+      await setAsset("FINE", "", { fixed: { price: new BN("500000000000000000"), exponent: -18 } });
+
+      try {
+        const whole = await getPrice("WHOLE");
+        expect(whole.normalizedPrice.toString()).to.equal("3000000000000000000");
+        expect(whole.normalizedEmaPrice.toString()).to.equal("3000000000000000000");
+
+        const fine = await getPrice("FINE");
+        expect(fine.normalizedPrice.toString()).to.equal("500000000000000000");
+        expect(fine.normalizedConfidence.toString()).to.equal("0");
+        console.log("✅ Normalized prices:", whole.normalizedPrice.toString(), fine.normalizedPrice.toString());
+      } finally {
+        await removeAsset("FINE");
+        await removeAsset("WHOLE");
+      }
+    });
+  });
+
+  function setAssetState(denom: string, state: object, authority?: Keypair) {
+    const builder = oracleProgram.methods
+      .setAssetState({ denom, state } as any)
+      .accounts({
+        authority: authority ? authority.publicKey : provider.wallet.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(denom, oracleProgram.programId),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      });
+    return authority ? builder.signers([authority]).rpc() : builder.rpc();
+  }
+
+  function getAssetState(denom: string) {
+    return oracleProgram.methods
+      .getAssetState({ denom })
+      .accounts({ asset: deriveOracleAssetPda(denom, oracleProgram.programId) })
+      .view();
+  }
+
+  describe("Test 15.28: Asset Lifecycle States", () => {
+    it("Should start active and move through the wind-down states", async () => {
+      await setAsset("WIND", "", NEAR_PEG);
+
+      try {
+        expect(await getAssetState("WIND")).to.deep.equal({ active: {} });
+
+        for (const state of [{ borrowDisabled: {} }, { liquidationOnly: {} }, { frozen: {} }, { active: {} }]) {
+          await setAssetState("WIND", state);
+          expect(await getAssetState("WIND")).to.deep.equal(state);
+        }
+        console.log("✅ Asset moved through every lifecycle state");
+      } finally {
+        await removeAsset("WIND");
+      }
+    });
+
+    it("Should keep pricing a wound-down asset and report its state", async () => {
+      await setAsset("WIND", "", NEAR_PEG);
+
+      try {
+        await setAssetState("WIND", { liquidationOnly: {} });
+        const price = await getPrice("WIND");
+        expect(price.price.toNumber()).to.be.greaterThan(0);
+        expect(price.assetState).to.deep.equal({ liquidationOnly: {} });
+        console.log("✅ Liquidation-only asset still priced");
+      } finally {
+        await removeAsset("WIND");
+      }
+    });
+  });
+
+  describe("Test 15.29: Asset Lifecycle Authorization", () => {
+    it("Should reject a state change from a non-risk-manager", async () => {
+      await setAsset("WIND", "", NEAR_PEG);
+
+      try {
+        await setAssetState("WIND", { frozen: {} }, Keypair.generate());
+        assert.fail("Should have rejected the unauthorized signer");
+      } catch (error: any) {
+        console.log("✅ Unauthorized state change rejected");
+        expect(error.message).to.include("Unauthorized");
+      } finally {
+        await removeAsset("WIND");
+      }
+    });
+  });
+
+  after(() => {
+    console.log("\n✅ Price Source Adapter Tests Complete");
+    console.log("  Total Tests Passed: 31\n");
+  });
+});
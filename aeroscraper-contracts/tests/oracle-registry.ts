@@ -0,0 +1,90 @@
+/**
+ * Oracle asset registry helpers
+ *
+ * Each collateral asset's price feed configuration lives in its own PDA
+ * (seeds: ["asset", denom]) with a registry index in 0..asset_count.
+ * get_all_denoms and get_all_prices page over those PDAs, which the caller
+ * passes as remaining accounts in index order.
+ */
+
+import { AccountMeta, PublicKey } from "@solana/web3.js";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+
+export interface OracleAsset {
+  address: PublicKey;
+  denom: string;
+  index: number;
+  pythPriceAccount: PublicKey;
+}
+
+const decodePadded = (bytes: number[]): string =>
+  Buffer.from(bytes).toString("utf8").replace(/\0+$/, "");
+
+/** Asset config PDA for a denom */
+export function deriveOracleAssetPda(denom: string, oracleProgramId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("asset"), Buffer.from(denom)],
+    oracleProgramId
+  )[0];
+}
+
+/** All configured assets, ordered by registry index */
+export async function fetchOracleAssets(oracleProgram: Program<AerospacerOracle>): Promise<OracleAsset[]> {
+  const accounts = await oracleProgram.account.assetConfig.all();
+  return accounts
+    .map(({ publicKey, account }) => ({
+      address: publicKey,
+      denom: decodePadded(account.denom),
+      index: account.index,
+      pythPriceAccount: account.pythPriceAccount,
+    }))
+    .sort((a, b) => a.index - b.index);
+}
+
+/**
+ * The asset at the last registry index, which remove_data moves into the freed slot.
+ * Falls back to the removed denom's own PDA when no assets are configured
+ */
+export async function lastOracleAsset(
+  oracleProgram: Program<AerospacerOracle>,
+  denom: string
+): Promise<PublicKey> {
+  const assets = await fetchOracleAssets(oracleProgram);
+  if (assets.length === 0) {
+    return deriveOracleAssetPda(denom, oracleProgram.programId);
+  }
+  return assets[assets.length - 1].address;
+}
+
+/** Remaining accounts for set_data_batch: one asset PDA per denom, in order */
+export function oracleAssetMetas(denoms: string[], oracleProgramId: PublicKey): AccountMeta[] {
+  return denoms.map((denom) => ({
+    pubkey: deriveOracleAssetPda(denom, oracleProgramId),
+    isSigner: false,
+    isWritable: true,
+  }));
+}
+
+/** Every supported denom, calling get_all_denoms one page at a time */
+export async function getAllOracleDenoms(
+  oracleProgram: Program<AerospacerOracle>,
+  pageSize = 20
+): Promise<string[]> {
+  const assets = await fetchOracleAssets(oracleProgram);
+  const denoms: string[] = [];
+  for (let start = 0; start < assets.length; start += pageSize) {
+    const page = await oracleProgram.methods
+      .getAllDenoms({ start })
+      .remainingAccounts(
+        assets.slice(start, start + pageSize).map((asset) => ({
+          pubkey: asset.address,
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .view();
+    denoms.push(...page);
+  }
+  return denoms;
+}
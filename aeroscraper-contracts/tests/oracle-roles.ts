@@ -0,0 +1,163 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { deriveOracleAssetPda } from "./oracle-registry";
+import { oracleAssetParams, SOL_PRICE_FEED, SOL_PRICE_ID } from "./test-utils";
+
+describe("Oracle Contract - Role Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+  const DENOM = "RSOL";
+
+  const admin = provider.wallet as anchor.Wallet;
+  const riskManager = Keypair.generate();
+  const oracleManager = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+
+  async function grantRole(role: any, account: PublicKey, signer: Keypair | null = null) {
+    const builder = oracleProgram.methods
+      .grantRole({ role, account })
+      .accounts({
+        admin: signer ? signer.publicKey : admin.publicKey,
+        state: stateAccountPda,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  async function revokeRole(role: any) {
+    await oracleProgram.methods
+      .revokeRole({ role })
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  }
+
+  async function setAsset(signer: Keypair) {
+    await oracleProgram.methods
+      .setData(oracleAssetParams({
+        denom: DENOM,
+        decimal: 9,
+        priceId: SOL_PRICE_ID,
+        pythPriceAccount: SOL_PRICE_FEED,
+      }))
+      .accounts({
+        admin: signer.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(DENOM, oracleProgram.programId),
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function setAssetState(state: any, signer: Keypair) {
+    await oracleProgram.methods
+      .setAssetState({ denom: DENOM, state })
+      .accounts({
+        authority: signer.publicKey,
+        state: stateAccountPda,
+        asset: deriveOracleAssetPda(DENOM, oracleProgram.programId),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    const existingState = await provider.connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+
+    for (const kp of [riskManager, oracleManager, stranger]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+  });
+
+  after(async () => {
+    await revokeRole({ riskManager: {} });
+    await revokeRole({ oracleManager: {} });
+  });
+
+  it("Only the owner can grant roles", async () => {
+    try {
+      await grantRole({ oracleManager: {} }, stranger.publicKey, stranger);
+      assert.fail("Stranger should not be able to grant a role");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Oracle manager configures assets", async () => {
+    await grantRole({ oracleManager: {} }, oracleManager.publicKey);
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.oracleManager.toString(), oracleManager.publicKey.toString());
+
+    await setAsset(oracleManager);
+  });
+
+  it("Oracle manager cannot change asset lifecycle state", async () => {
+    try {
+      await setAssetState({ borrowDisabled: {} }, oracleManager);
+      assert.fail("set_asset_state needs the risk manager");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Risk manager changes asset lifecycle state", async () => {
+    await grantRole({ riskManager: {} }, riskManager.publicKey);
+
+    await setAssetState({ borrowDisabled: {} }, riskManager);
+    await setAssetState({ active: {} }, riskManager);
+  });
+
+  it("Risk manager cannot configure assets", async () => {
+    try {
+      await setAsset(riskManager);
+      assert.fail("set_data needs the oracle manager");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Revoked role holder loses access", async () => {
+    await revokeRole({ oracleManager: {} });
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.oracleManager.toString(), PublicKey.default.toString());
+
+    try {
+      await setAsset(oracleManager);
+      assert.fail("Revoked oracle manager should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
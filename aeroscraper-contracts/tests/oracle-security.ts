@@ -3,6 +3,8 @@ import { Program } from "@coral-xyz/anchor";
 import { AerospacerOracle } from "../target/types/aerospacer_oracle";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { assert, expect } from "chai";
+import { deriveOracleAssetPda, getAllOracleDenoms, lastOracleAsset, oracleAssetMetas } from "./oracle-registry";
+import { oracleAssetParams } from "./test-utils";
 
 describe("Oracle Contract - Security & Authorization Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -44,15 +46,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
     }
 
     await oracleProgram.methods
-      .setData({
+      .setData(oracleAssetParams({
         denom: "SOL",
         decimal: 9,
         priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
         pythPriceAccount: SOL_PRICE_FEED,
-      })
+      }))
       .accounts({
         admin: provider.wallet.publicKey,
         state: stateAccountPda,
+        asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+        systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .rpc();
@@ -68,15 +72,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
         {
           name: "set_data",
           fn: () => oracleProgram.methods
-            .setData({
+            .setData(oracleAssetParams({
               denom: "ETH",
               decimal: 18,
               priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
               pythPriceAccount: Keypair.generate().publicKey,
-            })
+            }))
             .accounts({
               admin: attacker.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda("ETH", oracleProgram.programId),
+              systemProgram: SystemProgram.programId,
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .signers([attacker])
@@ -91,6 +97,8 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
             .accounts({
               admin: attacker.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
+              lastAsset: await lastOracleAsset(oracleProgram, "SOL"),
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .signers([attacker])
@@ -139,15 +147,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
 
         try {
           await oracleProgram.methods
-            .setData({
+            .setData(oracleAssetParams({
               denom: "TEST",
               decimal: 6,
               priceId: invalidId,
               pythPriceAccount: Keypair.generate().publicKey,
-            })
+            }))
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+              systemProgram: SystemProgram.programId,
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -169,15 +179,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "",
             decimal: 6,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -196,15 +208,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "TEST",
             decimal: 0,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -227,15 +241,17 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
       for (const invalidId of [shortId, longId]) {
         try {
           await oracleProgram.methods
-            .setData({
+            .setData(oracleAssetParams({
               denom: "TEST",
               decimal: 6,
               priceId: invalidId,
               pythPriceAccount: SOL_PRICE_FEED,
-            })
+            }))
             .accounts({
               admin: provider.wallet.publicKey,
               state: stateAccountPda,
+              asset: deriveOracleAssetPda("TEST", oracleProgram.programId),
+              systemProgram: SystemProgram.programId,
               clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             })
             .rpc();
@@ -262,6 +278,8 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("NONEXISTENT", oracleProgram.programId),
+            lastAsset: await lastOracleAsset(oracleProgram, "NONEXISTENT"),
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -281,7 +299,7 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
       const priceResponse = await oracleProgram.methods
         .getPrice({ denom: "SOL" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
           pythPriceAccount: SOL_PRICE_FEED,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
@@ -300,12 +318,7 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
       expect(config).to.exist;
       console.log("  ✓ get_config accessible");
 
-      const denoms = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denoms = await getAllOracleDenoms(oracleProgram);
 
       expect(denoms.length).to.be.greaterThan(0);
       console.log("  ✓ get_all_denoms accessible");
@@ -313,7 +326,7 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
       const exists = await oracleProgram.methods
         .checkDenom({ denom: "SOL" })
         .accounts({
-          state: stateAccountPda,
+          asset: deriveOracleAssetPda("SOL", oracleProgram.programId),
         })
         .view();
 
@@ -329,20 +342,18 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
       console.log("🔒 Testing batch validation...");
 
       const batchWithInvalid = [
-        {
+        oracleAssetParams({
           denom: "ETH",
           decimal: 18,
           priceId: "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: Keypair.generate().publicKey,
-        },
-        {
+        }),
+        oracleAssetParams({
           denom: "",
           decimal: 8,
           priceId: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-          configuredAt: new anchor.BN(Date.now() / 1000),
           pythPriceAccount: Keypair.generate().publicKey,
-        },
+        }),
       ];
 
       try {
@@ -353,8 +364,10 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
+          .remainingAccounts(oracleAssetMetas(batchWithInvalid.map((d) => d.denom), oracleProgram.programId))
           .rpc();
 
         assert.fail("Should have rejected batch");
@@ -367,26 +380,23 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
 
   describe("Test 5.9: State Remains Consistent After Failed Operations", () => {
     it("Should not modify state when operation fails", async () => {
-      const denomsBefore = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denomsBefore = await getAllOracleDenoms(oracleProgram);
 
       console.log("🔒 Attempting failed operation...");
 
       try {
         await oracleProgram.methods
-          .setData({
+          .setData(oracleAssetParams({
             denom: "INVALID",
             decimal: 0,
             priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
             pythPriceAccount: SOL_PRICE_FEED,
-          })
+          }))
           .accounts({
             admin: provider.wallet.publicKey,
             state: stateAccountPda,
+            asset: deriveOracleAssetPda("INVALID", oracleProgram.programId),
+            systemProgram: SystemProgram.programId,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           })
           .rpc();
@@ -394,12 +404,7 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
         // Expected to fail
       }
 
-      const denomsAfter = await oracleProgram.methods
-        .getAllDenoms({})
-        .accounts({
-          state: stateAccountPda,
-        })
-        .view();
+      const denomsAfter = await getAllOracleDenoms(oracleProgram);
 
       assert.deepEqual(denomsAfter, denomsBefore);
       console.log("✅ State unchanged after failed operation");
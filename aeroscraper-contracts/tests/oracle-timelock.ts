@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { AerospacerOracle } from "../target/types/aerospacer_oracle";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { deriveOracleAssetPda, lastOracleAsset } from "./oracle-registry";
+import { oracleAssetParams, SOL_PRICE_FEED, SOL_PRICE_ID } from "./test-utils";
+
+describe("Oracle Contract - Timelock Tests", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const oracleProgram = anchor.workspace.AerospacerOracle as Program<AerospacerOracle>;
+
+  const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+  const DENOM = "TSOL";
+  const DELAY_SECS = 5;
+
+  const admin = provider.wallet as anchor.Wallet;
+  const stranger = Keypair.generate();
+  const keeper = Keypair.generate();
+
+  const [stateAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state")],
+    oracleProgram.programId
+  );
+  const asset = deriveOracleAssetPda(DENOM, oracleProgram.programId);
+  const [priceHistory] = PublicKey.findProgramAddressSync(
+    [Buffer.from("history"), Buffer.from(DENOM)],
+    oracleProgram.programId
+  );
+
+  const queuedChangePda = (id: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("timelock"), id.toArrayLike(Buffer, "le", 8)],
+      oracleProgram.programId
+    )[0];
+
+  const writable = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+
+  // Queue a change as the admin; returns its id
+  async function queue(change: any): Promise<BN> {
+    const id = (await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda)).nextChangeId;
+    await oracleProgram.methods
+      .queueChange({ change })
+      .accounts({
+        proposer: admin.publicKey,
+        state: stateAccountPda,
+        queuedChange: queuedChangePda(id),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return id;
+  }
+
+  async function execute(id: BN, remainingAccounts: any[] = []) {
+    await oracleProgram.methods
+      .executeChange({ id })
+      .accounts({
+        executor: stranger.publicKey,
+        state: stateAccountPda,
+        queuedChange: queuedChangePda(id),
+        proposer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([stranger])
+      .rpc();
+  }
+
+  async function assetState(): Promise<number> {
+    const account = await oracleProgram.account.assetConfig.fetch(asset);
+    return account.assetState;
+  }
+
+  before(async () => {
+    const existingState = await connection.getAccountInfo(stateAccountPda);
+    if (!existingState) {
+      await oracleProgram.methods
+        .initialize({ oracleAddress: PYTH_ORACLE_ADDRESS })
+        .accounts({
+          state: stateAccountPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+    }
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.timelockDelay.toNumber(), 0);
+
+    const sig = await connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(sig);
+
+    await oracleProgram.methods
+      .setData(oracleAssetParams({
+        denom: DENOM,
+        decimal: 9,
+        priceId: SOL_PRICE_ID,
+        pythPriceAccount: SOL_PRICE_FEED,
+      }))
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        asset,
+        systemProgram: SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  });
+
+  it("Queued keeper changes execute once due (zero delay)", async () => {
+    await execute(await queue({ addUpdater: { 0: { updater: keeper.publicKey } } }));
+    let state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.isTrue(state.updaters.some((u: PublicKey) => u.equals(keeper.publicKey)));
+
+    await execute(await queue({ removeUpdater: { 0: { updater: keeper.publicKey } } }));
+    state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.isFalse(state.updaters.some((u: PublicKey) => u.equals(keeper.publicKey)));
+  });
+
+  it("Queued asset state change executes against the asset account", async () => {
+    const id = await queue({ setAssetState: { 0: { denom: DENOM, state: { borrowDisabled: {} } } } });
+
+    try {
+      await execute(id);
+      assert.fail("Execution without the asset account should fail");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidAssetAccount");
+    }
+
+    await execute(id, [writable(asset)]);
+    assert.equal(await assetState(), 1);
+
+    await execute(await queue({ setAssetState: { 0: { denom: DENOM, state: { active: {} } } } }), [
+      writable(asset),
+    ]);
+    assert.equal(await assetState(), 0);
+  });
+
+  it("Queued price history creation has the executor pay rent", async () => {
+    if (await connection.getAccountInfo(priceHistory)) {
+      return;
+    }
+    await execute(await queue({ initPriceHistory: { 0: { denom: DENOM } } }), [
+      writable(asset),
+      writable(priceHistory),
+    ]);
+
+    const history = await oracleProgram.account.priceHistory.fetch(priceHistory);
+    assert.equal(Buffer.from(history.denom).toString().replace(/\0+$/, ""), DENOM);
+  });
+
+  it("Stranger cannot queue an asset state change", async () => {
+    const id = (await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda)).nextChangeId;
+    try {
+      await oracleProgram.methods
+        .queueChange({ change: { setAssetState: { 0: { denom: DENOM, state: { frozen: {} } } } } })
+        .accounts({
+          proposer: stranger.publicKey,
+          state: stateAccountPda,
+          queuedChange: queuedChangePda(id),
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to queue changes");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Direct config instructions are rejected once a delay is set", async () => {
+    await execute(await queue({ setTimelockDelay: { 0: new BN(DELAY_SECS) } }));
+
+    try {
+      await oracleProgram.methods
+        .setAssetState({ denom: DENOM, state: { frozen: {} } })
+        .accounts({
+          authority: admin.publicKey,
+          state: stateAccountPda,
+          asset,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+      assert.fail("set_asset_state should need the timelock");
+    } catch (error: any) {
+      expect(error.message).to.include("TimelockActive");
+    }
+
+    try {
+      await oracleProgram.methods
+        .addUpdater({ updater: keeper.publicKey })
+        .accounts({
+          authority: admin.publicKey,
+          state: stateAccountPda,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+      assert.fail("add_updater should need the timelock");
+    } catch (error: any) {
+      expect(error.message).to.include("TimelockActive");
+    }
+
+    try {
+      await oracleProgram.methods
+        .migrateAsset({ denom: DENOM })
+        .accounts({
+          admin: admin.publicKey,
+          state: stateAccountPda,
+          asset,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("migrate_asset should need the timelock");
+    } catch (error: any) {
+      expect(error.message).to.include("TimelockActive");
+    }
+  });
+
+  it("Queued change waits for the delay, then restores a zero delay", async () => {
+    const id = await queue({ setTimelockDelay: { 0: new BN(0) } });
+
+    try {
+      await execute(id);
+      assert.fail("Change should not be executable before its eta");
+    } catch (error: any) {
+      expect(error.message).to.include("ChangeNotReady");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, (DELAY_SECS + 2) * 1000));
+    await execute(id);
+
+    const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+    assert.equal(state.timelockDelay.toNumber(), 0);
+  });
+
+  after(async () => {
+    await oracleProgram.methods
+      .removeData({ collateralDenom: DENOM })
+      .accounts({
+        admin: admin.publicKey,
+        state: stateAccountPda,
+        asset,
+        lastAsset: await lastOracleAsset(oracleProgram, DENOM),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+  });
+});
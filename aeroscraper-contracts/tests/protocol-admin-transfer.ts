@@ -0,0 +1,94 @@
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { setupTestEnvironment, TestContext } from "./test-utils";
+
+describe("Protocol Contract - Admin Transfer Tests", () => {
+  let ctx: TestContext;
+  const newAdmin = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+
+    for (const kp of [newAdmin, stranger]) {
+      const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await ctx.provider.connection.confirmTransaction(sig);
+    }
+  });
+
+  it("Accepting without a pending transfer fails", async () => {
+    try {
+      await ctx.protocolProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: newAdmin.publicKey, state: ctx.protocolState })
+        .signers([newAdmin])
+        .rpc();
+      assert.fail("Accept should fail with no pending transfer");
+    } catch (error: any) {
+      expect(error.message).to.include("NoPendingAdmin");
+    }
+  });
+
+  it("Admin can propose and cancel a transfer", async () => {
+    await ctx.protocolProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+
+    let state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.pendingAdmin.toString(), newAdmin.publicKey.toString());
+
+    await ctx.protocolProgram.methods
+      .cancelAdminTransfer()
+      .accounts({ admin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+
+    state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+    assert.equal(state.admin.toString(), ctx.admin.publicKey.toString());
+  });
+
+  it("Only the proposed admin can accept", async () => {
+    await ctx.protocolProgram.methods
+      .proposeAdmin({ newAdmin: newAdmin.publicKey })
+      .accounts({ admin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+
+    try {
+      await ctx.protocolProgram.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: stranger.publicKey, state: ctx.protocolState })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to accept");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Proposed admin accepts and can hand control back", async () => {
+    await ctx.protocolProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: newAdmin.publicKey, state: ctx.protocolState })
+      .signers([newAdmin])
+      .rpc();
+
+    let state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.admin.toString(), newAdmin.publicKey.toString());
+    assert.equal(state.pendingAdmin.toString(), PublicKey.default.toString());
+
+    // Restore the provider wallet as admin for the other suites
+    await ctx.protocolProgram.methods
+      .proposeAdmin({ newAdmin: ctx.admin.publicKey })
+      .accounts({ admin: newAdmin.publicKey, state: ctx.protocolState })
+      .signers([newAdmin])
+      .rpc();
+    await ctx.protocolProgram.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+
+    state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.admin.toString(), ctx.admin.publicKey.toString());
+  });
+});
@@ -0,0 +1,190 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert, expect } from "chai";
+import {
+  setupTestEnvironment,
+  createTestUser,
+  openTroveForUser,
+  stakeInStabilityPool,
+  derivePDAs,
+  getTokenBalance,
+  SOL_DENOM,
+  MIN_LOAN_AMOUNT,
+  TestContext,
+} from "./test-utils";
+
+const PAUSE_MINTING = 1 << 0;
+const PAUSE_STAKING = 1 << 4;
+const PAUSE_FEE_DISTRIBUTION = 1 << 0;
+
+describe("Protocol Contract - Circuit Breaker Tests", () => {
+  let ctx: TestContext;
+  const guardian = Keypair.generate();
+  const stranger = Keypair.generate();
+  let borrower: Keypair;
+
+  const COLLATERAL = new BN(10_000_000_000); // 10 SOL
+  const LOAN = MIN_LOAN_AMOUNT.mul(new BN(100)); // 100 aUSD
+
+  async function setPaused(paused: number, authority: Keypair | null = null) {
+    const builder = ctx.protocolProgram.methods
+      .setPaused({ paused })
+      .accounts({
+        authority: authority ? authority.publicKey : ctx.admin.publicKey,
+        state: ctx.protocolState,
+      });
+    await (authority ? builder.signers([authority]) : builder).rpc();
+  }
+
+  async function unstake(user: Keypair, amount: BN) {
+    const pdas = derivePDAs(SOL_DENOM, user.publicKey, ctx.protocolProgram.programId);
+    await ctx.protocolProgram.methods
+      .unstake({ amount })
+      .accounts({
+        user: user.publicKey,
+        userStakeAmount: pdas.userStakeAmount,
+        state: ctx.protocolState,
+        userStablecoinAccount: await getAssociatedTokenAddress(ctx.stablecoinMint, user.publicKey),
+        protocolStablecoinVault: pdas.protocolStablecoinAccount,
+        stableCoinMint: ctx.stablecoinMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+
+    for (const kp of [guardian, stranger]) {
+      const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await ctx.provider.connection.confirmTransaction(sig);
+    }
+
+    await ctx.protocolProgram.methods
+      .grantRole({ role: { guardian: {} }, account: guardian.publicKey })
+      .accounts({ admin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+
+    ({ user: borrower } = await createTestUser(ctx.provider, ctx.collateralMint, COLLATERAL.mul(new BN(2))));
+  });
+
+  after(async () => {
+    // Leave the shared states unpaused for the other suites
+    await setPaused(0);
+    await ctx.feesProgram.methods
+      .setPaused({ paused: 0 })
+      .accounts({ authority: ctx.admin.publicKey, state: ctx.feeState })
+      .rpc();
+  });
+
+  it("Guardian can pause minting, which blocks open_trove", async () => {
+    await setPaused(PAUSE_MINTING, guardian);
+
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.paused, PAUSE_MINTING);
+
+    try {
+      await openTroveForUser(ctx, borrower, COLLATERAL, LOAN, SOL_DENOM);
+      assert.fail("open_trove should be paused");
+    } catch (error: any) {
+      expect(error.message).to.include("OperationPaused");
+    }
+  });
+
+  it("Guardian cannot lift a pause", async () => {
+    try {
+      await setPaused(0, guardian);
+      assert.fail("Guardian should not be able to unpause");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Unrelated signer cannot pause", async () => {
+    try {
+      await setPaused(PAUSE_STAKING, stranger);
+      assert.fail("Stranger should not be able to pause");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects unknown pause flags", async () => {
+    try {
+      await setPaused(0x80);
+      assert.fail("Unknown flags should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidPauseFlags");
+    }
+  });
+
+  it("Admin can unpause minting", async () => {
+    await setPaused(0);
+    await openTroveForUser(ctx, borrower, COLLATERAL, LOAN, SOL_DENOM);
+
+    const pdas = derivePDAs(SOL_DENOM, borrower.publicKey, ctx.protocolProgram.programId);
+    const debt = await ctx.protocolProgram.account.userDebtAmount.fetch(pdas.userDebtAmount);
+    assert.isTrue(debt.amount.gtn(0));
+  });
+
+  it("Pausing staking blocks stake but not unstake", async () => {
+    const stakeAmount = MIN_LOAN_AMOUNT.mul(new BN(10));
+    await stakeInStabilityPool(ctx, borrower, stakeAmount);
+
+    await setPaused(PAUSE_STAKING, guardian);
+
+    try {
+      await stakeInStabilityPool(ctx, borrower, stakeAmount);
+      assert.fail("stake should be paused");
+    } catch (error: any) {
+      expect(error.message).to.include("OperationPaused");
+    }
+
+    // Stakers can always leave the pool
+    await unstake(borrower, stakeAmount);
+    const pdas = derivePDAs(SOL_DENOM, borrower.publicKey, ctx.protocolProgram.programId);
+    const stake = await ctx.protocolProgram.account.userStakeAmount.fetch(pdas.userStakeAmount);
+    assert.equal(stake.amount.toString(), "0");
+
+    await setPaused(0);
+  });
+
+  it("Pausing fee distribution rejects borrowing that owes a fee", async () => {
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.isAbove(state.protocolFee, 0);
+
+    await ctx.feesProgram.methods
+      .setPaused({ paused: PAUSE_FEE_DISTRIBUTION })
+      .accounts({ authority: ctx.admin.publicKey, state: ctx.feeState })
+      .rpc();
+
+    const { user } = await createTestUser(ctx.provider, ctx.collateralMint, COLLATERAL);
+    const fee1Before = await getTokenBalance(ctx.provider.connection, ctx.feeAddress1TokenAccount);
+    const fee2Before = await getTokenBalance(ctx.provider.connection, ctx.feeAddress2TokenAccount);
+
+    try {
+      await openTroveForUser(ctx, user, COLLATERAL, LOAN, SOL_DENOM);
+      assert.fail("open_trove should not run without charging its fee");
+    } catch (error: any) {
+      expect(error.message).to.include("FeeDistributionPaused");
+    }
+
+    const pdas = derivePDAs(SOL_DENOM, user.publicKey, ctx.protocolProgram.programId);
+    assert.isNull(await ctx.provider.connection.getAccountInfo(pdas.userDebtAmount));
+    assert.equal(
+      (await getTokenBalance(ctx.provider.connection, ctx.feeAddress1TokenAccount)).toString(),
+      fee1Before.toString()
+    );
+    assert.equal(
+      (await getTokenBalance(ctx.provider.connection, ctx.feeAddress2TokenAccount)).toString(),
+      fee2Before.toString()
+    );
+
+    await ctx.feesProgram.methods
+      .setPaused({ paused: 0 })
+      .accounts({ authority: ctx.admin.publicKey, state: ctx.feeState })
+      .rpc();
+  });
+});
@@ -0,0 +1,245 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  ComputeBudgetProgram,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { fetchAllTroves, sortTrovesByICR, findLiquidatableTroves, TroveData } from "./trove-indexer";
+import { setupTestEnvironment, TestContext, derivePDAs, SOL_DENOM, SOL_PRICE_FEED } from "./test-utils";
+
+/**
+ * Compute-unit budget report for liquidate_troves and redeem (see COMPUTE_BUDGET.md).
+ *
+ * Simulates each instruction against the troves that already exist on the cluster,
+ * with 1..N troves, inside a v0 transaction whose non-program accounts come from an
+ * address lookup table. Nothing is committed; the output is a table of
+ * serialized size, account count and units consumed per trove count.
+ */
+describe("Protocol - Compute Budget Report", () => {
+  const MAX_TX_BYTES = 1232;
+  const MAX_ACCOUNT_LOCKS = 64;
+  const MAX_TX_UNITS = 1_400_000;
+
+  let ctx: TestContext;
+  let sortedTroves: TroveData[];
+
+  const eventAuthority = (programId: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], programId)[0];
+
+  const troveAccounts = async (troves: TroveData[]): Promise<AccountMeta[]> => {
+    const metas: AccountMeta[] = [];
+    for (const trove of troves) {
+      metas.push({ pubkey: trove.debtAccount, isSigner: false, isWritable: true });
+      metas.push({ pubkey: trove.collateralAccount, isSigner: false, isWritable: true });
+      metas.push({ pubkey: trove.liquidityThresholdAccount, isSigner: false, isWritable: true });
+      metas.push({
+        pubkey: await getAssociatedTokenAddress(ctx.collateralMint, trove.owner),
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    return metas;
+  };
+
+  const createLookupTable = async (addresses: PublicKey[]): Promise<AddressLookupTableAccount> => {
+    const payer = ctx.admin.publicKey;
+    const recentSlot = await ctx.provider.connection.getSlot("finalized");
+    const [createIx, table] = AddressLookupTableProgram.createLookupTable({
+      authority: payer,
+      payer,
+      recentSlot,
+    });
+    await ctx.provider.sendAndConfirm(new anchor.web3.Transaction().add(createIx));
+
+    // extendLookupTable is itself size-limited, so add addresses in chunks
+    for (let i = 0; i < addresses.length; i += 20) {
+      const extendIx = AddressLookupTableProgram.extendLookupTable({
+        lookupTable: table,
+        authority: payer,
+        payer,
+        addresses: addresses.slice(i, i + 20),
+      });
+      await ctx.provider.sendAndConfirm(new anchor.web3.Transaction().add(extendIx));
+    }
+
+    // Lookup tables only become usable one slot after their last extension
+    const activeFrom = await ctx.provider.connection.getSlot();
+    while ((await ctx.provider.connection.getSlot()) <= activeFrom) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    return (await ctx.provider.connection.getAddressLookupTable(table)).value!;
+  };
+
+  const measure = async (ix: TransactionInstruction, lookupTable: AddressLookupTableAccount) => {
+    const { blockhash } = await ctx.provider.connection.getLatestBlockhash();
+    const message = new TransactionMessage({
+      payerKey: ctx.admin.publicKey,
+      recentBlockhash: blockhash,
+      instructions: [ComputeBudgetProgram.setComputeUnitLimit({ units: MAX_TX_UNITS }), ix],
+    }).compileToV0Message([lookupTable]);
+
+    const accounts =
+      message.staticAccountKeys.length +
+      message.addressTableLookups.reduce(
+        (sum, lookup) => sum + lookup.writableIndexes.length + lookup.readonlyIndexes.length,
+        0
+      );
+
+    let bytes: number;
+    try {
+      bytes = new VersionedTransaction(message).serialize().length;
+    } catch (error) {
+      return { bytes: Infinity, accounts, units: 0, err: "transaction too large" };
+    }
+
+    const simulation = await ctx.provider.connection.simulateTransaction(new VersionedTransaction(message), {
+      sigVerify: false,
+      replaceRecentBlockhash: true,
+    });
+
+    return {
+      bytes,
+      accounts,
+      units: simulation.value.unitsConsumed ?? 0,
+      err: simulation.value.err ? JSON.stringify(simulation.value.err) : "",
+    };
+  };
+
+  // perTrove is the marginal cost of the row's last trove, the figure COMPUTE_BUDGET.md records
+  const report = (rows: { troves: number; bytes: number; accounts: number; units: number; err: string }[]) => {
+    console.table(
+      rows.map((row, i) => ({
+        ...row,
+        perTrove: i === 0 ? null : row.units - rows[i - 1].units,
+        fits: row.bytes <= MAX_TX_BYTES && row.accounts <= MAX_ACCOUNT_LOCKS && row.units <= MAX_TX_UNITS && !row.err,
+      }))
+    );
+  };
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+    sortedTroves = sortTrovesByICR(await fetchAllTroves(ctx.provider.connection, ctx.protocolProgram, SOL_DENOM));
+  });
+
+  it("Redemption: units and size per trove count", async function () {
+    if (sortedTroves.length === 0) {
+      this.skip();
+    }
+
+    const redeemer = ctx.admin.publicKey;
+    const pdas = derivePDAs(SOL_DENOM, redeemer, ctx.protocolProgram.programId);
+    const candidates = sortedTroves.slice(0, 16);
+    const remaining = await troveAccounts(candidates);
+
+    const fixed = {
+      user: redeemer,
+      state: ctx.protocolState,
+      userDebtAmount: pdas.userDebtAmount,
+      liquidityThreshold: pdas.liquidityThreshold,
+      userStablecoinAccount: await getAssociatedTokenAddress(ctx.stablecoinMint, redeemer),
+      userCollateralAmount: pdas.userCollateralAmount,
+      userCollateralAccount: await getAssociatedTokenAddress(ctx.collateralMint, redeemer),
+      protocolStablecoinVault: pdas.protocolStablecoinAccount,
+      protocolCollateralVault: pdas.protocolCollateralAccount,
+      stableCoinMint: ctx.stablecoinMint,
+      totalCollateralAmount: pdas.totalCollateralAmount,
+      oracleProgram: ctx.oracleProgram.programId,
+      oracleState: ctx.oracleState,
+      oracleAsset: ctx.oracleAsset,
+      feesProgram: ctx.feesProgram.programId,
+      feesEventAuthority: eventAuthority(ctx.feesProgram.programId),
+      feesState: ctx.feeState,
+      stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
+      feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,
+      feeAddress2TokenAccount: ctx.feeAddress2TokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const lookupTable = await createLookupTable([
+      ...Object.values(fixed).filter((key) => !key.equals(redeemer)),
+      eventAuthority(ctx.protocolProgram.programId),
+      ...remaining.map((meta) => meta.pubkey),
+    ]);
+
+    const rows = [];
+    for (let troves = 1; troves <= candidates.length; troves++) {
+      // Redeem the full debt of the first `troves` troves so every one of them is touched
+      const amount = candidates
+        .slice(0, troves)
+        .reduce((sum, trove) => sum.add(new BN(trove.debt.toString())), new BN(0));
+      const ix = await ctx.protocolProgram.methods
+        .redeem({ amount, collateralDenom: SOL_DENOM })
+        .accounts(fixed as any)
+        .remainingAccounts(remaining.slice(0, troves * 4))
+        .instruction();
+      rows.push({ troves, ...(await measure(ix, lookupTable)) });
+    }
+
+    report(rows);
+  });
+
+  it("Liquidation: units and size per trove count", async function () {
+    const liquidatable = findLiquidatableTroves(sortedTroves, 110_000_000).slice(0, 16);
+    if (liquidatable.length === 0) {
+      this.skip();
+    }
+
+    const liquidator = ctx.admin.publicKey;
+    const pdas = derivePDAs(SOL_DENOM, liquidator, ctx.protocolProgram.programId);
+    const remaining = await troveAccounts(liquidatable);
+    const [stabilityPoolSnapshot] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stability_pool_snapshot"), Buffer.from(SOL_DENOM)],
+      ctx.protocolProgram.programId
+    );
+
+    const fixed = {
+      liquidator,
+      state: ctx.protocolState,
+      stableCoinMint: ctx.stablecoinMint,
+      protocolStablecoinVault: pdas.protocolStablecoinAccount,
+      protocolCollateralVault: pdas.protocolCollateralAccount,
+      totalCollateralAmount: pdas.totalCollateralAmount,
+      oracleProgram: ctx.oracleProgram.programId,
+      oracleState: ctx.oracleState,
+      oracleAsset: ctx.oracleAsset,
+      oracleEventAuthority: eventAuthority(ctx.oracleProgram.programId),
+      pythPriceAccount: SOL_PRICE_FEED,
+      // Single-source asset without a price history; each one set adds an account
+      secondaryPriceAccount: null,
+      tertiaryPriceAccount: null,
+      priceHistory: null,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      stabilityPoolSnapshot,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+    const lookupTable = await createLookupTable([
+      ...Object.values(fixed).filter((key): key is PublicKey => key !== null && !key.equals(liquidator)),
+      eventAuthority(ctx.protocolProgram.programId),
+      ...remaining.map((meta) => meta.pubkey),
+    ]);
+
+    const rows = [];
+    for (let troves = 1; troves <= liquidatable.length; troves++) {
+      const ix = await ctx.protocolProgram.methods
+        .liquidateTroves({
+          liquidationList: liquidatable.slice(0, troves).map((trove) => trove.owner),
+          collateralDenom: SOL_DENOM,
+        })
+        .accounts(fixed as any)
+        .remainingAccounts(remaining.slice(0, troves * 4))
+        .instruction();
+      rows.push({ troves, ...(await measure(ix, lookupTable)) });
+    }
+
+    report(rows);
+  });
+});
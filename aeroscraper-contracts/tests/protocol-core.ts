@@ -15,6 +15,7 @@ import {
 } from "@solana/spl-token";
 import { assert } from "chai";
 import { loadTestUsers } from "./test-utils";
+import { deriveOracleAssetPda } from "./oracle-registry";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
 
 // Constants
@@ -133,6 +134,7 @@ describe("Aeroscraper Protocol Core Operations", () => {
   // Program state accounts
   let protocolState: PublicKey;
   let oracleState: PublicKey;
+  let oracleAsset: PublicKey; // Oracle asset config PDA for SOL
   let feesState: PublicKey;
 
   // User trove accounts
@@ -221,6 +223,7 @@ describe("Aeroscraper Protocol Core Operations", () => {
 
     protocolState = protocolStatePda;
     oracleState = oracleStatePda;
+    oracleAsset = deriveOracleAssetPda("SOL", oracleProgram.programId);
     feesState = feesStatePda;
 
     // Get the existing stablecoin mint from the state account
@@ -628,9 +631,11 @@ describe("Aeroscraper Protocol Core Operations", () => {
             stableCoinMint: stablecoinMint,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
             feeAddress1TokenAccount: feeAddress1TokenAccount,
@@ -726,6 +731,7 @@ describe("Aeroscraper Protocol Core Operations", () => {
             node: user1NodePDA,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -784,9 +790,11 @@ describe("Aeroscraper Protocol Core Operations", () => {
             stableCoinMint: stablecoinMint,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
             feeAddress1TokenAccount: feeAddress1TokenAccount,
@@ -894,9 +902,11 @@ describe("Aeroscraper Protocol Core Operations", () => {
             stableCoinMint: stablecoinMint,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
             feeAddress1TokenAccount: feeAddress1TokenAccount,
@@ -958,9 +968,11 @@ describe("Aeroscraper Protocol Core Operations", () => {
             stableCoinMint: stablecoinMint,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
             feeAddress1TokenAccount: feeAddress1TokenAccount,
@@ -1019,6 +1031,7 @@ describe("Aeroscraper Protocol Core Operations", () => {
             node: user1NodePDA,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: SOL_PRICE_FEED,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -1050,6 +1063,7 @@ describe("Aeroscraper Protocol Core Operations", () => {
             protocolStablecoinAccount: protocolStablecoinAccountPDA,
             stableCoinMint: stablecoinMint,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
             feeAddress1TokenAccount: feeAddress1TokenAccount,
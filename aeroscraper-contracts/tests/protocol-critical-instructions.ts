@@ -127,9 +127,11 @@ describe("Protocol Contract - Critical Instructions (Full Functional Tests)", ()
             protocolCollateralAccount: borrowerPDAs.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -230,9 +232,11 @@ describe("Protocol Contract - Critical Instructions (Full Functional Tests)", ()
           protocolStablecoinAccount: redeemerPDAs.protocolStablecoinAccount,
           oracleProgram: ctx.oracleProgram.programId,
           oracleState: ctx.oracleState,
+          oracleAsset: ctx.oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: ctx.feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
           feesState: ctx.feeState,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert, expect } from "chai";
+import {
+  setupTestEnvironment,
+  createTestUser,
+  openTroveForUser,
+  derivePDAs,
+  getTokenBalance,
+  SOL_DENOM,
+  SOL_PRICE_FEED,
+  MIN_LOAN_AMOUNT,
+  TestContext,
+} from "./test-utils";
+
+// Shutdown is permanent, so this suite only runs against a throwaway validator:
+//   SHUTDOWN_TESTS=1 anchor test
+// Redemption opens 72h after shutdown, which a live validator cannot skip ahead to;
+// the post-grace payout math is covered by the trove_management unit tests
+const describeShutdown = process.env.SHUTDOWN_TESTS ? describe : describe.skip;
+
+describeShutdown("Protocol Contract - Emergency Shutdown Tests", () => {
+  let ctx: TestContext;
+  let borrower: Keypair;
+  let borrowerCollateralAccount: PublicKey;
+  let settledBorrower: Keypair;
+  const keeper = Keypair.generate();
+
+  const COLLATERAL = new BN(10_000_000_000); // 10 SOL
+  const LOAN = MIN_LOAN_AMOUNT.mul(new BN(10)); // 10 aUSD
+
+  let collateralSettlement: PublicKey;
+
+  async function settleTrove(user: PublicKey) {
+    const pdas = derivePDAs(SOL_DENOM, user, ctx.protocolProgram.programId);
+    await ctx.protocolProgram.methods
+      .settleTrove({ targetUser: user, collateralDenom: SOL_DENOM })
+      .accounts({
+        caller: keeper.publicKey,
+        state: ctx.protocolState,
+        userDebtAmount: pdas.userDebtAmount,
+        userCollateralAmount: pdas.userCollateralAmount,
+        collateralSettlement,
+        totalCollateralAmount: pdas.totalCollateralAmount,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([keeper])
+      .rpc();
+  }
+
+  async function redeemSettlement(user: Keypair, amount: BN) {
+    const pdas = derivePDAs(SOL_DENOM, user.publicKey, ctx.protocolProgram.programId);
+    await ctx.protocolProgram.methods
+      .redeemSettlement({ amount })
+      .accounts({
+        user: user.publicKey,
+        state: ctx.protocolState,
+        userStablecoinAccount: await getAssociatedTokenAddress(ctx.stablecoinMint, user.publicKey),
+        stableCoinMint: ctx.stablecoinMint,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: collateralSettlement, isSigner: false, isWritable: true },
+        { pubkey: pdas.protocolCollateralAccount, isSigner: false, isWritable: true },
+        {
+          pubkey: await getAssociatedTokenAddress(ctx.collateralMint, user.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+
+    [collateralSettlement] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral_settlement"), Buffer.from(SOL_DENOM)],
+      ctx.protocolProgram.programId
+    );
+
+    ({ user: borrower, collateralAccount: borrowerCollateralAccount } = await createTestUser(
+      ctx.provider,
+      ctx.collateralMint,
+      COLLATERAL
+    ));
+    ({ user: settledBorrower } = await createTestUser(ctx.provider, ctx.collateralMint, COLLATERAL));
+    await openTroveForUser(ctx, borrower, COLLATERAL, LOAN, SOL_DENOM);
+    await openTroveForUser(ctx, settledBorrower, COLLATERAL, LOAN, SOL_DENOM);
+
+    const sig = await ctx.provider.connection.requestAirdrop(keeper.publicKey, 100_000_000);
+    await ctx.provider.connection.confirmTransaction(sig);
+  });
+
+  it("Settlement redemption is rejected before shutdown", async () => {
+    try {
+      await redeemSettlement(borrower, MIN_LOAN_AMOUNT);
+      assert.fail("redeem_settlement should require a shutdown");
+    } catch (error: any) {
+      expect(error.message).to.include("ProtocolNotShutdown");
+    }
+  });
+
+  it("Admin shuts down and freezes the SOL price", async () => {
+    await ctx.protocolProgram.methods
+      .emergencyShutdown({ collateralDenom: SOL_DENOM })
+      .accounts({
+        admin: ctx.admin.publicKey,
+        state: ctx.protocolState,
+        collateralSettlement,
+        oracleProgram: ctx.oracleProgram.programId,
+        oracleState: ctx.oracleState,
+        oracleAsset: ctx.oracleAsset,
+        pythPriceAccount: SOL_PRICE_FEED,
+        secondaryPriceAccount: null,
+        tertiaryPriceAccount: null,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.isTrue(state.isShutdown);
+    assert.equal(state.settledDenomCount, 1);
+
+    const settlement = await ctx.protocolProgram.account.collateralSettlement.fetch(collateralSettlement);
+    assert.equal(settlement.denom, SOL_DENOM);
+    assert.isTrue(settlement.price.gtn(0));
+    assert.equal(settlement.backingCollateral.toString(), "0");
+  });
+
+  it("Minting stops after shutdown", async () => {
+    const { user } = await createTestUser(ctx.provider, ctx.collateralMint, COLLATERAL);
+    try {
+      await openTroveForUser(ctx, user, COLLATERAL, LOAN, SOL_DENOM);
+      assert.fail("open_trove should be rejected after shutdown");
+    } catch (error: any) {
+      expect(error.message).to.include("ProtocolShutdown");
+    }
+  });
+
+  it("Keeper settles a trove into the aUSD backing", async () => {
+    await settleTrove(settledBorrower.publicKey);
+
+    const pdas = derivePDAs(SOL_DENOM, settledBorrower.publicKey, ctx.protocolProgram.programId);
+    const debt = await ctx.protocolProgram.account.userDebtAmount.fetch(pdas.userDebtAmount);
+    assert.equal(debt.amount.toString(), "0");
+
+    const collateral = await ctx.protocolProgram.account.userCollateralAmount.fetch(pdas.userCollateralAmount);
+    const settlement = await ctx.protocolProgram.account.collateralSettlement.fetch(collateralSettlement);
+    assert.isTrue(settlement.backingCollateral.gtn(0));
+    assert.equal(collateral.amount.add(settlement.backingCollateral).toString(), COLLATERAL.toString());
+  });
+
+  it("A settled trove cannot be settled twice", async () => {
+    try {
+      await settleTrove(settledBorrower.publicKey);
+      assert.fail("Second settlement should fail");
+    } catch (error: any) {
+      expect(error.message).to.include("TroveDoesNotExist");
+    }
+  });
+
+  it("Settlement redemption waits for the grace period", async () => {
+    try {
+      await redeemSettlement(borrower, MIN_LOAN_AMOUNT);
+      assert.fail("redeem_settlement should not be open yet");
+    } catch (error: any) {
+      expect(error.message).to.include("SettlementNotOpen");
+    }
+  });
+
+  it("Owner withdraws the collateral left after settlement", async () => {
+    const pdas = derivePDAs(SOL_DENOM, borrower.publicKey, ctx.protocolProgram.programId);
+    const backingBefore = (await ctx.protocolProgram.account.collateralSettlement.fetch(collateralSettlement))
+      .backingCollateral;
+    const balanceBefore = await getTokenBalance(ctx.provider.connection, borrowerCollateralAccount);
+
+    await ctx.protocolProgram.methods
+      .withdrawSettledCollateral({ collateralDenom: SOL_DENOM })
+      .accounts({
+        user: borrower.publicKey,
+        state: ctx.protocolState,
+        userDebtAmount: pdas.userDebtAmount,
+        userCollateralAmount: pdas.userCollateralAmount,
+        liquidityThreshold: pdas.liquidityThreshold,
+        collateralSettlement,
+        totalCollateralAmount: pdas.totalCollateralAmount,
+        userCollateralAccount: borrowerCollateralAccount,
+        protocolCollateralVault: pdas.protocolCollateralAccount,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([borrower])
+      .rpc();
+
+    // The debt was settled on the way out: its collateral joined the backing, the rest came back
+    const backingAfter = (await ctx.protocolProgram.account.collateralSettlement.fetch(collateralSettlement))
+      .backingCollateral;
+    const reserved = backingAfter.sub(backingBefore);
+    assert.isTrue(reserved.gtn(0));
+
+    const balanceAfter = await getTokenBalance(ctx.provider.connection, borrowerCollateralAccount);
+    assert.equal(balanceAfter.sub(balanceBefore).toString(), COLLATERAL.sub(reserved).toString());
+
+    const debt = await ctx.protocolProgram.account.userDebtAmount.fetch(pdas.userDebtAmount);
+    assert.equal(debt.amount.toString(), "0");
+    const collateral = await ctx.protocolProgram.account.userCollateralAmount.fetch(pdas.userCollateralAmount);
+    assert.equal(collateral.amount.toString(), "0");
+  });
+});
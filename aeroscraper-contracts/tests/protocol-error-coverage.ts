@@ -153,9 +153,11 @@ describe("Protocol Contract - Error Coverage Tests", () => {
             protocolCollateralAccount: pdas.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -212,6 +214,7 @@ describe("Protocol Contract - Error Coverage Tests", () => {
             totalCollateralAmount: pdas.totalCollateralAmount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -291,9 +294,11 @@ describe("Protocol Contract - Error Coverage Tests", () => {
             protocolCollateralAccount: pdas.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -357,9 +362,11 @@ describe("Protocol Contract - Error Coverage Tests", () => {
             protocolCollateralAccount: pdas.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -421,6 +428,7 @@ describe("Protocol Contract - Error Coverage Tests", () => {
             totalCollateralAmount: pdas.totalCollateralAmount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             tokenProgram: TOKEN_PROGRAM_ID,
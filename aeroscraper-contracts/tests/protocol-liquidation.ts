@@ -290,9 +290,11 @@ describe("Protocol Contract - Liquidation Tests", () => {
           stableCoinMint: ctx.stablecoinMint,
           oracleProgram: ctx.oracleProgram.programId,
           oracleState: ctx.oracleState,
+          oracleAsset: ctx.oracleAsset,
           pythPriceAccount: new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"), // SOL price feed
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: ctx.feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
           feesState: ctx.feeState,
           stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
           feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,
@@ -360,6 +362,7 @@ describe("Protocol Contract - Liquidation Tests", () => {
         totalCollateralAmount: pdas.totalCollateralAmount,
         oracleProgram: ctx.oracleProgram.programId,
         oracleState: ctx.oracleState,
+        oracleAsset: ctx.oracleAsset,
         pythPriceAccount: new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"), // SOL price feed
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -504,7 +507,7 @@ describe("Protocol Contract - Liquidation Tests", () => {
 
       // Oracle context
       const oracleProgramId = ctx.oracleProgram.programId;
-      const oracleState = ctx.oracleState;
+      const oracleAsset = ctx.oracleAsset;
       const pythPriceAccount = new PublicKey("J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix");
       const clock = anchor.web3.SYSVAR_CLOCK_PUBKEY;
 
@@ -617,7 +620,8 @@ describe("Protocol Contract - Liquidation Tests", () => {
           userCollateralTokenAccount: userCollateralTokenAccount,
 
           oracleProgram: oracleProgramId,
-          oracleState: oracleState,
+          oracleState: ctx.oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: pythPriceAccount,
           clock: clock,
 
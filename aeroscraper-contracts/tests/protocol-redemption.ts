@@ -315,7 +315,9 @@ describe("Protocol Contract - Redemption Tests", () => {
           totalCollateralAmount: pdas.totalCollateralAmount,
           oracleProgram: ctx.oracleProgram.programId,
           oracleState: ctx.oracleState,
+          oracleAsset: ctx.oracleAsset,
           feesProgram: ctx.feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
           feesState: ctx.feeState,
           stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
           feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,
@@ -0,0 +1,126 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { setupTestEnvironment, TestContext } from "./test-utils";
+
+const DEFAULT_MINIMUM_COLLATERAL_RATIO = new BN(115_000_000); // 115%
+
+describe("Protocol Contract - Role Tests", () => {
+  let ctx: TestContext;
+  const riskManager = Keypair.generate();
+  const oracleManager = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  async function grantRole(role: any, account: PublicKey, signer: Keypair | null = null) {
+    const builder = ctx.protocolProgram.methods
+      .grantRole({ role, account })
+      .accounts({ admin: signer ? signer.publicKey : ctx.admin.publicKey, state: ctx.protocolState });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  async function revokeRole(role: any) {
+    await ctx.protocolProgram.methods
+      .revokeRole({ role })
+      .accounts({ admin: ctx.admin.publicKey, state: ctx.protocolState })
+      .rpc();
+  }
+
+  async function updateRiskParams(params: { minimumCollateralRatio: BN | null; protocolFee: number | null }, signer: Keypair) {
+    await ctx.protocolProgram.methods
+      .updateRiskParams(params)
+      .accounts({ authority: signer.publicKey, state: ctx.protocolState })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+
+    for (const kp of [riskManager, oracleManager, stranger]) {
+      const sig = await ctx.provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await ctx.provider.connection.confirmTransaction(sig);
+    }
+  });
+
+  after(async () => {
+    await revokeRole({ riskManager: {} });
+    await revokeRole({ oracleManager: {} });
+  });
+
+  it("Only the owner can grant roles", async () => {
+    try {
+      await grantRole({ riskManager: {} }, stranger.publicKey, stranger);
+      assert.fail("Stranger should not be able to grant a role");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Risk manager updates the minimum collateral ratio and protocol fee", async () => {
+    await grantRole({ riskManager: {} }, riskManager.publicKey);
+
+    const before = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    await updateRiskParams({ minimumCollateralRatio: new BN(120_000_000), protocolFee: before.protocolFee + 1 }, riskManager);
+
+    const after = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(after.minimumCollateralRatio.toString(), "120000000");
+    assert.equal(after.protocolFee, before.protocolFee + 1);
+
+    await updateRiskParams(
+      { minimumCollateralRatio: DEFAULT_MINIMUM_COLLATERAL_RATIO, protocolFee: before.protocolFee },
+      riskManager
+    );
+  });
+
+  it("Minimum collateral ratio must stay above the liquidation threshold", async () => {
+    try {
+      await updateRiskParams({ minimumCollateralRatio: new BN(110_000_000), protocolFee: null }, riskManager);
+      assert.fail("A ratio at the liquidation threshold should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("InvalidCollateralRatio");
+    }
+  });
+
+  it("Risk manager cannot change oracle addresses", async () => {
+    try {
+      await ctx.protocolProgram.methods
+        .updateProtocolAddresses({
+          oracleHelperAddr: Keypair.generate().publicKey,
+          oracleStateAddr: null,
+          feeDistributorAddr: null,
+          feeStateAddr: null,
+        })
+        .accounts({ admin: riskManager.publicKey, state: ctx.protocolState })
+        .signers([riskManager])
+        .rpc();
+      assert.fail("Oracle addresses need the oracle manager");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Oracle manager cannot change risk parameters", async () => {
+    await grantRole({ oracleManager: {} }, oracleManager.publicKey);
+
+    try {
+      await updateRiskParams({ minimumCollateralRatio: null, protocolFee: 0 }, oracleManager);
+      assert.fail("Risk parameters need the risk manager");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Revoked risk manager loses access", async () => {
+    await revokeRole({ riskManager: {} });
+
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.riskManager.toString(), PublicKey.default.toString());
+
+    try {
+      await updateRiskParams({ minimumCollateralRatio: null, protocolFee: 0 }, riskManager);
+      assert.fail("Revoked risk manager should be rejected");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+});
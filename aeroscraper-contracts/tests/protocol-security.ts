@@ -207,9 +207,11 @@ describe("Protocol Contract - Security Tests", () => {
             protocolCollateralAccount: pdas.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -273,9 +275,11 @@ describe("Protocol Contract - Security Tests", () => {
             protocolCollateralAccount: pdas.protocolCollateralAccount,
             oracleProgram: ctx.oracleProgram.programId,
             oracleState: ctx.oracleState,
+            oracleAsset: ctx.oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: ctx.feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
             feesState: ctx.feeState,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -0,0 +1,145 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { setupTestEnvironment, TestContext } from "./test-utils";
+
+describe("Protocol Contract - Timelock Tests", () => {
+  let ctx: TestContext;
+  const stranger = Keypair.generate();
+  const DELAY_SECS = 5;
+
+  const queuedChangePda = (id: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("timelock"), id.toArrayLike(Buffer, "le", 8)],
+      ctx.protocolProgram.programId
+    )[0];
+
+  // Queue a change as the admin; returns its id
+  async function queue(change: any): Promise<BN> {
+    const id = (await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState)).nextChangeId;
+    await ctx.protocolProgram.methods
+      .queueChange({ change })
+      .accounts({
+        proposer: ctx.admin.publicKey,
+        state: ctx.protocolState,
+        queuedChange: queuedChangePda(id),
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return id;
+  }
+
+  async function execute(id: BN) {
+    await ctx.protocolProgram.methods
+      .executeChange({ id })
+      .accounts({
+        executor: stranger.publicKey,
+        state: ctx.protocolState,
+        queuedChange: queuedChangePda(id),
+        proposer: ctx.admin.publicKey,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([stranger])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestEnvironment();
+
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.timelockDelay.toNumber(), 0);
+
+    const sig = await ctx.provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+    await ctx.provider.connection.confirmTransaction(sig);
+  });
+
+  it("Queued risk parameter change executes once due (zero delay)", async () => {
+    const before = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    const id = await queue({
+      updateRiskParams: { 0: { minimumCollateralRatio: null, protocolFee: before.protocolFee + 1 } },
+    });
+    await execute(id);
+
+    const after = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(after.protocolFee, before.protocolFee + 1);
+    assert.isNull(await ctx.provider.connection.getAccountInfo(queuedChangePda(id)));
+
+    await execute(
+      await queue({ updateRiskParams: { 0: { minimumCollateralRatio: null, protocolFee: before.protocolFee } } })
+    );
+  });
+
+  it("Guardian can cancel a queued change", async () => {
+    const before = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    const id = await queue({
+      updateRiskParams: { 0: { minimumCollateralRatio: null, protocolFee: before.protocolFee + 1 } },
+    });
+
+    await ctx.protocolProgram.methods
+      .cancelChange({ id })
+      .accounts({
+        authority: ctx.admin.publicKey,
+        state: ctx.protocolState,
+        queuedChange: queuedChangePda(id),
+        proposer: ctx.admin.publicKey,
+      })
+      .rpc();
+
+    const after = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(after.protocolFee, before.protocolFee);
+    assert.isNull(await ctx.provider.connection.getAccountInfo(queuedChangePda(id)));
+  });
+
+  it("Only the owner can queue a timelock delay change", async () => {
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    try {
+      await ctx.protocolProgram.methods
+        .queueChange({ change: { setTimelockDelay: { 0: new BN(3600) } } })
+        .accounts({
+          proposer: stranger.publicKey,
+          state: ctx.protocolState,
+          queuedChange: queuedChangePda(state.nextChangeId),
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Stranger should not be able to change the delay");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
+  });
+
+  it("Direct risk parameter updates are rejected once a delay is set", async () => {
+    await execute(await queue({ setTimelockDelay: { 0: new BN(DELAY_SECS) } }));
+
+    try {
+      await ctx.protocolProgram.methods
+        .updateRiskParams({ minimumCollateralRatio: null, protocolFee: 5 })
+        .accounts({ authority: ctx.admin.publicKey, state: ctx.protocolState })
+        .rpc();
+      assert.fail("update_risk_params should need the timelock");
+    } catch (error: any) {
+      expect(error.message).to.include("TimelockActive");
+    }
+  });
+
+  it("Queued change waits for the delay, then restores a zero delay", async () => {
+    const id = await queue({ setTimelockDelay: { 0: new BN(0) } });
+
+    try {
+      await execute(id);
+      assert.fail("Change should not be executable before its eta");
+    } catch (error: any) {
+      expect(error.message).to.include("ChangeNotReady");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, (DELAY_SECS + 2) * 1000));
+    await execute(id);
+
+    const state = await ctx.protocolProgram.account.stateAccount.fetch(ctx.protocolState);
+    assert.equal(state.timelockDelay.toNumber(), 0);
+  });
+});
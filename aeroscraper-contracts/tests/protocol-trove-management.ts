@@ -14,6 +14,7 @@ import {
 import { assert, expect } from "chai";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
 import { loadTestUsers } from "./test-utils";
+import { deriveOracleAssetPda } from "./oracle-registry";
 
 // Helper function to get neighbor hints for trove mutations
 async function getNeighborHints(
@@ -108,6 +109,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
   let collateralMint: PublicKey;
   let protocolState: PublicKey;
   let oracleState: PublicKey;
+  let oracleAsset: PublicKey; // Oracle asset config PDA for SOL
   let feeState: PublicKey;
   let protocolVault: PublicKey;
   let protocolStablecoinVault: PublicKey;
@@ -187,6 +189,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       oracleProgram.programId
     );
     oracleState = oracleStatePDA;
+    oracleAsset = deriveOracleAssetPda("SOL", oracleProgram.programId);
 
     try {
       const existingState = await oracleProgram.account.oracleStateAccount.fetch(oracleState);
@@ -345,9 +348,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: user3StablecoinAccount, // Use user account for now
           feeAddress1TokenAccount: user3StablecoinAccount, // Use user account for now
@@ -441,9 +446,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: user4StablecoinAccount,
           feeAddress1TokenAccount: user4StablecoinAccount,
@@ -492,9 +499,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
             liquidityThreshold: liquidityThresholdPda,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feeState,
             stabilityPoolTokenAccount: user4StablecoinAccount,
             feeAddress1TokenAccount: user4StablecoinAccount,
@@ -609,9 +618,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -662,6 +673,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
           totalCollateralAmount: totalCollateralPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           user: testUser.publicKey,
@@ -780,9 +792,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -833,9 +847,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           totalCollateralAmount: totalCollateralPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -953,9 +969,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -993,6 +1011,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
           totalCollateralAmount: totalCollateralPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           user: testUser.publicKey,
@@ -1107,9 +1126,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -1144,6 +1165,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
           totalCollateralAmount: totalCollateralPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           user: testUser.publicKey,
@@ -1255,9 +1277,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -1286,6 +1310,8 @@ describe("Protocol Contract - Trove Management Tests", () => {
           protocolCollateralVault: protocolVault,
           stableCoinMint: stablecoinMint,
           totalCollateralAmount: totalCollateralPda,
+          oracleProgram: oracleProgram.programId,
+          oracleAsset: oracleAsset,
           user: testUser.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -1399,9 +1425,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -1452,6 +1480,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
           totalCollateralAmount: totalCollateralPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           user: testUser.publicKey,
@@ -1570,9 +1599,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -1618,6 +1649,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
             totalCollateralAmount: totalCollateralPda,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             user: testUser.publicKey,
@@ -1733,9 +1765,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
             liquidityThreshold: liquidityThresholdPda,
             oracleProgram: oracleProgram.programId,
             oracleState: oracleState,
+            oracleAsset: oracleAsset,
             pythPriceAccount: PYTH_ORACLE_ADDRESS,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
             feesProgram: feesProgram.programId,
+            feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
             feesState: feeState,
             stabilityPoolTokenAccount: testStablecoinAccount,
             feeAddress1TokenAccount: testStablecoinAccount,
@@ -1850,9 +1884,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: oracleAsset,
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -1881,6 +1917,8 @@ describe("Protocol Contract - Trove Management Tests", () => {
             protocolCollateralVault: protocolVault,
             stableCoinMint: stablecoinMint,
             totalCollateralAmount: totalCollateralPda,
+            oracleProgram: oracleProgram.programId,
+            oracleAsset: oracleAsset,
             user: testUser.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -1992,9 +2030,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
           liquidityThreshold: liquidityThresholdPda,
           oracleProgram: oracleProgram.programId,
           oracleState: oracleState,
+          oracleAsset: deriveOracleAssetPda("USDC", oracleProgram.programId),
           pythPriceAccount: PYTH_ORACLE_ADDRESS,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
           feesProgram: feesProgram.programId,
+          feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], feesProgram.programId)[0],
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
           feeAddress1TokenAccount: testStablecoinAccount,
@@ -20,6 +20,7 @@ import {
 } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
+import { deriveOracleAssetPda } from "./oracle-registry";
 
 // Constants
 export const PYTH_ORACLE_ADDRESS = new PublicKey("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"); // Oracle contract address
@@ -30,6 +31,40 @@ export const MIN_LOAN_AMOUNT = SCALE_FACTOR; // 1 aUSD
 export const MIN_COLLATERAL_RATIO = 115; // 115%
 export const LIQUIDATION_THRESHOLD = 110; // 110%
 
+// Oracle asset config (set_data) defaults: a single Pyth push feed with every optional guard off
+export const SOL_PRICE_ID = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+export const SINGLE_PRICE_SOURCE = { extraSources: [], minSources: 1, maxDeviationBps: 0 };
+export const SPOT_PRICE_POLICY = {
+  borrow: { spot: {} },
+  withdraw: { spot: {} },
+  liquidation: { spot: {} },
+  twapWindowSecs: 0,
+};
+export const NO_PRICE_BOUNDS = { minPrice: new BN(0), maxPrice: new BN(0), maxChangeBps: 0, changeWindowSecs: 0 };
+export const NOT_COMPOSITE = { op: { none: {} }, denom: "" };
+
+// set_data params for an asset: the identifying fields plus any overrides of the defaults above.
+// New asset config fields get their default here instead of in every test
+export function oracleAssetParams(asset: {
+  denom: string;
+  decimal: number;
+  priceId: string;
+  pythPriceAccount: PublicKey;
+  [field: string]: any;
+}) {
+  return {
+    maxStalenessSecs: 60,
+    maxConfidenceBps: 200,
+    source: { pythPush: {} },
+    ...SINGLE_PRICE_SOURCE,
+    pricePolicy: SPOT_PRICE_POLICY,
+    priceBounds: NO_PRICE_BOUNDS,
+    composite: NOT_COMPOSITE,
+    pegBandBps: 0,
+    ...asset,
+  };
+}
+
 // Load fixed test user keypairs (avoids Node PDA collision on devnet)
 export function loadFixedKeypair(filename: string): Keypair {
   const keypairPath = path.join(__dirname, "..", "keys", filename);
@@ -65,6 +100,7 @@ export interface TestContext {
   collateralMint: PublicKey;
   protocolState: PublicKey;
   oracleState: PublicKey;
+  oracleAsset: PublicKey; // Oracle asset config PDA for SOL_DENOM
   feeState: PublicKey;
   // REMOVED: sortedTrovesState (obsolete with off-chain sorting)
   // Fee-related token accounts (ATAs, not PDAs)
@@ -378,6 +414,7 @@ export async function setupTestEnvironment(): Promise<TestContext> {
     collateralMint,
     protocolState: protocolStatePDA,
     oracleState: oracleStatePDA,
+    oracleAsset: deriveOracleAssetPda(SOL_DENOM, oracleProgram.programId),
     feeState: feesStatePDA,
     stabilityPoolTokenAccount,
     feeAddress1TokenAccount,
@@ -482,9 +519,11 @@ export async function openTroveForUser(
       stableCoinMint: ctx.stablecoinMint,
       oracleProgram: ctx.oracleProgram.programId,
       oracleState: ctx.oracleState,
+      oracleAsset: ctx.oracleAsset,
       pythPriceAccount: SOL_PRICE_FEED,
       clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       feesProgram: ctx.feesProgram.programId,
+      feesEventAuthority: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], ctx.feesProgram.programId)[0],
       feesState: ctx.feeState,
       stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
       feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,